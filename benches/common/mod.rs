@@ -0,0 +1,210 @@
+/*
+ * Copyright © 2020-present Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Representative corpora of input texts shared by the benchmarks and their tests.
+
+use std::str::FromStr;
+
+use strum_macros::EnumIter;
+
+use lingua::Language;
+
+const TWEETS: &[(&str, &str)] = &[
+    (
+        "English",
+        "Just finished my first marathon, legs are jelly but so worth it!",
+    ),
+    (
+        "German",
+        "Heute Abend endlich wieder Fußball mit den Jungs, freue mich riesig!",
+    ),
+    (
+        "French",
+        "Quelqu'un sait si le métro fonctionne ce soir après la grève ?",
+    ),
+    (
+        "Spanish",
+        "No puedo creer que ya sea viernes otra vez, esta semana voló.",
+    ),
+    (
+        "Italian",
+        "Stasera pizza e film sul divano, non ho proprio voglia di uscire.",
+    ),
+    (
+        "Portuguese",
+        "Acabei de ver o jogo e ainda estou sem palavras, que final!",
+    ),
+    (
+        "Dutch",
+        "Eindelijk weekend, tijd om de tuin eens goed op te ruimen.",
+    ),
+    (
+        "Turkish",
+        "Bu sabah kahvemi içmeden hiçbir şey yapamıyorum, yardım edin!",
+    ),
+    (
+        "Polish",
+        "Dzisiaj w końcu świeci słońce, idę na długi spacer z psem.",
+    ),
+    (
+        "Swedish",
+        "Äntligen fredag! Dags för fika och en lång promenad i parken.",
+    ),
+];
+
+const PARAGRAPHS: &[(&str, &str)] = &[
+    (
+        "English",
+        "The old lighthouse had stood on the cliff for more than two hundred years. \
+        Generations of keepers had climbed its narrow staircase every evening to light \
+        the lamp, and sailors had trusted its beam to guide them safely past the rocks. \
+        When the light was finally automated, the village decided to turn the keeper's \
+        cottage into a small museum about the history of the coast.",
+    ),
+    (
+        "German",
+        "Der alte Leuchtturm stand seit mehr als zweihundert Jahren auf der Klippe. \
+        Generationen von Wärtern waren jeden Abend die schmale Treppe hinaufgestiegen, \
+        um die Lampe zu entzünden, und die Seeleute hatten sich darauf verlassen, dass \
+        sein Licht sie sicher an den Felsen vorbeiführte. Als das Licht schließlich \
+        automatisiert wurde, beschloss das Dorf, das Haus des Wärters in ein kleines \
+        Museum über die Geschichte der Küste zu verwandeln.",
+    ),
+    (
+        "French",
+        "Le vieux phare se dressait sur la falaise depuis plus de deux cents ans. \
+        Des générations de gardiens avaient gravi chaque soir son étroit escalier pour \
+        allumer la lampe, et les marins comptaient sur sa lumière pour éviter les \
+        rochers. Lorsque le phare fut enfin automatisé, le village décida de transformer \
+        la maison du gardien en un petit musée consacré à l'histoire de la côte.",
+    ),
+    (
+        "Spanish",
+        "El viejo faro llevaba más de doscientos años en lo alto del acantilado. \
+        Generaciones de fareros habían subido cada noche su estrecha escalera para \
+        encender la lámpara, y los marineros confiaban en su luz para pasar sin peligro \
+        junto a las rocas. Cuando por fin se automatizó, el pueblo decidió convertir la \
+        casa del farero en un pequeño museo sobre la historia de la costa.",
+    ),
+];
+
+const CJK: &[(&str, &str)] = &[
+    (
+        "Chinese",
+        "今天天气很好，我们一起去公园散步，然后在附近的餐厅吃午饭。",
+    ),
+    ("Chinese", "这本书详细介绍了中国古代历史和文化的发展过程。"),
+    (
+        "Japanese",
+        "昨日は友達と一緒に新しいカフェに行って、美味しいケーキを食べました。",
+    ),
+    (
+        "Japanese",
+        "このプログラムは大量のデータを短時間で処理することができます。",
+    ),
+    (
+        "Korean",
+        "오늘은 날씨가 정말 좋아서 친구들과 함께 한강 공원에 산책을 갔어요.",
+    ),
+    (
+        "Korean",
+        "이 책은 한국의 전통 문화와 역사에 대해 자세히 설명하고 있습니다.",
+    ),
+];
+
+const CYRILLIC: &[(&str, &str)] = &[
+    (
+        "Russian",
+        "Вчера вечером мы долго гуляли по набережной и разговаривали о будущем.",
+    ),
+    (
+        "Ukrainian",
+        "Сьогодні вранці я пішов до крамниці, щоб купити свіжого хліба та молока.",
+    ),
+    (
+        "Bulgarian",
+        "Днес времето е много хубаво и решихме да отидем на разходка в планината.",
+    ),
+    (
+        "Belarusian",
+        "Учора ўвечары мы доўга гулялі па горадзе і размаўлялі пра жыццё.",
+    ),
+    (
+        "Serbian",
+        "Јуче смо цео дан провели у парку са децом и пријатељима.",
+    ),
+    (
+        "Macedonian",
+        "Утре ќе одиме на излет кај езерото со целото семејство.",
+    ),
+    (
+        "Kazakh",
+        "Бүгін ауа райы өте жақсы, біз саябаққа серуендеуге бардық.",
+    ),
+    (
+        "Mongolian",
+        "Өнөөдөр цаг агаар маш сайхан байгаа тул бид цэцэрлэгт хүрээлэнд алхахаар явлаа.",
+    ),
+];
+
+/// This enum specifies representative corpora of input texts for benchmarking
+/// the [LanguageDetector](lingua::LanguageDetector), so that performance regressions
+/// can be tracked from release to release with the same texts.
+#[derive(Clone, Copy, Debug, EnumIter, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub enum BenchmarkCorpus {
+    /// Short social media posts in languages written with the Latin alphabet
+    Tweets,
+    /// Paragraphs of several sentences in languages written with the Latin alphabet
+    Paragraphs,
+    /// Sentences in Chinese, Japanese and Korean
+    Cjk,
+    /// Sentences in languages written with the Cyrillic alphabet
+    Cyrillic,
+}
+
+impl BenchmarkCorpus {
+    /// Returns the texts of this corpus together with their languages. Texts in languages
+    /// which are not included in the current build of the library are left out.
+    pub fn texts(&self) -> Vec<(Language, &'static str)> {
+        let texts = match self {
+            BenchmarkCorpus::Tweets => TWEETS,
+            BenchmarkCorpus::Paragraphs => PARAGRAPHS,
+            BenchmarkCorpus::Cjk => CJK,
+            BenchmarkCorpus::Cyrillic => CYRILLIC,
+        };
+
+        texts
+            .iter()
+            .filter_map(|(language, text)| {
+                Language::from_str(language)
+                    .ok()
+                    .map(|language| (language, *text))
+            })
+            .collect()
+    }
+
+    /// Returns the distinct languages of the texts of this corpus in sorted order.
+    pub fn languages(&self) -> Vec<Language> {
+        let mut languages = self
+            .texts()
+            .into_iter()
+            .map(|(language, _)| language)
+            .collect::<Vec<_>>();
+        languages.sort();
+        languages.dedup();
+        languages
+    }
+}
@@ -0,0 +1,108 @@
+/*
+ * Copyright © 2020-present Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+mod common;
+
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use strum::IntoEnumIterator;
+
+use common::BenchmarkCorpus;
+use lingua::LanguageDetectorBuilder;
+
+fn benchmark_detection_of_corpora(c: &mut Criterion) {
+    let high_accuracy_detector = LanguageDetectorBuilder::from_all_languages()
+        .with_preloaded_language_models()
+        .build();
+    let low_accuracy_detector = LanguageDetectorBuilder::from_all_languages()
+        .with_low_accuracy_mode()
+        .with_preloaded_language_models()
+        .build();
+
+    let mut group = c.benchmark_group("detect language of corpus");
+
+    for corpus in BenchmarkCorpus::iter() {
+        let texts = corpus.texts();
+
+        group.bench_with_input(
+            BenchmarkId::new("high accuracy mode", format!("{corpus:?}")),
+            &texts,
+            |bencher, texts| {
+                bencher.iter(|| {
+                    for (_, text) in texts.iter() {
+                        black_box(high_accuracy_detector.detect_language_of(*text));
+                    }
+                })
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("low accuracy mode", format!("{corpus:?}")),
+            &texts,
+            |bencher, texts| {
+                bencher.iter(|| {
+                    for (_, text) in texts.iter() {
+                        black_box(low_accuracy_detector.detect_language_of(*text));
+                    }
+                })
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("confidence values", format!("{corpus:?}")),
+            &texts,
+            |bencher, texts| {
+                bencher.iter(|| {
+                    for (_, text) in texts.iter() {
+                        black_box(high_accuracy_detector.compute_language_confidence_values(*text));
+                    }
+                })
+            },
+        );
+    }
+
+    group.finish();
+}
+
+fn benchmark_loading_models_of_corpora(c: &mut Criterion) {
+    let mut group = c.benchmark_group("load language models of corpus");
+    group.sample_size(10);
+
+    for corpus in BenchmarkCorpus::iter() {
+        let languages = corpus.languages();
+
+        group.bench_with_input(
+            BenchmarkId::from_parameter(format!("{corpus:?}")),
+            &languages,
+            |bencher, languages| {
+                bencher.iter(|| {
+                    let detector = LanguageDetectorBuilder::from_languages(languages)
+                        .with_preloaded_language_models()
+                        .build();
+                    detector.unload_language_models();
+                })
+            },
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    benchmark_detection_of_corpora,
+    benchmark_loading_models_of_corpora
+);
+criterion_main!(benches);
@@ -0,0 +1,84 @@
+/*
+ * Copyright © 2020-present Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::fs;
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::Path;
+
+use brotli::{CompressorWriter, Decompressor};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use tempfile::TempDir;
+
+use lingua::Language::{English, German};
+use lingua::{encode_binary_model, LanguageDetectorBuilder, LanguageModel};
+
+fn convert_to_binary_models(json_directory: &Path, binary_directory: &Path) {
+    for entry in fs::read_dir(json_directory).unwrap() {
+        let path = entry.unwrap().path();
+        let file_name = path.file_name().unwrap().to_str().unwrap();
+        let mut json = String::new();
+
+        Decompressor::new(File::open(&path).unwrap(), 4096)
+            .read_to_string(&mut json)
+            .unwrap();
+
+        let model = LanguageModel::from_json(&json).unwrap();
+        let binary_file_name = file_name.replace(".json.br", ".bin.br");
+        let mut writer = CompressorWriter::new(
+            File::create(binary_directory.join(binary_file_name)).unwrap(),
+            4096,
+            11,
+            22,
+        );
+
+        writer.write_all(&encode_binary_model(&model)).unwrap();
+    }
+}
+
+fn benchmark_loading_of_language_models(c: &mut Criterion) {
+    let json_directory = Path::new(env!("CARGO_MANIFEST_DIR")).join("language-models/en/models");
+    let binary_directory = TempDir::new().unwrap();
+
+    convert_to_binary_models(&json_directory, binary_directory.path());
+
+    let mut group = c.benchmark_group("load language models");
+
+    group.sample_size(10);
+
+    for (format, directory) in [
+        ("json", json_directory.as_path()),
+        ("binary", binary_directory.path()),
+    ] {
+        group.bench_with_input(
+            BenchmarkId::new("english", format),
+            directory,
+            |bencher, directory| {
+                bencher.iter(|| {
+                    LanguageDetectorBuilder::from_languages(&[English, German])
+                        .with_custom_model_path(English, directory)
+                        .build()
+                        .warm_up(&[English])
+                })
+            },
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, benchmark_loading_of_language_models);
+criterion_main!(benches);
@@ -0,0 +1,93 @@
+/*
+ * Copyright © 2020-present Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+/*
+ * The stable C ABI of Lingua.
+ *
+ * All symbols carry the major ABI version in their names, so libraries of different
+ * major versions can be loaded into the same process. Within a major version, symbols
+ * are only ever added. Check the version of a dynamically loaded library with
+ * LINGUA_ABI_IS_COMPATIBLE(lingua_v1_abi_version()) before calling any other function.
+ */
+
+#ifndef LINGUA_H
+#define LINGUA_H
+
+#include <stdbool.h>
+#include <stdint.h>
+
+#define LINGUA_ABI_VERSION_MAJOR 1
+#define LINGUA_ABI_VERSION_MINOR 1
+#define LINGUA_ABI_VERSION_PATCH 0
+
+#define LINGUA_ABI_IS_COMPATIBLE(version) \
+    ((version).major == LINGUA_ABI_VERSION_MAJOR && (version).minor >= LINGUA_ABI_VERSION_MINOR)
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef struct LinguaDetector LinguaDetector;
+
+typedef struct LinguaAbiVersion {
+    uint32_t major;
+    uint32_t minor;
+    uint32_t patch;
+} LinguaAbiVersion;
+
+/* Returns the version of the C ABI implemented by the loaded library. */
+LinguaAbiVersion lingua_v1_abi_version(void);
+
+/*
+ * Creates a language detector for the languages given as comma-separated ISO 639-1 codes,
+ * e.g. "en,de,fr", or for all spoken languages if iso_codes is NULL. Returns NULL if the
+ * codes are invalid or fewer than two languages are given.
+ * The detector must be released with lingua_v1_detector_free().
+ */
+LinguaDetector *lingua_v1_detector_new(const char *iso_codes, bool low_accuracy_mode);
+
+/* Releases a language detector. Passing NULL is allowed. */
+void lingua_v1_detector_free(LinguaDetector *detector);
+
+/*
+ * Clears all language models loaded by the given detector and frees the memory consumed
+ * by them. The detector remains usable and reloads the models on demand.
+ * Passing NULL is allowed. Available since ABI version 1.1.
+ */
+void lingua_v1_detector_unload_language_models(const LinguaDetector *detector);
+
+/*
+ * Detects the language of the given UTF-8 encoded text and returns its ISO 639-1 code,
+ * or NULL if the language cannot be reliably detected. The returned string is statically
+ * allocated and must not be freed.
+ */
+const char *lingua_v1_detect_language_of(const LinguaDetector *detector, const char *text);
+
+/*
+ * Computes the confidence value between 0.0 and 1.0 for the given UTF-8 encoded text to be
+ * written in the language given as ISO 639-1 code. Returns -1.0 if the arguments are invalid.
+ */
+double lingua_v1_compute_language_confidence(
+    const LinguaDetector *detector,
+    const char *text,
+    const char *iso_code
+);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* LINGUA_H */
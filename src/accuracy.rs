@@ -0,0 +1,135 @@
+/*
+ * Copyright © 2020-present Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use strum_macros::EnumIter;
+
+use crate::language::Language;
+
+const AGGREGATED_ACCURACY_VALUES: &str =
+    include_str!("../accuracy-reports/aggregated-accuracy-values.csv");
+
+const ACCURACY_COLUMNS: [&str; 3] = [
+    "single-words-lingua-high",
+    "word-pairs-lingua-high",
+    "sentences-lingua-high",
+];
+
+static EXPECTED_ACCURACIES: Lazy<HashMap<Language, [f64; 3]>> =
+    Lazy::new(|| parse_accuracy_values(AGGREGATED_ACCURACY_VALUES));
+
+/// This enum specifies the length classes of input texts for which
+/// the accuracy of *Lingua* has been measured.
+#[derive(
+    Clone, Copy, Debug, Serialize, Deserialize, EnumIter, Eq, PartialEq, Hash, Ord, PartialOrd,
+)]
+pub enum TextLength {
+    /// Single words with an average length of 8 characters
+    Short,
+    /// Word pairs with an average length of 16 characters
+    Medium,
+    /// Sentences with an average length of about 100 characters
+    Long,
+}
+
+impl TextLength {
+    /// Returns the length class of the given text, based on its number of characters
+    /// without surrounding whitespace. Texts of up to 12 characters are considered short,
+    /// texts of up to 30 characters are considered medium and longer ones are considered long.
+    pub fn of(text: &str) -> Self {
+        match text.trim().chars().count() {
+            0..=12 => TextLength::Short,
+            13..=30 => TextLength::Medium,
+            _ => TextLength::Long,
+        }
+    }
+
+    fn column_index(&self) -> usize {
+        match self {
+            TextLength::Short => 0,
+            TextLength::Medium => 1,
+            TextLength::Long => 2,
+        }
+    }
+}
+
+/// Returns the accuracy measured for the given language and text length in high accuracy mode
+/// as a value between 0.0 and 1.0, or [None] if no accuracy report exists for the language.
+pub(crate) fn expected_accuracy(language: &Language, text_length: TextLength) -> Option<f64> {
+    EXPECTED_ACCURACIES
+        .get(language)
+        .map(|accuracies| accuracies[text_length.column_index()])
+}
+
+fn parse_accuracy_values(csv: &str) -> HashMap<Language, [f64; 3]> {
+    let mut lines = csv.lines();
+    let header = lines.next().unwrap().split(',').collect::<Vec<_>>();
+    let column_indices = ACCURACY_COLUMNS.map(|column| {
+        header
+            .iter()
+            .position(|it| *it == column)
+            .unwrap_or_else(|| panic!("accuracy column '{column}' is missing"))
+    });
+
+    lines
+        .filter_map(|line| {
+            let values = line.split(',').collect::<Vec<_>>();
+            let language = Language::from_str(values[0]).ok()?;
+            let accuracies = column_indices.map(|i| values[i].parse::<f64>().unwrap() / 100.0);
+            Some((language, accuracies))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::language::Language::*;
+
+    #[test]
+    fn assert_every_language_has_an_expected_accuracy() {
+        for language in Language::all() {
+            assert!(
+                expected_accuracy(&language, TextLength::Long).is_some(),
+                "{language:?} lacks an expected accuracy"
+            );
+        }
+    }
+
+    #[test]
+    fn assert_expected_accuracies_are_parsed_correctly() {
+        assert_eq!(expected_accuracy(&Afrikaans, TextLength::Short), Some(0.58));
+        assert_eq!(
+            expected_accuracy(&Afrikaans, TextLength::Medium),
+            Some(0.80)
+        );
+        assert_eq!(expected_accuracy(&Afrikaans, TextLength::Long), Some(0.96));
+    }
+
+    #[test]
+    fn assert_text_length_is_classified_correctly() {
+        assert_eq!(TextLength::of("  house  "), TextLength::Short);
+        assert_eq!(TextLength::of("languages are awesome"), TextLength::Medium);
+        assert_eq!(
+            TextLength::of("Languages are awesome, but detecting them is hard."),
+            TextLength::Long
+        );
+    }
+}
@@ -0,0 +1,135 @@
+/*
+ * Copyright © 2020-present Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::language::Language;
+
+/// This struct describes the outcome of verifying the languages of aligned segment pairs
+/// against their declared languages, as returned by
+/// [LanguageDetector::verify_aligned_segments](crate::LanguageDetector::verify_aligned_segments).
+#[derive(Clone, Debug, PartialEq)]
+pub struct AlignmentReport {
+    pub(crate) source_language: Language,
+    pub(crate) target_language: Language,
+    pub(crate) segment_pairs: Vec<SegmentPairLanguages>,
+}
+
+/// This struct describes the languages detected for the source and target side
+/// of a single aligned segment pair.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SegmentPairLanguages {
+    pub(crate) source_language: Option<Language>,
+    pub(crate) target_language: Option<Language>,
+    pub(crate) is_source_mismatch: bool,
+    pub(crate) is_target_mismatch: bool,
+}
+
+impl AlignmentReport {
+    /// Returns the declared language of the source segments.
+    pub fn source_language(&self) -> Language {
+        self.source_language
+    }
+    /// Returns the declared language of the target segments.
+    pub fn target_language(&self) -> Language {
+        self.target_language
+    }
+    /// Returns the detected languages of each segment pair in the order of the pairs.
+    pub fn segment_pairs(&self) -> &[SegmentPairLanguages] {
+        &self.segment_pairs
+    }
+    /// Returns the indices of those segment pairs where the language detected on
+    /// either side differs from the declared language.
+    pub fn mismatched_pair_indices(&self) -> Vec<usize> {
+        self.segment_pairs
+            .iter()
+            .enumerate()
+            .filter(|(_, pair)| pair.is_mismatch())
+            .map(|(index, _)| index)
+            .collect()
+    }
+}
+
+impl SegmentPairLanguages {
+    /// Returns the language detected for the source segment,
+    /// or [None] if it cannot be reliably detected.
+    pub fn source_language(&self) -> Option<Language> {
+        self.source_language
+    }
+    /// Returns the language detected for the target segment,
+    /// or [None] if it cannot be reliably detected.
+    pub fn target_language(&self) -> Option<Language> {
+        self.target_language
+    }
+    /// Returns `true` if the language detected for the source segment
+    /// differs from the declared source language.
+    pub fn is_source_mismatch(&self) -> bool {
+        self.is_source_mismatch
+    }
+    /// Returns `true` if the language detected for the target segment
+    /// differs from the declared target language. This is the case, for instance,
+    /// if the source segment has been copied to the target without translating it.
+    pub fn is_target_mismatch(&self) -> bool {
+        self.is_target_mismatch
+    }
+    /// Returns `true` if the language detected on either side of the pair
+    /// differs from the declared language.
+    pub fn is_mismatch(&self) -> bool {
+        self.is_source_mismatch || self.is_target_mismatch
+    }
+}
+
+/// Compares the detected languages of a segment pair with the declared ones.
+/// Segments whose language cannot be reliably detected, such as numbers or
+/// product codes, are not considered mismatches.
+pub(crate) fn compare_segment_pair_languages(
+    detected_languages: (Option<Language>, Option<Language>),
+    declared_languages: (Language, Language),
+) -> SegmentPairLanguages {
+    let (source_language, target_language) = detected_languages;
+    let is_mismatch = |detected: Option<Language>, declared| {
+        detected.is_some_and(|language| language != declared)
+    };
+
+    SegmentPairLanguages {
+        source_language,
+        target_language,
+        is_source_mismatch: is_mismatch(source_language, declared_languages.0),
+        is_target_mismatch: is_mismatch(target_language, declared_languages.1),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::language::Language::{English, French, German};
+
+    #[test]
+    fn assert_segment_pair_languages_are_compared_correctly() {
+        let matching =
+            compare_segment_pair_languages((Some(English), Some(German)), (English, German));
+        let untranslated =
+            compare_segment_pair_languages((Some(English), Some(English)), (English, German));
+        let swapped =
+            compare_segment_pair_languages((Some(German), Some(French)), (English, German));
+        let undetected = compare_segment_pair_languages((None, None), (English, German));
+
+        assert!(!matching.is_mismatch());
+        assert!(!untranslated.is_source_mismatch());
+        assert!(untranslated.is_target_mismatch());
+        assert!(swapped.is_source_mismatch());
+        assert!(swapped.is_target_mismatch());
+        assert!(!undetected.is_mismatch());
+    }
+}
@@ -15,21 +15,31 @@
  */
 
 use std::collections::HashMap;
+use std::mem::size_of;
 
 use ahash::AHashSet;
 use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
 use strum::IntoEnumIterator;
 use strum_macros::EnumIter;
 
 use crate::language::Language;
 
-#[derive(EnumIter, Eq, PartialEq, Hash)]
-pub(crate) enum Alphabet {
+/// This enum specifies the writing systems used by the languages supported by *Lingua*.
+///
+/// Some alphabets are recognized although no supported language is written in them yet,
+/// such as Ethiopic, Kannada, Lao, Malayalam, Myanmar or Oriya. Texts written in such
+/// alphabets are not assigned to any language.
+#[derive(
+    Clone, Copy, Debug, Serialize, Deserialize, EnumIter, Eq, PartialEq, Hash, Ord, PartialOrd,
+)]
+pub enum Alphabet {
     Arabic,
     Armenian,
     Bengali,
     Cyrillic,
     Devanagari,
+    Ethiopic,
     Georgian,
     Greek,
     Gujarati,
@@ -38,23 +48,115 @@ pub(crate) enum Alphabet {
     Hangul,
     Hebrew,
     Hiragana,
+    Kannada,
     Katakana,
+    Lao,
     Latin,
+    Malayalam,
+    Myanmar,
+    Oriya,
     Tamil,
     Telugu,
     Thai,
 }
 
+/// This enum specifies the directions in which the characters of an [Alphabet] are written.
+#[derive(
+    Clone, Copy, Debug, Serialize, Deserialize, EnumIter, Eq, PartialEq, Hash, Ord, PartialOrd,
+)]
+pub enum WritingDirection {
+    LeftToRight,
+    RightToLeft,
+}
+
 impl Alphabet {
-    pub fn matches(&self, text: &str) -> bool {
+    /// Returns the direction in which this alphabet is written horizontally.
+    pub fn writing_direction(&self) -> WritingDirection {
+        match self {
+            Alphabet::Arabic | Alphabet::Hebrew => WritingDirection::RightToLeft,
+            _ => WritingDirection::LeftToRight,
+        }
+    }
+
+    /// Returns `true` if every character of the given text belongs to this alphabet.
+    /// Punctuation such as hyphens or apostrophes therefore causes a mismatch.
+    pub fn matches_all(&self, text: &str) -> bool {
         self.char_set().is_match(text)
     }
 
+    /// Returns `true` if at least one character of the given text belongs to this alphabet.
+    pub fn matches_any(&self, text: &str) -> bool {
+        self.char_set().is_any_match(text)
+    }
+
+    /// Returns the fraction of characters of the given text that belong to this alphabet,
+    /// as a value between 0.0 and 1.0. For empty text, 0.0 is returned.
+    pub fn match_ratio(&self, text: &str) -> f64 {
+        self.char_set().match_ratio(text)
+    }
+
+    /// Returns `true` if the given character belongs to this alphabet.
     pub fn matches_char(&self, ch: char) -> bool {
         self.char_set().is_char_match(ch)
     }
 
-    pub fn all_supporting_single_language() -> HashMap<Alphabet, Language> {
+    /// Returns the distribution of the alphabets the characters of the given text belong to.
+    ///
+    /// Each alphabet occurring in the text is returned together with the fraction of those
+    /// characters of the text which belong to any alphabet, so whitespace, digits and
+    /// punctuation are not taken into account and the fractions sum up to 1.0. The result
+    /// is sorted by descending fraction. For text without any such characters, an empty
+    /// vector is returned. No language models are needed for this.
+    ///
+    /// ```
+    /// use lingua::Alphabet;
+    ///
+    /// assert_eq!(
+    ///     Alphabet::detect("Привет, world!"),
+    ///     vec![(Alphabet::Cyrillic, 6.0 / 11.0), (Alphabet::Latin, 5.0 / 11.0)]
+    /// );
+    /// assert!(Alphabet::detect("123 !?").is_empty());
+    /// ```
+    pub fn detect(text: &str) -> Vec<(Alphabet, f64)> {
+        let mut alphabet_counts = HashMap::<Alphabet, u32>::new();
+        let mut char_count = 0;
+
+        for alphabet in text.chars().filter_map(Alphabet::of_char) {
+            *alphabet_counts.entry(alphabet).or_insert(0) += 1;
+            char_count += 1;
+        }
+
+        let mut distribution = alphabet_counts
+            .into_iter()
+            .map(|(alphabet, count)| (alphabet, count as f64 / char_count as f64))
+            .collect::<Vec<_>>();
+
+        distribution.sort_by(|(first_alphabet, first), (second_alphabet, second)| {
+            second
+                .total_cmp(first)
+                .then(first_alphabet.cmp(second_alphabet))
+        });
+
+        distribution
+    }
+
+    /// Returns the alphabet the given character belongs to, or [None] if it belongs
+    /// to none of the supported alphabets. The alphabets do not share any characters,
+    /// so a single binary search over their character ranges is sufficient.
+    pub(crate) fn of_char(ch: char) -> Option<Alphabet> {
+        let index = ALPHABET_RANGES.partition_point(|(start, _, _)| *start <= ch);
+        if index == 0 {
+            return None;
+        }
+        let (_, end, alphabet) = ALPHABET_RANGES[index - 1];
+        if ch <= end {
+            Some(alphabet)
+        } else {
+            None
+        }
+    }
+
+    pub(crate) fn all_supporting_single_language() -> HashMap<Alphabet, Language> {
         let mut alphabets = HashMap::new();
         for alphabet in Alphabet::iter() {
             let supported_languages = alphabet.supported_languages();
@@ -65,6 +167,12 @@ impl Alphabet {
         alphabets
     }
 
+    /// Returns the approximate number of bytes occupied by this alphabet's character table,
+    /// or [None] if the table has not been initialized yet.
+    pub(crate) fn memory_usage(&self) -> Option<usize> {
+        Lazy::get(self.char_set()).map(|char_set| char_set.memory_usage())
+    }
+
     fn supported_languages(&self) -> Vec<Language> {
         let mut languages = vec![];
         for language in Language::iter() {
@@ -82,6 +190,7 @@ impl Alphabet {
             Alphabet::Bengali => &BENGALI,
             Alphabet::Cyrillic => &CYRILLIC,
             Alphabet::Devanagari => &DEVANAGARI,
+            Alphabet::Ethiopic => &ETHIOPIC,
             Alphabet::Georgian => &GEORGIAN,
             Alphabet::Greek => &GREEK,
             Alphabet::Gujarati => &GUJARATI,
@@ -90,8 +199,13 @@ impl Alphabet {
             Alphabet::Hangul => &HANGUL,
             Alphabet::Hebrew => &HEBREW,
             Alphabet::Hiragana => &HIRAGANA,
+            Alphabet::Kannada => &KANNADA,
             Alphabet::Katakana => &KATAKANA,
+            Alphabet::Lao => &LAO,
             Alphabet::Latin => &LATIN,
+            Alphabet::Malayalam => &MALAYALAM,
+            Alphabet::Myanmar => &MYANMAR,
+            Alphabet::Oriya => &ORIYA,
             Alphabet::Tamil => &TAMIL,
             Alphabet::Telugu => &TELUGU,
             Alphabet::Thai => &THAI,
@@ -132,16 +246,65 @@ impl CharSet {
         text.chars().all(|ch| self.is_char_match(ch))
     }
 
+    pub fn is_any_match(&self, text: &str) -> bool {
+        text.chars().any(|ch| self.is_char_match(ch))
+    }
+
+    pub fn match_ratio(&self, text: &str) -> f64 {
+        let mut char_count = 0;
+        let mut match_count = 0;
+
+        for ch in text.chars() {
+            char_count += 1;
+            if self.is_char_match(ch) {
+                match_count += 1;
+            }
+        }
+
+        if char_count == 0 {
+            return 0.0;
+        }
+
+        match_count as f64 / char_count as f64
+    }
+
     pub fn is_char_match(&self, ch: char) -> bool {
         self.characters.contains(&ch)
     }
+
+    pub fn memory_usage(&self) -> usize {
+        // Each bucket of the hash set holds one char plus one control byte.
+        self.characters.capacity() * (size_of::<char>() + 1)
+    }
 }
 
+/// The character ranges of all alphabets, sorted by their first character.
+static ALPHABET_RANGES: Lazy<Vec<(char, char, Alphabet)>> = Lazy::new(|| {
+    let mut ranges = vec![];
+
+    for alphabet in Alphabet::iter() {
+        let script_name = format!("{alphabet:?}");
+        let table = crate::script::BY_NAME
+            .iter()
+            .find(|(name, _)| *name == script_name)
+            .unwrap()
+            .1;
+
+        for &(start, end) in table {
+            ranges.push((start, end, alphabet));
+        }
+    }
+
+    ranges.sort_by_key(|(start, _, _)| *start);
+    ranges
+});
+
 static ARABIC: Lazy<CharSet> = Lazy::new(|| CharSet::from_char_class("Arabic"));
 static ARMENIAN: Lazy<CharSet> = Lazy::new(|| CharSet::from_char_class("Armenian"));
 static BENGALI: Lazy<CharSet> = Lazy::new(|| CharSet::from_char_class("Bengali"));
 static CYRILLIC: Lazy<CharSet> = Lazy::new(|| CharSet::from_char_class("Cyrillic"));
 static DEVANAGARI: Lazy<CharSet> = Lazy::new(|| CharSet::from_char_class("Devanagari"));
+static ETHIOPIC: Lazy<CharSet> = Lazy::new(|| CharSet::from_char_class("Ethiopic"));
 static GEORGIAN: Lazy<CharSet> = Lazy::new(|| CharSet::from_char_class("Georgian"));
 static GREEK: Lazy<CharSet> = Lazy::new(|| CharSet::from_char_class("Greek"));
 static GUJARATI: Lazy<CharSet> = Lazy::new(|| CharSet::from_char_class("Gujarati"));
@@ -150,8 +313,81 @@ static HAN: Lazy<CharSet> = Lazy::new(|| CharSet::from_char_class("Han"));
 static HANGUL: Lazy<CharSet> = Lazy::new(|| CharSet::from_char_class("Hangul"));
 static HEBREW: Lazy<CharSet> = Lazy::new(|| CharSet::from_char_class("Hebrew"));
 static HIRAGANA: Lazy<CharSet> = Lazy::new(|| CharSet::from_char_class("Hiragana"));
+static KANNADA: Lazy<CharSet> = Lazy::new(|| CharSet::from_char_class("Kannada"));
 static KATAKANA: Lazy<CharSet> = Lazy::new(|| CharSet::from_char_class("Katakana"));
+static LAO: Lazy<CharSet> = Lazy::new(|| CharSet::from_char_class("Lao"));
 static LATIN: Lazy<CharSet> = Lazy::new(|| CharSet::from_char_class("Latin"));
+static MALAYALAM: Lazy<CharSet> = Lazy::new(|| CharSet::from_char_class("Malayalam"));
+static MYANMAR: Lazy<CharSet> = Lazy::new(|| CharSet::from_char_class("Myanmar"));
+static ORIYA: Lazy<CharSet> = Lazy::new(|| CharSet::from_char_class("Oriya"));
 static TAMIL: Lazy<CharSet> = Lazy::new(|| CharSet::from_char_class("Tamil"));
 static TELUGU: Lazy<CharSet> = Lazy::new(|| CharSet::from_char_class("Telugu"));
 static THAI: Lazy<CharSet> = Lazy::new(|| CharSet::from_char_class("Thai"));
+
+#[cfg(test)]
+mod tests {
+    use float_cmp::approx_eq;
+    use rstest::*;
+
+    use super::*;
+
+    #[test]
+    fn assert_alphabet_distribution_is_detected_correctly() {
+        assert_eq!(Alphabet::detect("house"), vec![(Alphabet::Latin, 1.0)]);
+        assert_eq!(
+            Alphabet::detect("ab мир"),
+            vec![(Alphabet::Cyrillic, 0.6), (Alphabet::Latin, 0.4)]
+        );
+        assert_eq!(
+            Alphabet::detect("ab мп"),
+            vec![(Alphabet::Cyrillic, 0.5), (Alphabet::Latin, 0.5)]
+        );
+        assert!(Alphabet::detect("").is_empty());
+        assert!(Alphabet::detect("42 - 7").is_empty());
+    }
+
+    #[rstest(
+        alphabet, text, expected_all, expected_any, expected_ratio,
+        case(Alphabet::Latin, "house", true, true, 1.0),
+        case(Alphabet::Latin, "rock-n-roll", false, true, 9.0 / 11.0),
+        case(Alphabet::Latin, "l'homme", false, true, 6.0 / 7.0),
+        case(Alphabet::Cyrillic, "house", false, false, 0.0),
+        case(Alphabet::Cyrillic, "п'ять", false, true, 0.8),
+        case(Alphabet::Ethiopic, "አማርኛ", true, true, 1.0),
+        case(Alphabet::Kannada, "ಕನ್ನಡ", true, true, 1.0),
+        case(Alphabet::Lao, "ພາສາລາວ", true, true, 1.0),
+        case(Alphabet::Malayalam, "മലയാളം", true, true, 1.0),
+        case(Alphabet::Myanmar, "မြန်မာစာ", true, true, 1.0),
+        case(Alphabet::Oriya, "ଓଡ଼ିଆ", true, true, 1.0),
+        case(Alphabet::Thai, "ພາສາລາວ", false, false, 0.0),
+        case(Alphabet::Latin, "", true, false, 0.0)
+    )]
+    fn assert_alphabet_matches_text_correctly(
+        alphabet: Alphabet,
+        text: &str,
+        expected_all: bool,
+        expected_any: bool,
+        expected_ratio: f64,
+    ) {
+        assert_eq!(alphabet.matches_all(text), expected_all);
+        assert_eq!(alphabet.matches_any(text), expected_any);
+        assert!(approx_eq!(
+            f64,
+            alphabet.match_ratio(text),
+            expected_ratio,
+            ulps = 2
+        ));
+    }
+
+    #[test]
+    fn assert_alphabet_of_char_is_looked_up_correctly() {
+        for ch in (0..0x32000).filter_map(char::from_u32) {
+            let expected_alphabet = Alphabet::iter().find(|alphabet| alphabet.matches_char(ch));
+            assert_eq!(
+                Alphabet::of_char(ch),
+                expected_alphabet,
+                "character: {ch:?}"
+            );
+        }
+    }
+}
@@ -0,0 +1,157 @@
+/*
+ * Copyright © 2020-present Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::borrow::Cow;
+
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+use strum::IntoEnumIterator;
+use strum_macros::EnumIter;
+
+/// URLs with a scheme, such as `https://example.com/path`, or starting with `www.`.
+static URLS: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)\b(?:[a-z][a-z0-9+.-]*://|www\.)[^\s<>]+").unwrap());
+
+/// Email addresses such as `jane.doe+news@example.co.uk`.
+static EMAILS: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"[\p{L}\p{N}._%+-]+@[\p{L}\p{N}-]+(?:\.[\p{L}\p{N}-]+)*\.\p{L}{2,}").unwrap()
+});
+
+/// Candidates for phone numbers such as `+49 (30) 123-456 78`. Only candidates with
+/// at least seven digits are considered phone numbers.
+static PHONE_NUMBERS: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\+?\(?\p{N}[\p{N}\s()./-]{5,}\p{N}").unwrap());
+
+/// Words consisting of letters and digits which contain at least one digit,
+/// such as `4711`, `AB-12345` or `x9f3k2`.
+static NUMERIC_IDS: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?:[\p{L}\p{N}]+[_-])*[\p{L}\p{N}]*\p{N}[\p{L}\p{N}]*(?:[_-][\p{L}\p{N}]+)*")
+        .unwrap()
+});
+
+const MINIMUM_PHONE_NUMBER_DIGITS: usize = 7;
+
+/// This enum specifies the kinds of personal data and noise which can be scrubbed from
+/// training corpora before the ngrams of the language models are counted, see
+/// [LanguageModelFilesWriter::create_and_write_anonymized_language_model_files](crate::LanguageModelFilesWriter::create_and_write_anonymized_language_model_files).
+///
+/// Scrubbed text is replaced with whitespace, so that no ngrams are formed across it.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, EnumIter)]
+pub enum AnonymizationFilter {
+    /// URLs with a scheme such as `https://` or starting with `www.`.
+    Url,
+    /// Email addresses.
+    Email,
+    /// Phone numbers consisting of at least seven digits, which may be separated by
+    /// whitespace, dots, slashes, hyphens or parentheses and preceded by a plus sign.
+    PhoneNumber,
+    /// Words which contain digits, such as customer numbers, order numbers or hashes.
+    NumericId,
+}
+
+impl AnonymizationFilter {
+    fn apply<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        match self {
+            AnonymizationFilter::Url => URLS.replace_all(text, " "),
+            AnonymizationFilter::Email => EMAILS.replace_all(text, " "),
+            AnonymizationFilter::PhoneNumber => {
+                PHONE_NUMBERS.replace_all(text, |captures: &Captures| {
+                    let candidate = &captures[0];
+                    let digit_count = candidate.chars().filter(|c| c.is_numeric()).count();
+                    if digit_count >= MINIMUM_PHONE_NUMBER_DIGITS {
+                        " ".to_string()
+                    } else {
+                        candidate.to_string()
+                    }
+                })
+            }
+            AnonymizationFilter::NumericId => NUMERIC_IDS.replace_all(text, " "),
+        }
+    }
+}
+
+/// Applies the given filters to the given text. The filters are applied in the order
+/// of their declaration, regardless of their order in the slice, so that URLs
+/// are removed before the email addresses and numbers they may contain.
+pub(crate) fn anonymize(text: &str, filters: &[AnonymizationFilter]) -> String {
+    let mut anonymized_text = text.to_string();
+
+    for filter in AnonymizationFilter::iter() {
+        if filters.contains(&filter) {
+            anonymized_text = filter.apply(&anonymized_text).into_owned();
+        }
+    }
+
+    anonymized_text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constant::MULTIPLE_WHITESPACE;
+
+    fn anonymize_and_normalize(text: &str, filters: &[AnonymizationFilter]) -> String {
+        MULTIPLE_WHITESPACE
+            .replace_all(&anonymize(text, filters), " ")
+            .trim()
+            .to_string()
+    }
+
+    #[test]
+    fn assert_urls_and_emails_are_removed() {
+        let text = "Write to jane.doe+news@example.co.uk or see https://example.com/a?b=c and www.example.org";
+
+        assert_eq!(
+            anonymize_and_normalize(
+                text,
+                &[AnonymizationFilter::Email, AnonymizationFilter::Url]
+            ),
+            "Write to or see and"
+        );
+        assert_eq!(
+            anonymize_and_normalize(text, &[AnonymizationFilter::Email]),
+            "Write to or see https://example.com/a?b=c and www.example.org"
+        );
+    }
+
+    #[test]
+    fn assert_phone_numbers_are_removed() {
+        assert_eq!(
+            anonymize_and_normalize(
+                "Call +49 (30) 123-456 78 or 555 0199 between 9 and 17 in 2023.",
+                &[AnonymizationFilter::PhoneNumber]
+            ),
+            "Call or between 9 and 17 in 2023."
+        );
+    }
+
+    #[test]
+    fn assert_numeric_ids_are_removed() {
+        assert_eq!(
+            anonymize_and_normalize(
+                "Order AB-12345 of customer x9f3k2 was shipped in 2023",
+                &[AnonymizationFilter::NumericId]
+            ),
+            "Order of customer was shipped in"
+        );
+    }
+
+    #[test]
+    fn assert_text_without_filters_is_unchanged() {
+        let text = "Mail jane@example.com, call 555 0199";
+        assert_eq!(anonymize(text, &[]), text);
+    }
+}
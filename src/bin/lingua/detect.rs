@@ -0,0 +1,197 @@
+/*
+ * Copyright © 2020-present Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::error::Error;
+use std::fs;
+use std::io::{Read, Write};
+use std::path::Path;
+
+use clap::ValueEnum;
+use serde_json::json;
+
+use lingua::LanguageDetector;
+
+#[derive(Clone, Copy, ValueEnum)]
+pub(crate) enum OutputFormat {
+    /// The detected language on the first line, followed by one line per language
+    /// with its confidence value, separated by a tab
+    Text,
+    /// A JSON object with the detected language and the confidence values
+    Json,
+}
+
+/// Reads the text to detect the language of from the given words, the given file
+/// or, if neither is given, from stdin.
+pub(crate) fn read_text<R: Read>(
+    words: &[String],
+    file: Option<&Path>,
+    mut stdin: R,
+) -> Result<String, Box<dyn Error>> {
+    if !words.is_empty() {
+        return Ok(words.join(" "));
+    }
+    match file {
+        Some(file) => Ok(fs::read_to_string(file)?),
+        None => {
+            let mut text = String::new();
+            stdin.read_to_string(&mut text)?;
+            Ok(text)
+        }
+    }
+}
+
+/// Writes the detected language of the given text and the confidence values
+/// of the `top` most likely languages in the given format.
+pub(crate) fn detect<W: Write>(
+    detector: &LanguageDetector,
+    text: &str,
+    top: usize,
+    format: OutputFormat,
+    mut output: W,
+) -> Result<(), Box<dyn Error>> {
+    let language = detector.detect_language_of(text);
+    let confidence_values = detector.detect_top_k_languages_of(text, top);
+
+    match format {
+        OutputFormat::Text => {
+            match language {
+                Some(language) => writeln!(output, "{language}")?,
+                None => writeln!(output, "unknown")?,
+            }
+            for (language, confidence) in confidence_values {
+                writeln!(output, "{language}\t{confidence:.4}")?;
+            }
+        }
+        OutputFormat::Json => {
+            let confidence_values = confidence_values
+                .into_iter()
+                .map(|(language, confidence)| {
+                    json!({"language": language.to_string(), "confidence": confidence})
+                })
+                .collect::<Vec<_>>();
+            let result = json!({
+                "language": language.map(|it| it.to_string()),
+                "confidenceValues": confidence_values,
+            });
+            writeln!(output, "{result}")?;
+        }
+    }
+
+    output.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lingua::Language::{English, French, German};
+    use lingua::LanguageDetectorBuilder;
+    use serde_json::Value;
+    use tempfile::tempdir;
+
+    const GERMAN_TEXT: &str = "Dies ist ein Satz in deutscher Sprache";
+
+    fn detect_to_string(text: &str, top: usize, format: OutputFormat) -> String {
+        let detector = LanguageDetectorBuilder::from_languages(&[English, French, German]).build();
+        let mut output = vec![];
+        detect(&detector, text, top, format, &mut output).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn assert_text_is_read_from_arguments() {
+        let words = vec!["Hello".to_string(), "world".to_string()];
+
+        assert_eq!(
+            read_text(&words, None, "ignored".as_bytes()).unwrap(),
+            "Hello world"
+        );
+    }
+
+    #[test]
+    fn assert_text_is_read_from_file() {
+        let directory = tempdir().unwrap();
+        let file = directory.path().join("text.txt");
+
+        fs::write(&file, GERMAN_TEXT).unwrap();
+
+        assert_eq!(
+            read_text(&[], Some(&file), "ignored".as_bytes()).unwrap(),
+            GERMAN_TEXT
+        );
+        assert!(read_text(
+            &[],
+            Some(&directory.path().join("missing.txt")),
+            "".as_bytes()
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn assert_text_is_read_from_stdin() {
+        assert_eq!(
+            read_text(&[], None, "Hello\nworld\n".as_bytes()).unwrap(),
+            "Hello\nworld\n"
+        );
+    }
+
+    #[test]
+    fn assert_text_output_lists_top_languages() {
+        let output = detect_to_string(GERMAN_TEXT, 2, OutputFormat::Text);
+        let lines = output.lines().collect::<Vec<_>>();
+
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], "German");
+
+        let (language, confidence) = lines[1].split_once('\t').unwrap();
+
+        assert_eq!(language, "German");
+        assert_eq!(confidence.split_once('.').unwrap().1.len(), 4);
+        assert!(confidence.parse::<f64>().unwrap() > 0.5);
+        assert!(lines[2].starts_with("English\t") || lines[2].starts_with("French\t"));
+    }
+
+    #[test]
+    fn assert_text_output_reports_unknown_language() {
+        assert_eq!(
+            detect_to_string("", 2, OutputFormat::Text),
+            "unknown\nEnglish\t0.0000\nFrench\t0.0000\n"
+        );
+    }
+
+    #[test]
+    fn assert_json_output_contains_language_and_confidence_values() {
+        let output = detect_to_string(GERMAN_TEXT, 1, OutputFormat::Json);
+        let result = serde_json::from_str::<Value>(&output).unwrap();
+        let confidence_values = result["confidenceValues"].as_array().unwrap();
+
+        assert!(output.ends_with('\n'));
+        assert_eq!(result["language"], "German");
+        assert_eq!(confidence_values.len(), 1);
+        assert_eq!(confidence_values[0]["language"], "German");
+        assert!(confidence_values[0]["confidence"].as_f64().unwrap() > 0.5);
+
+        let output = detect_to_string("", 1, OutputFormat::Json);
+
+        assert_eq!(
+            serde_json::from_str::<Value>(&output).unwrap(),
+            json!({
+                "language": null,
+                "confidenceValues": [{"language": "English", "confidence": 0.0}]
+            })
+        );
+    }
+}
@@ -0,0 +1,282 @@
+/*
+ * Copyright © 2020-present Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::error::Error;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::Path;
+use std::str::FromStr;
+
+use csv::{ReaderBuilder, StringRecord, WriterBuilder};
+
+use lingua::{IsoCode639_1, Language, LanguageDetector, LanguageDetectorBuilder};
+
+pub(crate) fn build_detector(
+    iso_codes: &[String],
+    is_low_accuracy_mode_enabled: bool,
+    custom_model_path: Option<(Language, &Path)>,
+) -> Result<LanguageDetector, Box<dyn Error>> {
+    let mut builder = if iso_codes.is_empty() {
+        LanguageDetectorBuilder::from_all_languages()
+    } else {
+        let iso_codes = iso_codes
+            .iter()
+            .map(|code| {
+                IsoCode639_1::from_str(code.trim())
+                    .map_err(|_| format!("unsupported ISO 639-1 code '{code}'"))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if iso_codes.len() < 2 {
+            return Err("at least two languages must be specified".into());
+        }
+
+        LanguageDetectorBuilder::from_iso_codes_639_1(&iso_codes)
+    };
+
+    if is_low_accuracy_mode_enabled {
+        builder.with_low_accuracy_mode();
+    }
+
+    if let Some((language, directory)) = custom_model_path {
+        builder.with_custom_model_path(language, directory);
+    }
+
+    Ok(builder.try_build()?)
+}
+
+pub(crate) fn parse_delimiter(delimiter: Option<&str>, file: &Path) -> Result<u8, Box<dyn Error>> {
+    match delimiter {
+        Some("\\t") | Some("\t") => Ok(b'\t'),
+        Some(delimiter) if delimiter.len() == 1 => Ok(delimiter.as_bytes()[0]),
+        Some(delimiter) => {
+            Err(format!("delimiter '{delimiter}' is not a single ASCII character").into())
+        }
+        None if file
+            .extension()
+            .is_some_and(|it| it.eq_ignore_ascii_case("tsv")) =>
+        {
+            Ok(b'\t')
+        }
+        None => Ok(b','),
+    }
+}
+
+/// Reads delimited rows from `input` and writes them to `output` with the language detected
+/// in the column at the 0-based `column_index` appended. Rows lacking the column get an empty
+/// language. Returns an error if the header row, or the first row if there is no header,
+/// lacks the column.
+pub(crate) fn detect_column<R: Read, W: Write>(
+    detector: &LanguageDetector,
+    input: R,
+    column_index: usize,
+    delimiter: u8,
+    has_header: bool,
+    output: W,
+) -> Result<(), Box<dyn Error>> {
+    let mut reader = ReaderBuilder::new()
+        .delimiter(delimiter)
+        .has_headers(has_header)
+        .flexible(true)
+        .from_reader(BufReader::new(input));
+
+    let mut writer = WriterBuilder::new()
+        .delimiter(delimiter)
+        .flexible(true)
+        .from_writer(BufWriter::new(output));
+
+    if has_header {
+        let mut header = reader.headers()?.clone();
+        check_column(&header, column_index)?;
+        header.push_field("language");
+        writer.write_record(&header)?;
+    }
+
+    let mut record = StringRecord::new();
+    let mut is_first_row = !has_header;
+
+    while reader.read_record(&mut record)? {
+        if is_first_row {
+            check_column(&record, column_index)?;
+            is_first_row = false;
+        }
+
+        let language = record
+            .get(column_index)
+            .and_then(|text| detector.detect_language_of(text))
+            .map(|language| language.to_string())
+            .unwrap_or_default();
+
+        record.push_field(&language);
+        writer.write_record(&record)?;
+    }
+
+    writer.flush()?;
+    Ok(())
+}
+
+fn check_column(record: &StringRecord, column_index: usize) -> Result<(), Box<dyn Error>> {
+    if column_index >= record.len() {
+        return Err(format!(
+            "column {} not found, the first row has {} columns",
+            column_index + 1,
+            record.len()
+        )
+        .into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+    use lingua::Language::{English, German};
+    use std::collections::HashSet;
+    use tempfile::tempdir;
+
+    fn iso_codes(codes: &[&str]) -> Vec<String> {
+        codes.iter().map(|code| code.to_string()).collect()
+    }
+
+    fn detect_column_to_string(
+        input: &str,
+        column_index: usize,
+        delimiter: u8,
+        has_header: bool,
+    ) -> Result<String, Box<dyn Error>> {
+        let detector = build_detector(&iso_codes(&["en", "de"]), false, None)?;
+        let mut output = vec![];
+        detect_column(
+            &detector,
+            input.as_bytes(),
+            column_index,
+            delimiter,
+            has_header,
+            &mut output,
+        )?;
+        Ok(String::from_utf8(output)?)
+    }
+
+    #[test]
+    fn assert_detector_is_built_from_iso_codes() {
+        let detector = build_detector(&iso_codes(&[" en", "DE "]), true, None).unwrap();
+
+        assert_eq!(
+            detector.supported_languages(),
+            HashSet::from([English, German])
+        );
+        assert_eq!(
+            build_detector(&[], false, None)
+                .unwrap()
+                .supported_languages(),
+            Language::all()
+        );
+    }
+
+    #[test]
+    fn assert_at_least_two_iso_codes_are_required() {
+        assert_eq!(
+            build_detector(&iso_codes(&["en"]), false, None)
+                .err()
+                .unwrap()
+                .to_string(),
+            "at least two languages must be specified"
+        );
+        assert_eq!(
+            build_detector(&iso_codes(&["en", "xx"]), false, None)
+                .err()
+                .unwrap()
+                .to_string(),
+            "unsupported ISO 639-1 code 'xx'"
+        );
+    }
+
+    #[test]
+    fn assert_missing_custom_models_are_rejected() {
+        let directory = tempdir().unwrap();
+
+        assert!(build_detector(
+            &iso_codes(&["en", "de"]),
+            false,
+            Some((English, directory.path()))
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn assert_delimiter_is_parsed() {
+        let csv_file = Path::new("data.csv");
+        let tsv_file = Path::new("data.TSV");
+
+        assert_eq!(parse_delimiter(None, csv_file).unwrap(), b',');
+        assert_eq!(parse_delimiter(None, tsv_file).unwrap(), b'\t');
+        assert_eq!(parse_delimiter(Some("\\t"), csv_file).unwrap(), b'\t');
+        assert_eq!(parse_delimiter(Some(";"), tsv_file).unwrap(), b';');
+        assert!(parse_delimiter(Some(";;"), csv_file).is_err());
+    }
+
+    #[test]
+    fn assert_language_column_is_appended() {
+        let input = indoc!(
+            r#"
+            id,text
+            1,"Hello, this is a sentence written in English"
+            2,Dies ist ein Satz in deutscher Sprache
+            3
+            "#
+        );
+
+        assert_eq!(
+            detect_column_to_string(input, 1, b',', true).unwrap(),
+            indoc!(
+                r#"
+                id,text,language
+                1,"Hello, this is a sentence written in English",English
+                2,Dies ist ein Satz in deutscher Sprache,German
+                3,
+                "#
+            )
+        );
+    }
+
+    #[test]
+    fn assert_first_row_is_treated_as_data_without_header() {
+        let input = "Dies ist ein Satz in deutscher Sprache\t1\n";
+
+        assert_eq!(
+            detect_column_to_string(input, 0, b'\t', false).unwrap(),
+            "Dies ist ein Satz in deutscher Sprache\t1\tGerman\n"
+        );
+    }
+
+    #[test]
+    fn assert_missing_column_is_rejected() {
+        assert_eq!(
+            detect_column_to_string("id,text\n1,Hello\n", 2, b',', true)
+                .err()
+                .unwrap()
+                .to_string(),
+            "column 3 not found, the first row has 2 columns"
+        );
+        assert_eq!(
+            detect_column_to_string("Hello\n", 1, b',', false)
+                .err()
+                .unwrap()
+                .to_string(),
+            "column 2 not found, the first row has 1 columns"
+        );
+    }
+}
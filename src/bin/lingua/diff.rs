@@ -0,0 +1,340 @@
+/*
+ * Copyright © 2020-present Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use csv::{ReaderBuilder, StringRecord};
+
+use lingua::{Language, LanguageDetector, TestData};
+
+use crate::models::ModelLayout;
+
+/// A text of the evaluation data together with its actual language.
+struct Sample {
+    text: String,
+    language: Language,
+}
+
+/// Returns the directory containing the language model files of one model version,
+/// which is either the given directory itself or the directory `models` within it.
+pub(crate) fn find_models_directory(directory: &Path) -> PathBuf {
+    ModelLayout::detect(directory).models_directory(directory)
+}
+
+/// Runs both detectors over the same evaluation data and writes the accuracy of each
+/// language before and after the model upgrade to `output`, followed by at most
+/// `maximum_example_count` texts whose detected language has changed.
+///
+/// The evaluation data is read from `file` as (text, language) pairs if given.
+/// Otherwise, the bundled test data of the languages of the old detector is used.
+pub(crate) fn diff_models<W: Write>(
+    old_detector: &LanguageDetector,
+    new_detector: &LanguageDetector,
+    file: Option<(&Path, u8, bool)>,
+    maximum_example_count: usize,
+    output: W,
+) -> Result<(), Box<dyn Error>> {
+    let samples = match file {
+        Some((file, delimiter, has_header)) => read_samples(file, delimiter, has_header)?,
+        None => collect_test_data_samples(old_detector),
+    };
+
+    if samples.is_empty() {
+        return Err("no evaluation data found".into());
+    }
+
+    let texts = samples
+        .iter()
+        .map(|sample| sample.text.as_str())
+        .collect::<Vec<_>>();
+    let old_languages = old_detector.detect_languages_in_parallel_of(&texts);
+    let new_languages = new_detector.detect_languages_in_parallel_of(&texts);
+
+    let mut writer = BufWriter::new(output);
+
+    writeln!(writer, "{} texts evaluated", samples.len())?;
+
+    write_accuracy_deltas(&mut writer, &samples, &old_languages, &new_languages)?;
+    write_changed_predictions(
+        &mut writer,
+        &samples,
+        &old_languages,
+        &new_languages,
+        maximum_example_count,
+    )?;
+
+    writer.flush()?;
+    Ok(())
+}
+
+fn read_samples(
+    file: &Path,
+    delimiter: u8,
+    has_header: bool,
+) -> Result<Vec<Sample>, Box<dyn Error>> {
+    let mut reader = ReaderBuilder::new()
+        .delimiter(delimiter)
+        .has_headers(has_header)
+        .from_reader(BufReader::new(File::open(file)?));
+
+    let mut record = StringRecord::new();
+    let mut samples = vec![];
+
+    while reader.read_record(&mut record)? {
+        let line = record.position().map_or(0, |position| position.line());
+
+        if record.len() != 2 {
+            return Err(format!("line {line}: expected 2 fields, found {}", record.len()).into());
+        }
+
+        let language = Language::from_str(record[1].trim())
+            .map_err(|_| format!("line {line}: unsupported language '{}'", &record[1]))?;
+
+        samples.push(Sample {
+            text: record[0].trim().to_string(),
+            language,
+        });
+    }
+
+    Ok(samples)
+}
+
+fn collect_test_data_samples(detector: &LanguageDetector) -> Vec<Sample> {
+    let mut languages = detector
+        .supported_languages()
+        .into_iter()
+        .collect::<Vec<_>>();
+    languages.sort();
+
+    languages
+        .into_iter()
+        .flat_map(|language| {
+            [
+                TestData::single_words(language),
+                TestData::word_pairs(language),
+                TestData::sentences(language),
+            ]
+            .concat()
+            .into_iter()
+            .map(move |text| Sample {
+                text: text.to_string(),
+                language,
+            })
+        })
+        .collect()
+}
+
+fn write_accuracy_deltas<W: Write>(
+    writer: &mut W,
+    samples: &[Sample],
+    old_languages: &[Option<Language>],
+    new_languages: &[Option<Language>],
+) -> Result<(), Box<dyn Error>> {
+    // For each language, the number of texts and the numbers of correct old and new detections
+    let mut counts: BTreeMap<Language, (usize, usize, usize)> = BTreeMap::new();
+
+    for ((sample, old_language), new_language) in
+        samples.iter().zip(old_languages).zip(new_languages)
+    {
+        let (total, old_correct, new_correct) = counts.entry(sample.language).or_default();
+        *total += 1;
+        if *old_language == Some(sample.language) {
+            *old_correct += 1;
+        }
+        if *new_language == Some(sample.language) {
+            *new_correct += 1;
+        }
+    }
+
+    writeln!(writer, "\nAccuracy per language (old -> new):")?;
+
+    for (language, (total, old_correct, new_correct)) in counts {
+        let old_accuracy = 100.0 * old_correct as f64 / total as f64;
+        let new_accuracy = 100.0 * new_correct as f64 / total as f64;
+
+        writeln!(
+            writer,
+            "  {:<12} {old_accuracy:>6.2}% -> {new_accuracy:>6.2}%  ({:+.2})",
+            language.to_string(),
+            new_accuracy - old_accuracy
+        )?;
+    }
+
+    Ok(())
+}
+
+fn write_changed_predictions<W: Write>(
+    writer: &mut W,
+    samples: &[Sample],
+    old_languages: &[Option<Language>],
+    new_languages: &[Option<Language>],
+    maximum_example_count: usize,
+) -> Result<(), Box<dyn Error>> {
+    let changes = samples
+        .iter()
+        .zip(old_languages)
+        .zip(new_languages)
+        .filter(|((_, old_language), new_language)| old_language != new_language)
+        .collect::<Vec<_>>();
+
+    writeln!(
+        writer,
+        "\nChanged predictions ({} texts, actual: old -> new):",
+        changes.len()
+    )?;
+
+    for ((sample, old_language), new_language) in changes.into_iter().take(maximum_example_count) {
+        writeln!(
+            writer,
+            "  {}: {} -> {}  {}",
+            sample.language,
+            format_language(*old_language),
+            format_language(*new_language),
+            sample.text
+        )?;
+    }
+
+    Ok(())
+}
+
+fn format_language(language: Option<Language>) -> String {
+    language.map_or_else(|| "unknown".to_string(), |language| language.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::detect_column::build_detector;
+    use brotli::CompressorWriter;
+    use indoc::indoc;
+    use lingua::Language::Welsh;
+    use std::fs;
+    use tempfile::tempdir;
+
+    fn write_trigram_model(directory: &Path, ngrams: &str) {
+        fs::create_dir_all(directory).unwrap();
+
+        let mut writer = CompressorWriter::new(
+            File::create(directory.join("trigrams.json.br")).unwrap(),
+            4096,
+            11,
+            22,
+        );
+
+        write!(
+            writer,
+            r#"{{"language":"WELSH","ngrams":{{"1/2":"{ngrams}"}}}}"#
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn assert_models_directory_is_found_in_both_layouts() {
+        let directory = tempdir().unwrap();
+
+        assert_eq!(find_models_directory(directory.path()), directory.path());
+
+        fs::create_dir(directory.path().join("models")).unwrap();
+
+        assert_eq!(
+            find_models_directory(directory.path()),
+            directory.path().join("models")
+        );
+    }
+
+    #[test]
+    fn assert_accuracy_deltas_and_changed_predictions_are_reported() {
+        let old_directory = tempdir().unwrap();
+        let new_directory = tempdir().unwrap();
+        let evaluation_directory = tempdir().unwrap();
+        let evaluation_file = evaluation_directory.path().join("evaluation.tsv");
+
+        write_trigram_model(&old_directory.path().join("models"), "wyw ywy");
+        write_trigram_model(new_directory.path(), "qqz zqq wyw ywy");
+        fs::write(
+            &evaluation_file,
+            indoc!(
+                "
+                text\tlanguage
+                qqz zqq\tcy
+                wyw ywy\tWelsh
+                the house is big\ten
+                "
+            ),
+        )
+        .unwrap();
+
+        let languages = vec!["en".to_string(), "cy".to_string()];
+        let old_models_directory = find_models_directory(old_directory.path());
+        let new_models_directory = find_models_directory(new_directory.path());
+        let old_detector =
+            build_detector(&languages, false, Some((Welsh, &old_models_directory))).unwrap();
+        let new_detector =
+            build_detector(&languages, false, Some((Welsh, &new_models_directory))).unwrap();
+        let mut output = vec![];
+
+        diff_models(
+            &old_detector,
+            &new_detector,
+            Some((&evaluation_file, b'\t', true)),
+            10,
+            &mut output,
+        )
+        .unwrap();
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            indoc!(
+                "
+                3 texts evaluated
+
+                Accuracy per language (old -> new):
+                  English      100.00% -> 100.00%  (+0.00)
+                  Welsh         50.00% -> 100.00%  (+50.00)
+
+                Changed predictions (1 texts, actual: old -> new):
+                  Welsh: English -> Welsh  qqz zqq
+                "
+            )
+        );
+    }
+
+    #[test]
+    fn assert_empty_evaluation_data_is_rejected() {
+        let directory = tempdir().unwrap();
+        let evaluation_file = directory.path().join("evaluation.csv");
+
+        fs::write(&evaluation_file, "text,language\n").unwrap();
+
+        let languages = vec!["en".to_string(), "cy".to_string()];
+        let detector = build_detector(&languages, false, None).unwrap();
+        let error = diff_models(
+            &detector,
+            &detector,
+            Some((&evaluation_file, b',', true)),
+            10,
+            vec![],
+        )
+        .unwrap_err();
+
+        assert_eq!(error.to_string(), "no evaluation data found");
+    }
+}
@@ -0,0 +1,423 @@
+/*
+ * Copyright © 2020-present Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::error::Error;
+use std::fs;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Write};
+use std::path::Path;
+use std::str::FromStr;
+
+use csv::{ReaderBuilder, StringRecord};
+
+use lingua::Language;
+
+/// A production feedback entry whose language has been detected wrongly.
+struct Misdetection {
+    text: String,
+    detected_language: Language,
+    corrected_language: Language,
+}
+
+/// Reads a feedback log of (text, detected, corrected) triples, mines the systematic
+/// failure patterns and writes suggestions for improving the rules and models to `output`.
+///
+/// Entries whose detected language equals the corrected one are ignored. A pattern is only
+/// reported if it is supported by at least `minimum_support` entries. If `corpus_directory`
+/// is given, the misdetected texts are written there as training corpus supplements,
+/// one file per corrected language named by its ISO 639-1 code.
+pub(crate) fn mine_feedback<W: Write>(
+    file: &Path,
+    delimiter: u8,
+    has_header: bool,
+    minimum_support: usize,
+    corpus_directory: Option<&Path>,
+    output: W,
+) -> Result<(), Box<dyn Error>> {
+    let misdetections = read_misdetections(file, delimiter, has_header)?;
+    let mut writer = BufWriter::new(output);
+
+    writeln!(
+        writer,
+        "{} misdetections found in feedback log",
+        misdetections.len()
+    )?;
+
+    write_confusions(&mut writer, &misdetections, minimum_support)?;
+    write_character_suggestions(&mut writer, &misdetections, minimum_support)?;
+    write_corpus_supplements(
+        &mut writer,
+        &misdetections,
+        minimum_support,
+        corpus_directory,
+    )?;
+
+    writer.flush()?;
+    Ok(())
+}
+
+fn read_misdetections(
+    file: &Path,
+    delimiter: u8,
+    has_header: bool,
+) -> Result<Vec<Misdetection>, Box<dyn Error>> {
+    let mut reader = ReaderBuilder::new()
+        .delimiter(delimiter)
+        .has_headers(has_header)
+        .from_reader(BufReader::new(File::open(file)?));
+
+    let mut record = StringRecord::new();
+    let mut misdetections = vec![];
+
+    while reader.read_record(&mut record)? {
+        let line = record.position().map_or(0, |position| position.line());
+
+        if record.len() != 3 {
+            return Err(format!("line {line}: expected 3 fields, found {}", record.len()).into());
+        }
+
+        let detected_language = Language::from_str(record[1].trim())
+            .map_err(|_| format!("line {line}: unsupported language '{}'", &record[1]))?;
+        let corrected_language = Language::from_str(record[2].trim())
+            .map_err(|_| format!("line {line}: unsupported language '{}'", &record[2]))?;
+
+        if detected_language != corrected_language {
+            misdetections.push(Misdetection {
+                text: record[0].trim().to_string(),
+                detected_language,
+                corrected_language,
+            });
+        }
+    }
+
+    Ok(misdetections)
+}
+
+fn write_confusions<W: Write>(
+    writer: &mut W,
+    misdetections: &[Misdetection],
+    minimum_support: usize,
+) -> Result<(), Box<dyn Error>> {
+    let mut confusion_counts = BTreeMap::new();
+
+    for misdetection in misdetections {
+        *confusion_counts
+            .entry((
+                misdetection.detected_language,
+                misdetection.corrected_language,
+            ))
+            .or_insert(0) += 1;
+    }
+
+    let mut confusions = confusion_counts
+        .into_iter()
+        .filter(|(_, count)| *count >= minimum_support)
+        .collect::<Vec<_>>();
+
+    confusions.sort_by(|first, second| second.1.cmp(&first.1).then(first.0.cmp(&second.0)));
+
+    writeln!(
+        writer,
+        "\nSystematic misdetections (detected -> corrected):"
+    )?;
+
+    for ((detected_language, corrected_language), count) in confusions {
+        writeln!(
+            writer,
+            "  {count:>5}  {detected_language} -> {corrected_language}"
+        )?;
+    }
+
+    Ok(())
+}
+
+fn write_character_suggestions<W: Write>(
+    writer: &mut W,
+    misdetections: &[Misdetection],
+    minimum_support: usize,
+) -> Result<(), Box<dyn Error>> {
+    // For each character, the number of misdetected texts containing it per corrected language
+    let mut character_counts: BTreeMap<char, BTreeMap<Language, usize>> = BTreeMap::new();
+
+    for misdetection in misdetections {
+        let characters = misdetection
+            .text
+            .to_lowercase()
+            .chars()
+            .filter(|ch| ch.is_alphabetic() && !ch.is_ascii())
+            .collect::<BTreeSet<_>>();
+
+        for ch in characters {
+            *character_counts
+                .entry(ch)
+                .or_default()
+                .entry(misdetection.corrected_language)
+                .or_insert(0) += 1;
+        }
+    }
+
+    let mut unique_character_suggestions = vec![];
+    let mut mapping_suggestions = vec![];
+
+    for (ch, language_counts) in character_counts.iter() {
+        let known_languages = Language::languages_with_character(*ch);
+
+        for (language, count) in language_counts.iter() {
+            if *count < minimum_support || known_languages.contains(language) {
+                continue;
+            }
+
+            if known_languages.is_empty() && language_counts.len() == 1 {
+                unique_character_suggestions.push((*language, *ch, *count));
+            } else {
+                mapping_suggestions.push((*ch, *language, *count));
+            }
+        }
+    }
+
+    unique_character_suggestions.sort();
+
+    writeln!(writer, "\nSuggested unique characters:")?;
+
+    for (language, ch, count) in unique_character_suggestions {
+        writeln!(writer, "  {language}: {ch} ({count} texts)")?;
+    }
+
+    writeln!(writer, "\nSuggested chars-to-languages mappings:")?;
+
+    for (ch, language, count) in mapping_suggestions {
+        writeln!(writer, "  {ch}: {language} ({count} texts)")?;
+    }
+
+    Ok(())
+}
+
+fn write_corpus_supplements<W: Write>(
+    writer: &mut W,
+    misdetections: &[Misdetection],
+    minimum_support: usize,
+    corpus_directory: Option<&Path>,
+) -> Result<(), Box<dyn Error>> {
+    let mut texts: BTreeMap<Language, BTreeSet<&str>> = BTreeMap::new();
+
+    for misdetection in misdetections {
+        texts
+            .entry(misdetection.corrected_language)
+            .or_default()
+            .insert(&misdetection.text);
+    }
+
+    writeln!(writer, "\nTraining corpus supplements:")?;
+
+    if let Some(directory) = corpus_directory {
+        fs::create_dir_all(directory)?;
+    }
+
+    for (language, language_texts) in texts {
+        if language_texts.len() < minimum_support {
+            continue;
+        }
+
+        match corpus_directory {
+            Some(directory) => {
+                let file_path = directory.join(format!("{}.txt", language.iso_code_639_1()));
+                let mut file = BufWriter::new(File::create(&file_path)?);
+
+                for text in language_texts.iter() {
+                    writeln!(file, "{text}")?;
+                }
+
+                file.flush()?;
+
+                writeln!(
+                    writer,
+                    "  {language}: {} texts written to {}",
+                    language_texts.len(),
+                    file_path.display()
+                )?;
+            }
+            None => writeln!(writer, "  {language}: {} texts", language_texts.len())?,
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+    use lingua::Language::{English, German, Somali, Swedish};
+    use tempfile::tempdir;
+
+    fn misdetection(
+        text: &str,
+        detected_language: Language,
+        corrected_language: Language,
+    ) -> Misdetection {
+        Misdetection {
+            text: text.to_string(),
+            detected_language,
+            corrected_language,
+        }
+    }
+
+    fn write_to_string<F>(write: F) -> String
+    where
+        F: FnOnce(&mut Vec<u8>) -> Result<(), Box<dyn Error>>,
+    {
+        let mut output = vec![];
+        write(&mut output).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn assert_misdetections_are_read_from_feedback_log() {
+        let directory = tempdir().unwrap();
+        let file = directory.path().join("feedback.csv");
+
+        fs::write(
+            &file,
+            indoc!(
+                "
+                text,detected,corrected
+                Hallo Welt,en,German
+                Hello world,English,eng
+                Hej världen,de, sv
+                "
+            ),
+        )
+        .unwrap();
+
+        let misdetections = read_misdetections(&file, b',', true).unwrap();
+
+        assert_eq!(misdetections.len(), 2);
+        assert_eq!(misdetections[0].text, "Hallo Welt");
+        assert_eq!(misdetections[0].detected_language, English);
+        assert_eq!(misdetections[0].corrected_language, German);
+        assert_eq!(misdetections[1].detected_language, German);
+        assert_eq!(misdetections[1].corrected_language, Swedish);
+    }
+
+    #[test]
+    fn assert_invalid_feedback_log_is_rejected() {
+        let directory = tempdir().unwrap();
+        let file = directory.path().join("feedback.tsv");
+
+        fs::write(&file, "Hallo Welt\ten\txx\n").unwrap();
+        assert_eq!(
+            read_misdetections(&file, b'\t', false)
+                .err()
+                .unwrap()
+                .to_string(),
+            "line 1: unsupported language 'xx'"
+        );
+
+        fs::write(&file, "Hallo Welt\ten\n").unwrap();
+        assert_eq!(
+            read_misdetections(&file, b'\t', false)
+                .err()
+                .unwrap()
+                .to_string(),
+            "line 1: expected 3 fields, found 2"
+        );
+    }
+
+    #[test]
+    fn assert_confusions_are_aggregated_and_filtered_by_support() {
+        let misdetections = vec![
+            misdetection("a", English, German),
+            misdetection("b", German, Swedish),
+            misdetection("c", English, German),
+            misdetection("d", German, Swedish),
+            misdetection("e", English, German),
+            misdetection("f", Somali, English),
+        ];
+
+        let output = write_to_string(|writer| write_confusions(writer, &misdetections, 2));
+
+        assert_eq!(
+            output,
+            indoc!(
+                "
+
+                Systematic misdetections (detected -> corrected):
+                      3  English -> German
+                      2  German -> Swedish
+                "
+            )
+        );
+    }
+
+    #[test]
+    fn assert_character_suggestions_are_mined() {
+        let misdetections = vec![
+            misdetection("Straße", German, Swedish),
+            misdetection("Grüße", German, Swedish),
+            misdetection("Soŋ", English, Somali),
+            misdetection("ŋaa", English, Somali),
+            misdetection("Über", English, German),
+            misdetection("über", English, German),
+        ];
+
+        let output =
+            write_to_string(|writer| write_character_suggestions(writer, &misdetections, 2));
+
+        assert_eq!(
+            output,
+            indoc!(
+                "
+
+                Suggested unique characters:
+                  Somali: ŋ (2 texts)
+
+                Suggested chars-to-languages mappings:
+                  ß: Swedish (2 texts)
+                "
+            )
+        );
+    }
+
+    #[test]
+    fn assert_corpus_supplements_are_written_per_language() {
+        let directory = tempdir().unwrap();
+        let corpus_directory = directory.path().join("corpora");
+        let misdetections = vec![
+            misdetection("Hej världen", German, Swedish),
+            misdetection("God morgon", English, Swedish),
+            misdetection("Hej världen", English, Swedish),
+            misdetection("Guten Morgen", English, German),
+        ];
+
+        let output = write_to_string(|writer| {
+            write_corpus_supplements(writer, &misdetections, 2, Some(&corpus_directory))
+        });
+
+        assert_eq!(
+            output,
+            format!(
+                "\nTraining corpus supplements:\n  Swedish: 2 texts written to {}\n",
+                corpus_directory.join("sv.txt").display()
+            )
+        );
+        assert_eq!(
+            fs::read_to_string(corpus_directory.join("sv.txt")).unwrap(),
+            "God morgon\nHej världen\n"
+        );
+        assert!(!corpus_directory.join("de.txt").exists());
+    }
+}
@@ -0,0 +1,387 @@
+/*
+ * Copyright © 2020-present Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::fs::File;
+use std::io;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::str::FromStr;
+
+use clap::{Parser, Subcommand};
+
+use lingua::Language;
+
+use crate::detect::{detect, read_text, OutputFormat};
+use crate::detect_column::{build_detector, detect_column, parse_delimiter};
+use crate::diff::{diff_models, find_models_directory};
+use crate::feedback::mine_feedback;
+use crate::models::{convert_models, ModelFormat, ModelLayout};
+use crate::train::train_models;
+
+mod detect;
+mod detect_column;
+mod diff;
+mod feedback;
+mod models;
+mod train;
+
+#[derive(Parser)]
+#[command(
+    name = "lingua",
+    version,
+    about = "Natural language detection on the command line"
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Detects the language of a text and prints it together with the confidence
+    /// values of the most likely languages. The text is given as arguments, read
+    /// from a file or, if neither is given, read from stdin.
+    Detect {
+        /// The text to detect the language of
+        #[arg(conflicts_with = "file")]
+        text: Vec<String>,
+
+        /// The UTF-8 encoded file to read the text from
+        #[arg(long)]
+        file: Option<PathBuf>,
+
+        /// Comma-separated ISO 639-1 codes of the languages to choose from.
+        /// Defaults to all supported languages.
+        #[arg(long, value_delimiter = ',')]
+        languages: Vec<String>,
+
+        /// The number of most likely languages to print the confidence values of
+        #[arg(long, default_value_t = 5)]
+        top: usize,
+
+        /// The output format
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+
+        /// Evaluate only trigrams, trading accuracy for speed and memory
+        #[arg(long)]
+        low_accuracy: bool,
+    },
+
+    /// Detects the language of one column of a CSV or TSV file and appends
+    /// the detected language as a new column. The result is written to stdout.
+    DetectColumn {
+        /// The delimited file to read, processed row by row
+        #[arg(long)]
+        file: PathBuf,
+
+        /// The 1-based index of the column whose language is to be detected
+        #[arg(long, value_parser = clap::value_parser!(u64).range(1..))]
+        column: u64,
+
+        /// The field delimiter, `\t` for tab. Defaults to tab for *.tsv files
+        /// and to comma for all other files.
+        #[arg(long)]
+        delimiter: Option<String>,
+
+        /// Treat the first row as data instead of as a header row
+        #[arg(long)]
+        no_header: bool,
+
+        /// Comma-separated ISO 639-1 codes of the languages to choose from.
+        /// Defaults to all supported languages.
+        #[arg(long, value_delimiter = ',')]
+        languages: Vec<String>,
+
+        /// Evaluate only trigrams, trading accuracy for speed and memory
+        #[arg(long)]
+        low_accuracy: bool,
+    },
+
+    /// Mines systematic misdetections from a feedback log and suggests additions
+    /// to unique characters, chars-to-languages mappings and training corpora.
+    /// The log is a CSV or TSV file with the columns text, detected language
+    /// and corrected language. The report is written to stdout.
+    MineFeedback {
+        /// The feedback log to read, with languages given by name or ISO 639 code
+        #[arg(long)]
+        file: PathBuf,
+
+        /// The field delimiter, `\t` for tab. Defaults to tab for *.tsv files
+        /// and to comma for all other files.
+        #[arg(long)]
+        delimiter: Option<String>,
+
+        /// Treat the first row as data instead of as a header row
+        #[arg(long)]
+        no_header: bool,
+
+        /// The minimum number of feedback entries a pattern must occur in to be reported
+        #[arg(long, default_value_t = 3, value_parser = clap::value_parser!(u64).range(1..))]
+        min_support: u64,
+
+        /// The directory to write the misdetected texts to as training corpus supplements,
+        /// one file per corrected language
+        #[arg(long)]
+        corpus_output: Option<PathBuf>,
+    },
+
+    /// Manages language model files
+    Models {
+        #[command(subcommand)]
+        command: ModelsCommand,
+    },
+}
+
+#[derive(Subcommand)]
+enum ModelsCommand {
+    /// Trains the unigram to fivegram models of a language on a text corpus and writes
+    /// them as Brotli-compressed JSON files, which can be loaded at runtime or used
+    /// as the models of a language model crate.
+    Train {
+        /// The UTF-8 encoded text corpus to train on, one sentence per line.
+        /// With --related-language, a seed lexicon with one word per line.
+        #[arg(long)]
+        input: PathBuf,
+
+        /// The directory to write the language model files to, created if necessary
+        #[arg(long)]
+        output: PathBuf,
+
+        /// The language to train the models for, given by name or ISO 639 code
+        #[arg(long)]
+        language: String,
+
+        /// A regex character class restricting the characters the models are built from
+        #[arg(long, default_value = "\\p{L}")]
+        char_class: String,
+
+        /// Bootstraps low-confidence models by interpolating the input with the bundled
+        /// models of this closely related language, given by name or ISO 639 code
+        #[arg(long)]
+        related_language: Option<String>,
+
+        /// The weight of the related language's models between 0.0 and 1.0
+        #[arg(long, default_value_t = 0.5, requires = "related_language")]
+        related_weight: f64,
+    },
+
+    /// Converts language model files between storage formats and directory layouts.
+    /// The format and layout of the input directory are detected automatically.
+    Convert {
+        /// The directory containing the language model files to convert
+        #[arg(long)]
+        input: PathBuf,
+
+        /// The directory to write the converted language model files to
+        #[arg(long)]
+        output: PathBuf,
+
+        /// The storage format of the converted files
+        #[arg(long, value_enum, default_value_t = ModelFormat::CompressedJson)]
+        format: ModelFormat,
+
+        /// The directory layout of the converted files
+        #[arg(long, value_enum, default_value_t = ModelLayout::Flat)]
+        layout: ModelLayout,
+    },
+
+    /// Compares two versions of the language models of one language, e.g. the bundled
+    /// and retrained ones, by running detectors built with either version over the same
+    /// evaluation data. Reports the accuracy delta of each language and the texts whose
+    /// detected language has changed. The report is written to stdout.
+    Diff {
+        /// The language whose models are compared, given by name or ISO 639 code
+        #[arg(long)]
+        language: String,
+
+        /// The directory containing the old Brotli-compressed language model files.
+        /// Defaults to the bundled models.
+        #[arg(long)]
+        old: Option<PathBuf>,
+
+        /// The directory containing the new Brotli-compressed language model files
+        #[arg(long)]
+        new: PathBuf,
+
+        /// Comma-separated ISO 639-1 codes of the languages to choose from.
+        /// Defaults to all supported languages.
+        #[arg(long, value_delimiter = ',')]
+        languages: Vec<String>,
+
+        /// A CSV or TSV file with the columns text and language to evaluate on.
+        /// Defaults to the bundled test data of the languages to choose from.
+        #[arg(long)]
+        file: Option<PathBuf>,
+
+        /// The field delimiter of the evaluation file, `\t` for tab. Defaults to tab
+        /// for *.tsv files and to comma for all other files.
+        #[arg(long)]
+        delimiter: Option<String>,
+
+        /// Treat the first row of the evaluation file as data instead of as a header row
+        #[arg(long)]
+        no_header: bool,
+
+        /// The maximum number of changed predictions to list
+        #[arg(long, default_value_t = 20)]
+        max_examples: usize,
+
+        /// Evaluate only trigrams, trading accuracy for speed and memory
+        #[arg(long)]
+        low_accuracy: bool,
+    },
+}
+
+fn main() -> ExitCode {
+    let result = match Cli::parse().command {
+        Command::Detect {
+            text,
+            file,
+            languages,
+            top,
+            format,
+            low_accuracy,
+        } => build_detector(&languages, low_accuracy, None).and_then(|detector| {
+            let text = read_text(&text, file.as_deref(), io::stdin().lock())?;
+            detect(&detector, &text, top, format, io::stdout().lock())
+        }),
+        Command::DetectColumn {
+            file,
+            column,
+            delimiter,
+            no_header,
+            languages,
+            low_accuracy,
+        } => build_detector(&languages, low_accuracy, None).and_then(|detector| {
+            let delimiter = parse_delimiter(delimiter.as_deref(), &file)?;
+            detect_column(
+                &detector,
+                File::open(&file)?,
+                (column - 1) as usize,
+                delimiter,
+                !no_header,
+                io::stdout().lock(),
+            )
+        }),
+        Command::MineFeedback {
+            file,
+            delimiter,
+            no_header,
+            min_support,
+            corpus_output,
+        } => parse_delimiter(delimiter.as_deref(), &file).and_then(|delimiter| {
+            mine_feedback(
+                &file,
+                delimiter,
+                !no_header,
+                min_support as usize,
+                corpus_output.as_deref(),
+                io::stdout().lock(),
+            )
+        }),
+        Command::Models {
+            command:
+                ModelsCommand::Train {
+                    input,
+                    output,
+                    language,
+                    char_class,
+                    related_language,
+                    related_weight,
+                },
+        } => Language::from_str(&language)
+            .map_err(|_| format!("unsupported language '{language}'").into())
+            .and_then(|language| {
+                let related_language = related_language
+                    .map(|value| {
+                        Language::from_str(&value)
+                            .map(|it| (it, related_weight))
+                            .map_err(|_| format!("unsupported language '{value}'"))
+                    })
+                    .transpose()?;
+                train_models(&input, &output, language, &char_class, related_language)
+            })
+            .map(|()| eprintln!("wrote language model files to {}", output.display())),
+        Command::Models {
+            command:
+                ModelsCommand::Convert {
+                    input,
+                    output,
+                    format,
+                    layout,
+                },
+        } => convert_models(&input, &output, format, layout).map(|file_count| {
+            eprintln!("converted {file_count} language model files");
+        }),
+        Command::Models {
+            command:
+                ModelsCommand::Diff {
+                    language,
+                    old,
+                    new,
+                    languages,
+                    file,
+                    delimiter,
+                    no_header,
+                    max_examples,
+                    low_accuracy,
+                },
+        } => Language::from_str(&language)
+            .map_err(|_| format!("unsupported language '{language}'").into())
+            .and_then(|language| {
+                let old = old.as_deref().map(find_models_directory);
+                let new = find_models_directory(&new);
+                let old_detector = build_detector(
+                    &languages,
+                    low_accuracy,
+                    old.as_deref().map(|directory| (language, directory)),
+                )?;
+                let new_detector =
+                    build_detector(&languages, low_accuracy, Some((language, &new)))?;
+
+                if !old_detector.supported_languages().contains(&language) {
+                    return Err(
+                        format!("{language} is not among the languages to choose from").into(),
+                    );
+                }
+
+                let file = match &file {
+                    Some(file) => Some((
+                        file.as_path(),
+                        parse_delimiter(delimiter.as_deref(), file)?,
+                        !no_header,
+                    )),
+                    None => None,
+                };
+                diff_models(
+                    &old_detector,
+                    &new_detector,
+                    file,
+                    max_examples,
+                    io::stdout().lock(),
+                )
+            }),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("error: {error}");
+            ExitCode::FAILURE
+        }
+    }
+}
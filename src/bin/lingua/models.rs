@@ -0,0 +1,259 @@
+/*
+ * Copyright © 2020-present Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::error::Error;
+use std::fs;
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+use brotli::{CompressorWriter, Decompressor};
+use clap::ValueEnum;
+
+use lingua::{decode_binary_model, encode_binary_model, LanguageModel};
+
+const NGRAM_NAMES: [&str; 5] = [
+    "unigrams",
+    "bigrams",
+    "trigrams",
+    "quadrigrams",
+    "fivegrams",
+];
+
+/// The storage formats of language model files.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, ValueEnum)]
+pub(crate) enum ModelFormat {
+    /// Plain JSON files (`*.json`)
+    Json,
+    /// Brotli-compressed JSON files (`*.json.br`) as embedded in the language model crates
+    CompressedJson,
+    /// Brotli-compressed files in the compact binary format (`*.bin.br`),
+    /// which are loaded considerably faster than JSON files
+    Binary,
+}
+
+/// The directory layouts in which language model files are stored.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, ValueEnum)]
+pub(crate) enum ModelLayout {
+    /// The model files reside directly in the given directory
+    Flat,
+    /// The model files reside in the `models` subdirectory of a language model crate
+    Crate,
+}
+
+impl ModelFormat {
+    fn file_extension(&self) -> &'static str {
+        match self {
+            ModelFormat::Json => "json",
+            ModelFormat::CompressedJson => "json.br",
+            ModelFormat::Binary => "bin.br",
+        }
+    }
+}
+
+impl ModelLayout {
+    pub(crate) fn models_directory(&self, directory: &Path) -> PathBuf {
+        match self {
+            ModelLayout::Flat => directory.to_path_buf(),
+            ModelLayout::Crate => directory.join("models"),
+        }
+    }
+
+    pub(crate) fn detect(directory: &Path) -> Self {
+        if directory.join("models").is_dir() {
+            ModelLayout::Crate
+        } else {
+            ModelLayout::Flat
+        }
+    }
+}
+
+/// Converts all language model files found in the input directory to the given format
+/// and layout, writing them to the output directory. The input format and layout are
+/// detected automatically. Every model is validated before it is written.
+pub(crate) fn convert_models(
+    input_directory: &Path,
+    output_directory: &Path,
+    format: ModelFormat,
+    layout: ModelLayout,
+) -> Result<usize, Box<dyn Error>> {
+    let input_models_directory =
+        ModelLayout::detect(input_directory).models_directory(input_directory);
+    let output_models_directory = layout.models_directory(output_directory);
+    let mut converted_file_count = 0;
+
+    fs::create_dir_all(&output_models_directory)?;
+
+    for ngram_name in NGRAM_NAMES {
+        let model = match read_model(&input_models_directory, ngram_name)? {
+            Some(model) => model,
+            None => continue,
+        };
+
+        let file_name = format!("{ngram_name}.{}", format.file_extension());
+        let file = File::create(output_models_directory.join(file_name))?;
+
+        match format {
+            ModelFormat::Json => write_all(file, model.to_json().as_bytes())?,
+            ModelFormat::CompressedJson => write_all(
+                CompressorWriter::new(file, 4096, 11, 22),
+                model.to_json().as_bytes(),
+            )?,
+            ModelFormat::Binary => write_all(
+                CompressorWriter::new(file, 4096, 11, 22),
+                &encode_binary_model(&model),
+            )?,
+        }
+
+        converted_file_count += 1;
+    }
+
+    if converted_file_count == 0 {
+        return Err(format!(
+            "no language model files found in directory '{}'",
+            input_models_directory.display()
+        )
+        .into());
+    }
+
+    Ok(converted_file_count)
+}
+
+fn read_model(directory: &Path, ngram_name: &str) -> Result<Option<LanguageModel>, Box<dyn Error>> {
+    let mut json = String::new();
+    let binary_file_path = directory.join(format!("{ngram_name}.bin.br"));
+    let compressed_file_path = directory.join(format!("{ngram_name}.json.br"));
+    let file_path = directory.join(format!("{ngram_name}.json"));
+
+    if binary_file_path.is_file() {
+        let mut bytes = vec![];
+        Decompressor::new(File::open(binary_file_path)?, 4096).read_to_end(&mut bytes)?;
+        let model = decode_binary_model(&bytes)
+            .map_err(|error| format!("{ngram_name} model is not a valid binary model: {error}"))?;
+        return Ok(Some(model));
+    } else if compressed_file_path.is_file() {
+        Decompressor::new(File::open(compressed_file_path)?, 4096).read_to_string(&mut json)?;
+    } else if file_path.is_file() {
+        File::open(file_path)?.read_to_string(&mut json)?;
+    } else {
+        return Ok(None);
+    }
+
+    let model = LanguageModel::from_json(&json)
+        .map_err(|error| format!("{ngram_name} model is not a valid JSON model: {error}"))?;
+
+    Ok(Some(model))
+}
+
+fn write_all<W: Write>(mut writer: W, bytes: &[u8]) -> Result<(), Box<dyn Error>> {
+    writer.write_all(bytes)?;
+    writer.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lingua::Language::{English, Shona};
+    use lingua::{LanguageDetectorBuilder, ModelPack};
+    use tempfile::tempdir;
+
+    #[test]
+    fn assert_json_models_are_converted_to_loadable_binary_models() {
+        let input_directory = tempdir().unwrap();
+        let output_directory = tempdir().unwrap();
+        let models_directory = input_directory.path().join("models");
+        let trigrams = r#"{"language":"SHONA","ngrams":{"1/2":"qqz zqq"}}"#;
+        let unigrams = r#"{"language":"SHONA","ngrams":{"1/2":"q z"}}"#;
+
+        fs::create_dir(&models_directory).unwrap();
+        fs::write(models_directory.join("trigrams.json"), trigrams).unwrap();
+        write_all(
+            CompressorWriter::new(
+                File::create(models_directory.join("unigrams.json.br")).unwrap(),
+                4096,
+                11,
+                22,
+            ),
+            unigrams.as_bytes(),
+        )
+        .unwrap();
+
+        let file_count = convert_models(
+            input_directory.path(),
+            output_directory.path(),
+            ModelFormat::Binary,
+            ModelLayout::Flat,
+        )
+        .unwrap();
+
+        assert_eq!(file_count, 2);
+        assert!(output_directory.path().join("trigrams.bin.br").is_file());
+        assert!(output_directory.path().join("unigrams.bin.br").is_file());
+        assert_eq!(
+            read_model(output_directory.path(), "trigrams").unwrap(),
+            Some(LanguageModel::from_json(trigrams).unwrap())
+        );
+
+        ModelPack::Custom
+            .register_models(Shona, output_directory.path())
+            .unwrap();
+
+        let detector = LanguageDetectorBuilder::from_languages(&[English, Shona])
+            .with_model_pack(ModelPack::Custom)
+            .build();
+
+        assert_eq!(detector.detect_language_of("qqz zqq"), Some(Shona));
+    }
+
+    #[test]
+    fn assert_invalid_models_are_not_converted() {
+        let input_directory = tempdir().unwrap();
+        let output_directory = tempdir().unwrap();
+
+        fs::write(
+            input_directory.path().join("trigrams.json"),
+            r#"{"ngrams":{"1/2":"qqz zqq"}}"#,
+        )
+        .unwrap();
+
+        let error = convert_models(
+            input_directory.path(),
+            output_directory.path(),
+            ModelFormat::Binary,
+            ModelLayout::Flat,
+        )
+        .unwrap_err();
+
+        assert!(error
+            .to_string()
+            .starts_with("trigrams model is not a valid JSON model"));
+    }
+
+    #[test]
+    fn assert_empty_directory_is_rejected() {
+        let input_directory = tempdir().unwrap();
+        let output_directory = tempdir().unwrap();
+
+        assert!(convert_models(
+            input_directory.path(),
+            output_directory.path(),
+            ModelFormat::Json,
+            ModelLayout::Crate,
+        )
+        .is_err());
+    }
+}
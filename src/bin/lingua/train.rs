@@ -0,0 +1,72 @@
+/*
+ * Copyright © 2020-present Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+use regex::Regex;
+
+use lingua::{Language, LanguageModelFilesWriter};
+
+/// Trains the unigram to fivegram models of the given language on the given corpus
+/// and writes them to the output directory, which is created if necessary. If a related
+/// language is given, the corpus is treated as a seed lexicon and the models are
+/// bootstrapped from it and the bundled models of the related language.
+pub(crate) fn train_models(
+    input: &Path,
+    output: &Path,
+    language: Language,
+    char_class: &str,
+    related_language: Option<(Language, f64)>,
+) -> Result<(), Box<dyn Error>> {
+    if Regex::new(&format!("^[{char_class}]+$")).is_err() {
+        return Err(format!("invalid character class '{char_class}'").into());
+    }
+
+    fs::create_dir_all(output)?;
+
+    // The writer requires absolute paths.
+    let input = fs::canonicalize(input)?;
+    let output = fs::canonicalize(output)?;
+
+    if !input.is_file() {
+        return Err(format!("{} is not a file", input.display()).into());
+    }
+
+    match related_language {
+        Some((related_language, related_language_weight)) => {
+            if !(0.0..=1.0).contains(&related_language_weight) {
+                return Err(
+                    "the weight of the related language must be between 0.0 and 1.0".into(),
+                );
+            }
+            LanguageModelFilesWriter::create_and_write_bootstrapped_language_model_files(
+                &input,
+                &output,
+                &language,
+                &related_language,
+                related_language_weight,
+                char_class,
+            )?
+        }
+        None => LanguageModelFilesWriter::create_and_write_language_model_files(
+            &input, &output, &language, char_class,
+        )?,
+    }
+
+    Ok(())
+}
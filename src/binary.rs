@@ -0,0 +1,366 @@
+/*
+ * Copyright © 2020-present Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::BTreeMap;
+use std::io;
+use std::io::ErrorKind;
+
+use ahash::AHashMap;
+use compact_str::CompactString;
+
+use crate::fraction::Fraction;
+use crate::model::LanguageModel;
+
+/// The bytes every binary language model starts with.
+const MAGIC_BYTES: &[u8; 4] = b"LNGM";
+
+/// The version of the binary format, incremented on incompatible changes.
+const FORMAT_VERSION: u8 = 1;
+
+/// The flag marking bootstrapped models, see
+/// [LanguageModelFilesWriter::create_and_write_bootstrapped_language_model_files](crate::LanguageModelFilesWriter::create_and_write_bootstrapped_language_model_files).
+const BOOTSTRAPPED_FLAG: u8 = 0b0000_0001;
+
+/// The number of bytes preceding the ngrams of each probability.
+const PROBABILITY_HEADER_LENGTH: usize = 12;
+
+/// Converts a language model into the compact binary format.
+///
+/// The binary format stores the same data as the JSON format, but can be loaded faster
+/// because no text has to be parsed and the number of ngrams is known in advance,
+/// see the benchmark `models` of this crate. After Brotli compression, binary model
+/// files are about as large as JSON ones, so the bundled language model crates keep
+/// shipping the latter. Binary model files are named like the JSON ones with the
+/// extension `.bin.br` instead of `.json.br` and take precedence over the latter
+/// when both are present. They are loaded from the directories registered with
+/// [ModelPack::register_models](crate::ModelPack::register_models) or passed to
+/// [LanguageDetectorBuilder::with_custom_model_path](crate::LanguageDetectorBuilder::with_custom_model_path)
+/// as well as from the `models` directories of the language model crates.
+///
+/// The layout is, with all integers stored in little-endian byte order:
+/// - the magic bytes `LNGM`, followed by the format version and a byte of flags
+/// - the length of the language name as one byte, followed by the name
+/// - the total number of ngrams and the number of distinct probabilities as `u32` values
+/// - for each probability its numerator and denominator as `u32` values, followed by the
+///   number of bytes of the space-separated ngrams as `u32` value and the ngrams themselves
+///
+/// ```
+/// use lingua::{decode_binary_model, encode_binary_model, LanguageModel};
+///
+/// let json = r#"{"language":"ENGLISH","ngrams":{"1/2":"a b","1/4":"c"}}"#;
+/// let model = LanguageModel::from_json(json).unwrap();
+/// let binary = encode_binary_model(&model);
+///
+/// assert!(binary.len() < json.len());
+/// assert_eq!(decode_binary_model(&binary).unwrap(), model);
+/// ```
+pub fn encode_binary_model(model: &LanguageModel) -> Vec<u8> {
+    let language_name = serde_json::to_value(model.language)
+        .unwrap()
+        .as_str()
+        .unwrap()
+        .to_string();
+    let ngram_count = model.ngram_count();
+
+    let mut bytes = vec![];
+    bytes.extend_from_slice(MAGIC_BYTES);
+    bytes.push(FORMAT_VERSION);
+    bytes.push(if model.is_bootstrapped {
+        BOOTSTRAPPED_FLAG
+    } else {
+        0
+    });
+    bytes.push(language_name.len() as u8);
+    bytes.extend_from_slice(language_name.as_bytes());
+    bytes.extend_from_slice(&(ngram_count as u32).to_le_bytes());
+    bytes.extend_from_slice(&(model.ngrams.len() as u32).to_le_bytes());
+
+    for (fraction, ngrams) in &model.ngrams {
+        bytes.extend_from_slice(&fraction.numerator().to_le_bytes());
+        bytes.extend_from_slice(&fraction.denominator().to_le_bytes());
+        bytes.extend_from_slice(&(ngrams.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(ngrams.as_bytes());
+    }
+
+    bytes
+}
+
+/// Reads a language model from the compact binary format, see [encode_binary_model]
+/// for details.
+///
+/// Returns an error of kind [ErrorKind::InvalidData] if the bytes are not a valid binary
+/// language model, e.g. because they are truncated, contain more or fewer ngrams than
+/// stated in the header or stem from an unknown format version.
+pub fn decode_binary_model(bytes: &[u8]) -> io::Result<LanguageModel> {
+    let mut reader = BinaryModelReader::new(bytes)?;
+    let mut ngrams = BTreeMap::new();
+    let mut ngram_count = 0;
+
+    for _ in 0..reader.fraction_count {
+        let (numerator, denominator, joined_ngrams) = reader.read_ngrams()?;
+        ngram_count += joined_ngrams.split(' ').count();
+        ngrams.insert(
+            Fraction::new(numerator, denominator),
+            joined_ngrams.to_string(),
+        );
+    }
+
+    reader.finish(ngram_count)?;
+
+    Ok(LanguageModel {
+        language: serde_json::from_value(serde_json::Value::String(
+            reader.language_name.to_string(),
+        ))
+        .map_err(|_| invalid_data("unknown language of binary language model"))?,
+        ngrams,
+        is_bootstrapped: reader.is_bootstrapped,
+    })
+}
+
+/// Loads the ngram probabilities of a binary language model like
+/// [TrainingDataLanguageModel::from_json](crate::model::TrainingDataLanguageModel::from_json)
+/// does for JSON models.
+pub(crate) fn load_binary_model(bytes: &[u8]) -> io::Result<AHashMap<CompactString, f64>> {
+    let mut reader = BinaryModelReader::new(bytes)?;
+    let mut probabilities = AHashMap::with_capacity(reader.ngram_count);
+    let mut ngram_count = 0;
+
+    for _ in 0..reader.fraction_count {
+        let (numerator, denominator, joined_ngrams) = reader.read_ngrams()?;
+        let probability = numerator as f64 / denominator as f64;
+        for ngram in joined_ngrams.split(' ') {
+            probabilities.insert(CompactString::new(ngram), probability);
+            ngram_count += 1;
+        }
+    }
+
+    reader.finish(ngram_count)?;
+
+    Ok(probabilities)
+}
+
+/// Returns `true` if the given binary language model has been bootstrapped.
+pub(crate) fn is_bootstrapped_binary(bytes: &[u8]) -> bool {
+    BinaryModelReader::new(bytes).is_ok_and(|reader| reader.is_bootstrapped)
+}
+
+struct BinaryModelReader<'a> {
+    bytes: &'a [u8],
+    language_name: &'a str,
+    is_bootstrapped: bool,
+    ngram_count: usize,
+    fraction_count: usize,
+}
+
+impl<'a> BinaryModelReader<'a> {
+    fn new(bytes: &'a [u8]) -> io::Result<Self> {
+        let mut reader = Self {
+            bytes,
+            language_name: "",
+            is_bootstrapped: false,
+            ngram_count: 0,
+            fraction_count: 0,
+        };
+
+        if reader.read_bytes(MAGIC_BYTES.len())? != MAGIC_BYTES {
+            return Err(invalid_data("missing magic bytes of binary language model"));
+        }
+
+        let version = reader.read_bytes(1)?[0];
+
+        if version != FORMAT_VERSION {
+            return Err(invalid_data(&format!(
+                "unsupported version {version} of binary language model"
+            )));
+        }
+
+        reader.is_bootstrapped = reader.read_bytes(1)?[0] & BOOTSTRAPPED_FLAG != 0;
+        let language_name_length = reader.read_bytes(1)?[0] as usize;
+        reader.language_name = reader.read_str(language_name_length)?;
+        reader.ngram_count = reader.read_u32()? as usize;
+        reader.fraction_count = reader.read_u32()? as usize;
+
+        // Each ngram takes at least one byte, so that counts exceeding the remaining
+        // bytes are rejected before any memory is allocated for them.
+        let remaining_byte_count = reader.bytes.len();
+
+        if reader.ngram_count > remaining_byte_count
+            || reader
+                .fraction_count
+                .saturating_mul(PROBABILITY_HEADER_LENGTH)
+                > remaining_byte_count
+        {
+            return Err(invalid_data(
+                "counts in header exceed length of binary language model",
+            ));
+        }
+
+        Ok(reader)
+    }
+
+    /// Checks that the given number of ngrams which have been read matches the number
+    /// stated in the header and that no bytes are left over.
+    fn finish(&self, ngram_count: usize) -> io::Result<()> {
+        if ngram_count != self.ngram_count {
+            return Err(invalid_data(
+                "ngram count in header does not match binary language model",
+            ));
+        }
+        if !self.bytes.is_empty() {
+            return Err(invalid_data("trailing bytes in binary language model"));
+        }
+        Ok(())
+    }
+
+    /// Reads the numerator and denominator of the next probability
+    /// together with the space-separated ngrams sharing it.
+    fn read_ngrams(&mut self) -> io::Result<(u32, u32, &'a str)> {
+        let numerator = self.read_u32()?;
+        let denominator = self.read_u32()?;
+
+        if denominator == 0 {
+            return Err(invalid_data("zero denominator in binary language model"));
+        }
+
+        let length = self.read_u32()? as usize;
+        let ngrams = self.read_str(length)?;
+
+        Ok((numerator, denominator, ngrams))
+    }
+
+    fn read_u32(&mut self) -> io::Result<u32> {
+        Ok(u32::from_le_bytes(self.read_bytes(4)?.try_into().unwrap()))
+    }
+
+    fn read_str(&mut self, length: usize) -> io::Result<&'a str> {
+        std::str::from_utf8(self.read_bytes(length)?)
+            .map_err(|_| invalid_data("invalid UTF-8 in binary language model"))
+    }
+
+    fn read_bytes(&mut self, length: usize) -> io::Result<&'a [u8]> {
+        if self.bytes.len() < length {
+            return Err(invalid_data("truncated binary language model"));
+        }
+        let (bytes, remaining_bytes) = self.bytes.split_at(length);
+        self.bytes = remaining_bytes;
+        Ok(bytes)
+    }
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(ErrorKind::InvalidData, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const JSON: &str = r#"{"language":"ENGLISH","ngrams":{"1/2":"a b","1/4":"c"}}"#;
+
+    fn encode_json(json: &str) -> Vec<u8> {
+        encode_binary_model(&LanguageModel::from_json(json).unwrap())
+    }
+
+    #[test]
+    fn assert_binary_model_is_loaded_correctly() {
+        let binary = encode_json(JSON);
+        let model = load_binary_model(&binary).unwrap();
+
+        assert_eq!(model.len(), 3);
+        assert_eq!(model.get("a"), Some(&0.5));
+        assert_eq!(model.get("b"), Some(&0.5));
+        assert_eq!(model.get("c"), Some(&0.25));
+        assert!(!is_bootstrapped_binary(&binary));
+    }
+
+    #[test]
+    fn assert_bootstrapped_flag_is_preserved() {
+        let json = r#"{"language":"AFRIKAANS","ngrams":{"1/2":"a"},"bootstrapped":true}"#;
+        let binary = encode_json(json);
+        let model = decode_binary_model(&binary).unwrap();
+
+        assert!(is_bootstrapped_binary(&binary));
+        assert!(model.is_bootstrapped());
+        assert_eq!(model.to_json(), json);
+    }
+
+    #[test]
+    fn assert_invalid_binary_models_are_rejected() {
+        let binary = encode_json(JSON);
+        let mut wrong_version = binary.clone();
+        wrong_version[4] = FORMAT_VERSION + 1;
+        let mut trailing_bytes = binary.clone();
+        trailing_bytes.push(b'd');
+
+        for bytes in [
+            &binary[..binary.len() - 1],
+            &wrong_version[..],
+            &trailing_bytes[..],
+            JSON.as_bytes(),
+        ] {
+            assert_eq!(
+                load_binary_model(bytes).map_err(|error| error.kind()),
+                Err(ErrorKind::InvalidData)
+            );
+            assert_eq!(
+                decode_binary_model(bytes).map_err(|error| error.kind()),
+                Err(ErrorKind::InvalidData)
+            );
+        }
+
+        assert_eq!(
+            LanguageModel::from_json("{}").map_err(|error| error.kind()),
+            Err(ErrorKind::InvalidData)
+        );
+    }
+
+    #[test]
+    fn assert_binary_models_with_wrong_counts_in_header_are_rejected() {
+        let binary = encode_json(JSON);
+        // The counts follow the magic bytes, version, flags and the language name "ENGLISH".
+        let ngram_count_offset = 4 + 1 + 1 + 1 + 7;
+        let fraction_count_offset = ngram_count_offset + 4;
+
+        for (offset, count) in [
+            (ngram_count_offset, u32::MAX),
+            (ngram_count_offset, 4),
+            (ngram_count_offset, 2),
+            (fraction_count_offset, u32::MAX),
+            (fraction_count_offset, 1),
+        ] {
+            let mut bytes = binary.clone();
+            bytes[offset..offset + 4].copy_from_slice(&count.to_le_bytes());
+
+            assert_eq!(
+                load_binary_model(&bytes).map_err(|error| error.kind()),
+                Err(ErrorKind::InvalidData)
+            );
+        }
+    }
+
+    #[test]
+    fn assert_truncated_header_is_rejected() {
+        let binary = encode_json(JSON);
+        let header_length = 4 + 1 + 1 + 1 + 7 + 4 + 4;
+
+        for length in 0..header_length {
+            assert_eq!(
+                load_binary_model(&binary[..length]).map_err(|error| error.kind()),
+                Err(ErrorKind::InvalidData)
+            );
+        }
+    }
+}
@@ -14,11 +14,37 @@
  * limitations under the License.
  */
 
-use std::collections::HashSet;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::error::Error;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::io;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
 
+#[cfg(not(target_family = "wasm"))]
+use rayon::{ThreadPool, ThreadPoolBuilder};
+
+use crate::alphabet::Alphabet;
+use crate::cleanup::{compile_ignored_tokens_regex, CleanupPolicy};
+use crate::combiner::{DefaultScoreCombiner, ScoreCombiner};
+use crate::dedup::NEAR_DUPLICATE_THRESHOLD_MESSAGE;
 use crate::detector::LanguageDetector;
+use crate::fallback::Fallback;
 use crate::isocode::{IsoCode639_1, IsoCode639_3};
 use crate::language::Language;
+use crate::limits::{
+    DetectionLimits, MAXIMUM_INPUT_BYTES_MESSAGE, MAXIMUM_NGRAM_COUNT_MESSAGE,
+    MAXIMUM_PROCESSING_TIME_MESSAGE,
+};
+use crate::normalization::NormalizationForm;
+use crate::pack::{CustomModels, ModelPack};
+use crate::postprocessor::ConfidencePostProcessor;
+use crate::preprocessor::TextPreprocessor;
+use crate::region::Region;
+use crate::usage::{
+    LanguageUsageTracker, MAXIMUM_IDLE_TIME_MESSAGE, MAXIMUM_RESIDENT_LANGUAGES_MESSAGE,
+};
 
 pub(crate) const MISSING_LANGUAGE_MESSAGE: &str =
     "LanguageDetector needs at least 2 languages to choose from";
@@ -26,6 +52,65 @@ pub(crate) const MISSING_LANGUAGE_MESSAGE: &str =
 pub(crate) const MINIMUM_RELATIVE_DISTANCE_MESSAGE: &str =
     "Minimum relative distance must lie in between 0.0 and 0.99";
 
+pub(crate) const ALPHABET_DOMINANCE_RATIO_MESSAGE: &str =
+    "Alphabet dominance ratio must lie in between 0.0 and 1.0";
+pub(crate) const MAXIMUM_NUMBER_OF_THREADS_MESSAGE: &str =
+    "Maximum number of threads must be greater than zero";
+
+/// This enum describes the configuration mistakes which prevent
+/// [LanguageDetectorBuilder::try_build] from creating a [LanguageDetector].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum BuilderError {
+    /// Less than two distinct languages have been selected.
+    TooFewLanguages,
+    /// The minimum relative distance lies outside of 0.0 and 0.99.
+    InvalidMinimumRelativeDistance,
+    /// The alphabet dominance ratio lies outside of 0.0 and 1.0.
+    InvalidAlphabetDominanceRatio,
+    /// The near-duplicate threshold is not greater than 0.0 or greater than 1.0.
+    InvalidNearDuplicateThreshold,
+    /// The maximum number of input bytes is zero.
+    InvalidMaximumInputBytes,
+    /// The maximum number of ngrams is zero.
+    InvalidMaximumNgramCount,
+    /// The maximum processing time is zero.
+    InvalidMaximumProcessingTime,
+    /// The maximum idle time of language models is zero.
+    InvalidMaximumIdleTime,
+    /// The maximum number of resident languages is zero.
+    InvalidMaximumResidentLanguages,
+    /// The maximum number of threads is zero.
+    InvalidMaximumNumberOfThreads,
+    /// The custom language models of a language could not be registered.
+    MissingModels {
+        /// The language whose models could not be registered.
+        language: Language,
+        /// The reason why the models could not be registered.
+        message: String,
+    },
+}
+
+impl Display for BuilderError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let message = match self {
+            BuilderError::TooFewLanguages => MISSING_LANGUAGE_MESSAGE,
+            BuilderError::InvalidMinimumRelativeDistance => MINIMUM_RELATIVE_DISTANCE_MESSAGE,
+            BuilderError::InvalidAlphabetDominanceRatio => ALPHABET_DOMINANCE_RATIO_MESSAGE,
+            BuilderError::InvalidNearDuplicateThreshold => NEAR_DUPLICATE_THRESHOLD_MESSAGE,
+            BuilderError::InvalidMaximumInputBytes => MAXIMUM_INPUT_BYTES_MESSAGE,
+            BuilderError::InvalidMaximumNgramCount => MAXIMUM_NGRAM_COUNT_MESSAGE,
+            BuilderError::InvalidMaximumProcessingTime => MAXIMUM_PROCESSING_TIME_MESSAGE,
+            BuilderError::InvalidMaximumIdleTime => MAXIMUM_IDLE_TIME_MESSAGE,
+            BuilderError::InvalidMaximumResidentLanguages => MAXIMUM_RESIDENT_LANGUAGES_MESSAGE,
+            BuilderError::InvalidMaximumNumberOfThreads => MAXIMUM_NUMBER_OF_THREADS_MESSAGE,
+            BuilderError::MissingModels { message, .. } => message,
+        };
+        write!(f, "{message}")
+    }
+}
+
+impl Error for BuilderError {}
+
 /// This struct configures and creates an instance of [LanguageDetector].
 #[derive(Clone)]
 pub struct LanguageDetectorBuilder {
@@ -33,6 +118,33 @@ pub struct LanguageDetectorBuilder {
     minimum_relative_distance: f64,
     is_every_language_model_preloaded: bool,
     is_low_accuracy_mode_enabled: bool,
+    alphabet_dominance_ratio: f64,
+    is_homoglyph_normalization_enabled: bool,
+    is_symbol_filtering_enabled: bool,
+    ignored_tokens: BTreeSet<String>,
+    unicode_normalization: Option<NormalizationForm>,
+    is_graded_rule_confidence_enabled: bool,
+    is_transcript_preprocessing_enabled: bool,
+    is_all_caps_handling_enabled: bool,
+    is_eszett_restoration_enabled: bool,
+    is_cyrillic_ocr_normalization_enabled: bool,
+    model_pack: ModelPack,
+    custom_models: HashMap<Language, CustomModels>,
+    minimum_matched_ngram_count: u32,
+    short_text_threshold: usize,
+    near_duplicate_threshold: Option<f64>,
+    limits: DetectionLimits,
+    is_language_usage_tracking_enabled: bool,
+    maximum_idle_time: Option<Duration>,
+    maximum_resident_languages: Option<usize>,
+    cleanup_policy: CleanupPolicy,
+    fallback_chain: Vec<Fallback>,
+    preprocessors: Vec<Arc<dyn TextPreprocessor>>,
+    post_processors: Vec<Arc<dyn ConfidencePostProcessor>>,
+    score_combiner: Arc<dyn ScoreCombiner>,
+    #[cfg(not(target_family = "wasm"))]
+    thread_pool: Option<Arc<ThreadPool>>,
+    error: Option<BuilderError>,
 }
 
 impl LanguageDetectorBuilder {
@@ -71,39 +183,65 @@ impl LanguageDetectorBuilder {
         Self::from(Language::all_with_latin_script())
     }
 
+    /// Creates and returns an instance of `LanguageDetectorBuilder`
+    /// with all built-in languages supporting the given script.
+    ///
+    /// ⚠ Building the detector fails with [BuilderError::TooFewLanguages]
+    /// if less than two languages support the script.
+    pub fn from_all_languages_with_script(alphabet: Alphabet) -> Self {
+        Self::from(Language::all_with_script(alphabet))
+    }
+
+    /// Creates and returns an instance of `LanguageDetectorBuilder`
+    /// with all built-in languages spoken in the given region.
+    ///
+    /// ⚠ Building the detector fails with [BuilderError::TooFewLanguages]
+    /// if less than two languages are spoken in the region.
+    pub fn from_languages_spoken_in(region: Region) -> Self {
+        Self::from(Language::all_spoken_in(region))
+    }
+
     /// Creates and returns an instance of `LanguageDetectorBuilder`
     /// with all built-in languages except those specified in `languages`.
     ///
-    /// ⚠ Panics if less than two `languages` are used to build the
-    /// `LanguageDetector`.
+    /// ⚠ Building the detector fails with [BuilderError::TooFewLanguages]
+    /// if less than two languages remain.
     pub fn from_all_languages_without(languages: &[Language]) -> Self {
         let mut languages_to_load = Language::all();
         languages_to_load.retain(|it| !languages.contains(it));
-        if languages_to_load.len() < 2 {
-            panic!("{}", MISSING_LANGUAGE_MESSAGE);
-        }
         Self::from(languages_to_load)
     }
 
     /// Creates and returns an instance of `LanguageDetectorBuilder`
     /// with the specified `languages`.
     ///
-    /// ⚠ Panics if less than two `languages` are specified.
+    /// ⚠ Building the detector fails with [BuilderError::TooFewLanguages]
+    /// if less than two distinct `languages` are specified.
     pub fn from_languages(languages: &[Language]) -> Self {
-        if languages.len() < 2 {
-            panic!("{}", MISSING_LANGUAGE_MESSAGE);
-        }
         Self::from(languages.iter().cloned().collect())
     }
 
     /// Creates and returns an instance of `LanguageDetectorBuilder`
     /// with the languages specified by the respective ISO 639-1 codes.
     ///
-    /// ⚠ Panics if less than two `iso_codes` are specified.
+    /// ISO codes stored in configuration files can be parsed case-insensitively
+    /// with [str::parse]:
+    ///
+    /// ```
+    /// use lingua::{IsoCode639_1, LanguageDetectorBuilder};
+    ///
+    /// let iso_codes = "en, de, fr"
+    ///     .split(',')
+    ///     .map(|iso_code| iso_code.trim().parse::<IsoCode639_1>())
+    ///     .collect::<Result<Vec<_>, _>>()
+    ///     .unwrap();
+    ///
+    /// let detector = LanguageDetectorBuilder::from_iso_codes_639_1(&iso_codes).build();
+    /// ```
+    ///
+    /// ⚠ Building the detector fails with [BuilderError::TooFewLanguages]
+    /// if less than two distinct `iso_codes` are specified.
     pub fn from_iso_codes_639_1(iso_codes: &[IsoCode639_1]) -> Self {
-        if iso_codes.len() < 2 {
-            panic!("{}", MISSING_LANGUAGE_MESSAGE);
-        }
         let languages = iso_codes
             .iter()
             .map(Language::from_iso_code_639_1)
@@ -114,11 +252,9 @@ impl LanguageDetectorBuilder {
     /// Creates and returns an instance of `LanguageDetectorBuilder`
     /// with the languages specified by the respective ISO 639-3 codes.
     ///
-    /// ⚠ Panics if less than two `iso_codes` are specified.
+    /// ⚠ Building the detector fails with [BuilderError::TooFewLanguages]
+    /// if less than two distinct `iso_codes` are specified.
     pub fn from_iso_codes_639_3(iso_codes: &[IsoCode639_3]) -> Self {
-        if iso_codes.len() < 2 {
-            panic!("{}", MISSING_LANGUAGE_MESSAGE);
-        }
         let languages = iso_codes
             .iter()
             .map(Language::from_iso_code_639_3)
@@ -145,15 +281,640 @@ impl LanguageDetectorBuilder {
     /// returned as [`None`] which is the return value for cases
     /// where language detection is not reliably possible.
     ///
-    /// ⚠ Panics if `distance` is smaller than 0.0 or greater than 0.99.
+    /// ⚠ Building the detector fails with [BuilderError::InvalidMinimumRelativeDistance]
+    /// if `distance` is smaller than 0.0 or greater than 0.99.
     pub fn with_minimum_relative_distance(&mut self, distance: f64) -> &mut Self {
         if !(0.0..=0.99).contains(&distance) {
-            panic!("{}", MINIMUM_RELATIVE_DISTANCE_MESSAGE);
+            return self.fail(BuilderError::InvalidMinimumRelativeDistance);
         }
         self.minimum_relative_distance = distance;
         self
     }
 
+    /// Sets the share of words that the most frequent alphabet of the input text
+    /// must at least be written in so that only the languages using this alphabet
+    /// are considered.
+    ///
+    /// By default, the rule-based filter engine only keeps the languages of the
+    /// most frequent alphabet, even if it wins by 51% to 49%. For bilingual input
+    /// such as headlines or product listings, this can discard the correct language
+    /// prematurely. If the share of the most frequent alphabet is below the given
+    /// ratio, the languages of the second most frequent alphabet remain candidates
+    /// as well. A ratio of 0.0 restores the default behavior.
+    ///
+    /// ⚠ Building the detector fails with [BuilderError::InvalidAlphabetDominanceRatio]
+    /// if `ratio` is smaller than 0.0 or greater than 1.0.
+    pub fn with_alphabet_dominance_ratio(&mut self, ratio: f64) -> &mut Self {
+        if !(0.0..=1.0).contains(&ratio) {
+            return self.fail(BuilderError::InvalidAlphabetDominanceRatio);
+        }
+        self.alphabet_dominance_ratio = ratio;
+        self
+    }
+
+    /// Sets the minimum number of distinct trigrams, quadrigrams and fivegrams of the input
+    /// text that must occur in a language's models so that this language can be detected
+    /// at all.
+    ///
+    /// The minimum relative distance only requires a probability margin between the most
+    /// likely languages. For very short texts such as two-word inputs, this margin can be
+    /// caused by a single ngram which happens to be known to one language only. Requiring
+    /// a minimum number of matched ngrams prevents such flukes from deciding the result.
+    /// Languages with fewer matched ngrams receive a confidence value of 0.0. The counts
+    /// can be inspected with [LanguageDetector::compute_matched_ngram_counts].
+    ///
+    /// By default, the minimum number is 0, i.e. there is no such requirement.
+    pub fn with_minimum_matched_ngrams(&mut self, count: u32) -> &mut Self {
+        self.minimum_matched_ngram_count = count;
+        self
+    }
+
+    /// Sets the number of characters below which input texts are evaluated with unigrams,
+    /// bigrams and trigrams only, together with the rule-based filter engine.
+    ///
+    /// By default, texts shorter than 120 characters are evaluated with all ngram lengths
+    /// from unigrams up to fivegrams, so that short queries take nearly as long as paragraphs.
+    /// Skipping quadrigrams and fivegrams for very short texts reduces the detection time
+    /// for them considerably, but may reduce the detection accuracy for them as well.
+    ///
+    /// By default, the threshold is 0, i.e. all ngram lengths are evaluated.
+    pub fn with_short_text_threshold(&mut self, character_count: usize) -> &mut Self {
+        self.short_text_threshold = character_count;
+        self
+    }
+
+    /// Configures the batch methods such as [LanguageDetector::detect_languages_in_parallel_of]
+    /// to treat texts as duplicates of each other if the Jaccard similarity of their character
+    /// trigrams, estimated with MinHash, reaches the given threshold. The language of such
+    /// near-duplicates is detected once only and the result is reused for all of them.
+    ///
+    /// Exact duplicates are always detected once only. Near-duplicate detection is disabled
+    /// by default because the result for a near-duplicate may differ slightly from the one
+    /// for the text it is grouped with.
+    ///
+    /// ⚠ Building the detector fails with [BuilderError::InvalidNearDuplicateThreshold]
+    /// if the threshold is smaller than or equal to 0.0 or greater than 1.0.
+    pub fn with_near_duplicate_threshold(&mut self, threshold: f64) -> &mut Self {
+        if threshold <= 0.0 || threshold > 1.0 {
+            return self.fail(BuilderError::InvalidNearDuplicateThreshold);
+        }
+        self.near_duplicate_threshold = Some(threshold);
+        self
+    }
+
+    /// Sets the maximum size of an input text in bytes.
+    ///
+    /// Internet-facing services should limit the effort spent on a single request, so that
+    /// adversarial megabyte-scale inputs cannot exhaust their resources. Texts exceeding
+    /// this limit are rejected before they are split into words. Methods such as
+    /// [LanguageDetector::try_detect_language_of] then return
+    /// [DetectionError::InputTooLarge](crate::DetectionError::InputTooLarge), whereas
+    /// methods such as [LanguageDetector::detect_language_of] treat the text as one
+    /// whose language cannot be detected.
+    ///
+    /// By default, the size of input texts is not limited.
+    ///
+    /// ⚠ Building the detector fails with [BuilderError::InvalidMaximumInputBytes]
+    /// if `bytes` is zero.
+    pub fn with_maximum_input_bytes(&mut self, bytes: usize) -> &mut Self {
+        if bytes == 0 {
+            return self.fail(BuilderError::InvalidMaximumInputBytes);
+        }
+        self.limits.maximum_input_bytes = Some(bytes);
+        self
+    }
+
+    /// Sets the maximum number of ngrams of all lengths which are looked up in the
+    /// language models for a single input text.
+    ///
+    /// The effort of the statistical detection grows with the number of characters times
+    /// the number of evaluated ngram lengths. Unlike [LanguageDetectorBuilder::with_maximum_input_bytes],
+    /// this limit takes the cleanup of the text into account, so texts consisting mostly of
+    /// punctuation or digits are not affected. Duplicate ngrams are counted as often as they
+    /// occur. Texts exceeding this limit result in
+    /// [DetectionError::TooManyNgrams](crate::DetectionError::TooManyNgrams).
+    ///
+    /// By default, the number of ngrams is not limited.
+    ///
+    /// ⚠ Building the detector fails with [BuilderError::InvalidMaximumNgramCount]
+    /// if `count` is zero.
+    pub fn with_maximum_ngram_count(&mut self, count: usize) -> &mut Self {
+        if count == 0 {
+            return self.fail(BuilderError::InvalidMaximumNgramCount);
+        }
+        self.limits.maximum_ngram_count = Some(count);
+        self
+    }
+
+    /// Sets the maximum time spent on the detection of a single input text.
+    ///
+    /// The elapsed time is checked in between the processing stages and before the models
+    /// of each ngram length are looked up, so the detection may take somewhat longer than
+    /// the given time before it is aborted with
+    /// [DetectionError::Timeout](crate::DetectionError::Timeout). The time needed to load
+    /// language models lazily counts towards the limit, so models should be preloaded
+    /// with [LanguageDetectorBuilder::with_preloaded_language_models] if this limit is set.
+    ///
+    /// By default, the processing time is not limited. This limit is not supported
+    /// on WebAssembly targets without a system clock.
+    ///
+    /// ⚠ Building the detector fails with [BuilderError::InvalidMaximumProcessingTime]
+    /// if `time` is zero.
+    pub fn with_maximum_processing_time(&mut self, time: Duration) -> &mut Self {
+        if time.is_zero() {
+            return self.fail(BuilderError::InvalidMaximumProcessingTime);
+        }
+        self.limits.maximum_processing_time = Some(time);
+        self
+    }
+
+    /// Configures `LanguageDetectorBuilder` to track how often each language is detected
+    /// and when its language models have been used for the last time. The usage can be
+    /// retrieved with [LanguageDetector::language_usage] in order to right-size the set of
+    /// languages to the actual traffic mix. As idle language models can then be evicted with
+    /// [LanguageDetector::evict_idle_language_models], the detector does not share its
+    /// language models with other [LanguageDetector] instances.
+    ///
+    /// Usage tracking is not supported on WebAssembly targets without a system clock.
+    pub fn with_language_usage_tracking(&mut self) -> &mut Self {
+        self.is_language_usage_tracking_enabled = true;
+        self
+    }
+
+    /// Configures `LanguageDetectorBuilder` to track the usage of each language, see
+    /// [LanguageDetectorBuilder::with_language_usage_tracking], and to automatically unload
+    /// the language models of languages whose models have not been used for the given time.
+    ///
+    /// In long-running processes, this bounds the memory held by the language models to the
+    /// languages which actually occur in the traffic. Evicted models are reloaded on demand.
+    /// Idle languages are looked for while detecting languages, at most once a minute or once
+    /// per `maximum_idle_time` if that is shorter, so no background thread is involved.
+    /// The models which concurrent detections are evaluating are not evicted. The detector
+    /// does not share its language models with other [LanguageDetector] instances, so that
+    /// evicting them never affects other instances.
+    ///
+    /// ⚠ Building the detector fails with [BuilderError::InvalidMaximumIdleTime]
+    /// if `maximum_idle_time` is zero.
+    pub fn with_language_eviction(&mut self, maximum_idle_time: Duration) -> &mut Self {
+        if maximum_idle_time.is_zero() {
+            return self.fail(BuilderError::InvalidMaximumIdleTime);
+        }
+        self.maximum_idle_time = Some(maximum_idle_time);
+        self
+    }
+
+    /// Configures `LanguageDetectorBuilder` to track the usage of each language, see
+    /// [LanguageDetectorBuilder::with_language_usage_tracking], and to keep the language
+    /// models of at most the given number of languages in memory.
+    ///
+    /// The language models are embedded into the binary in Brotli-compressed form and each
+    /// language is only decompressed when a text requires its models for the first time.
+    /// This setting additionally bounds the memory held by the decompressed models: whenever
+    /// more languages are resident, the models of the least recently used languages are
+    /// unloaded and reloaded on demand. This allows to include all languages in the binary
+    /// without paying the full memory cost. The models of the languages evaluated for the
+    /// current text are never unloaded, so the maximum should not be smaller than the number
+    /// of languages a single text is typically evaluated against, otherwise models are
+    /// reloaded constantly. The models which concurrent detections are evaluating are not
+    /// unloaded either. The detector does not share its language models with other
+    /// [LanguageDetector] instances, so that unloading them never affects other instances.
+    ///
+    /// ```
+    /// use lingua::LanguageDetectorBuilder;
+    /// use lingua::Language::{English, French, German, Spanish};
+    ///
+    /// let detector = LanguageDetectorBuilder::from_languages(&[English, French, German, Spanish])
+    ///     .with_maximum_resident_languages(4)
+    ///     .build();
+    ///
+    /// assert_eq!(detector.detect_language_of("languages are awesome"), Some(English));
+    /// ```
+    ///
+    /// ⚠ Building the detector fails with [BuilderError::InvalidMaximumResidentLanguages]
+    /// if `count` is zero.
+    pub fn with_maximum_resident_languages(&mut self, count: usize) -> &mut Self {
+        if count == 0 {
+            return self.fail(BuilderError::InvalidMaximumResidentLanguages);
+        }
+        self.maximum_resident_languages = Some(count);
+        self
+    }
+
+    /// Sets the cleanup policy which defines the characters that are kept within words
+    /// depending on the alphabet that most letters of the input text belong to.
+    ///
+    /// By default, only letters are kept and all other characters separate words.
+    /// See [CleanupPolicy] for details.
+    pub fn with_cleanup_policy(&mut self, policy: CleanupPolicy) -> &mut Self {
+        self.cleanup_policy = policy;
+        self
+    }
+
+    /// Configures `LanguageDetectorBuilder` to normalize homoglyphs before the rules
+    /// of the rule-based filter engine are evaluated.
+    ///
+    /// Spam often mixes visually identical characters of the Latin, Cyrillic and Greek
+    /// alphabets, such as the Cyrillic `р` in `рaypal`, in order to evade filters. As the
+    /// rule-based filter engine counts the alphabets of each word, such words confuse it.
+    /// If this option is enabled, the characters of every word mixing these alphabets are
+    /// replaced by their homoglyphs in the alphabet the majority of the word is written in,
+    /// based on the confusables data of Unicode Technical Standard #39.
+    pub fn with_homoglyph_normalization(&mut self) -> &mut Self {
+        self.is_homoglyph_normalization_enabled = true;
+        self
+    }
+
+    /// Configures `LanguageDetectorBuilder` to remove emoji, pictographs and all other
+    /// codepoints of the Unicode category Symbol from the input text before it is split
+    /// into words.
+    ///
+    /// Social media posts often consist mostly of emoji and a few words. The built-in
+    /// splitting into words drops symbols, but characters kept within words by a
+    /// [CleanupPolicy] and [TextPreprocessor]s registered with
+    /// [LanguageDetectorBuilder::with_preprocessor] see the text as it is.
+    /// If this option is enabled, symbols are replaced with spaces before any
+    /// of them is applied, so that they neither count towards the alphabets
+    /// of the text nor merge the words around them.
+    pub fn with_symbol_filtering(&mut self) -> &mut Self {
+        self.is_symbol_filtering_enabled = true;
+        self
+    }
+
+    /// Configures `LanguageDetectorBuilder` to remove the given tokens, such as brand names,
+    /// product SKUs or code identifiers, from the input text before its language is detected.
+    /// Tokens are matched case-insensitively and only as a whole, not as part of longer words.
+    /// They may consist of several words and contain characters other than letters.
+    ///
+    /// Foreign brand names embedded in sentences skew the detection towards the language
+    /// the brand names appear to be written in, especially in short texts.
+    ///
+    /// ```
+    /// use lingua::Language::{English, Russian};
+    /// use lingua::LanguageDetectorBuilder;
+    ///
+    /// let detector = LanguageDetectorBuilder::from_languages(&[English, Russian])
+    ///     .with_ignored_tokens(&["Apple Watch", "iPhone"])
+    ///     .build();
+    ///
+    /// assert_eq!(
+    ///     detector.detect_language_of("Мой iPhone и Apple Watch разрядились"),
+    ///     Some(Russian)
+    /// );
+    /// ```
+    pub fn with_ignored_tokens(&mut self, tokens: &[&str]) -> &mut Self {
+        self.ignored_tokens
+            .extend(tokens.iter().map(|token| token.to_string()));
+        self
+    }
+
+    /// Configures `LanguageDetectorBuilder` to bring the input text into the given Unicode
+    /// normalization form before it is split into words.
+    ///
+    /// The language models and the rules of the rule-based filter engine expect precomposed
+    /// characters. Decomposed input, such as Vietnamese letters followed by combining
+    /// diacritics or Hangul syllables spelled out as individual jamo, is therefore neither
+    /// matched by the characters unique to a language nor by the ngrams of the models.
+    /// See [NormalizationForm] for details.
+    ///
+    /// ```
+    /// use lingua::Language::{English, Vietnamese};
+    /// use lingua::{LanguageDetectorBuilder, NormalizationForm};
+    ///
+    /// let detector = LanguageDetectorBuilder::from_languages(&[English, Vietnamese])
+    ///     .with_unicode_normalization(NormalizationForm::Nfc)
+    ///     .build();
+    ///
+    /// assert_eq!(
+    ///     detector.detect_language_of("Tie\u{0302}\u{0301}ng Vie\u{0323}\u{0302}t"),
+    ///     Some(Vietnamese)
+    /// );
+    /// ```
+    pub fn with_unicode_normalization(&mut self, form: NormalizationForm) -> &mut Self {
+        self.unicode_normalization = Some(form);
+        self
+    }
+
+    /// Configures `LanguageDetectorBuilder` to compute graded confidence values
+    /// if the language of a text is identified unambiguously by the rule engine,
+    /// e.g. because it is written in a script used by a single language only, such as Greek.
+    ///
+    /// By default, the identified language then receives the confidence value 1.0 and all
+    /// other languages receive 0.0, without consulting the ngram models. If this option is
+    /// enabled, the identified language still receives 1.0, but the other languages receive
+    /// the confidence values computed from the ngram models. This way, the margin of the
+    /// identified language over the runner-ups can be thresholded uniformly across scripts.
+    /// This is slower for texts written in such scripts.
+    ///
+    /// Note that the confidence values of such texts no longer form a probability distribution:
+    /// they sum to more than 1.0 because the value of the identified language is not
+    /// renormalized.
+    pub fn with_graded_rule_confidences(&mut self) -> &mut Self {
+        self.is_graded_rule_confidence_enabled = true;
+        self
+    }
+
+    /// Configures `LanguageDetectorBuilder` to preprocess input text as the output of
+    /// a speech recognition system.
+    ///
+    /// Transcripts contain artifacts such as timestamps, speaker labels and fillers,
+    /// e.g. `[00:01:02] SPEAKER 1: uh, bonjour`, which distort the detection of short
+    /// utterances. If this option is enabled, annotations in square, round or angle brackets,
+    /// timestamps and speaker labels at the start of lines are removed, and so are filler
+    /// words such as `uh`, `ähm` or `euh` unless the text consists of nothing else.
+    pub fn with_transcript_preprocessing(&mut self) -> &mut Self {
+        self.is_transcript_preprocessing_enabled = true;
+        self
+    }
+
+    /// Configures `LanguageDetectorBuilder` to treat text written in all-caps,
+    /// such as headlines or signs, specifically.
+    ///
+    /// A text is considered all-caps if it contains at least four cased letters of which
+    /// at least 90 % are uppercase. Such text is decapitalized with care for the
+    /// Turkish and Azerbaijani `İ`, which the default case mapping converts into `i`
+    /// followed by a combining dot, so that words such as `İSTANBUL` would be split in two.
+    pub fn with_all_caps_handling(&mut self) -> &mut Self {
+        self.is_all_caps_handling_enabled = true;
+        self
+    }
+
+    /// Configures `LanguageDetectorBuilder` to restore the letter `ß` in all-caps text.
+    ///
+    /// German text written in all-caps usually replaces `ß` with `SS`, e.g. `STRASSE`
+    /// instead of `STRAßE`, which makes it less similar to the German training data.
+    /// If this option is enabled and German is a candidate language after the rule-based
+    /// filtering, every `SS` of an all-caps text is replaced with `ß` where the German models
+    /// consider this spelling more likely. As the restoration may be wrong for texts in other
+    /// languages, it is disabled by default. This is slower for all-caps texts. In low accuracy
+    /// mode, the trigram models decide about the spelling instead of the quadrigram models,
+    /// which restores `ß` less reliably.
+    pub fn with_eszett_restoration(&mut self) -> &mut Self {
+        self.is_eszett_restoration_enabled = true;
+        self
+    }
+
+    /// Configures `LanguageDetectorBuilder` for Cyrillic text recognized by OCR from
+    /// scanned all-caps documents, such as typewritten Soviet-era records.
+    ///
+    /// The diacritics of `Ё`, `Ї` and `Ў` rarely survive the recognition of such
+    /// documents and `І` is frequently read as `И`. Unnormalized, the texts then look
+    /// like Russian, so that Belarusian and Ukrainian texts are misclassified. If this
+    /// option is enabled, these letters are folded into `Е`, `И` and `У` both in the texts
+    /// and in the language models, so that the languages are told apart by the remaining
+    /// evidence. Where the letters have been recognized, the rule-based filter engine still
+    /// takes them into account. As this loses information for correctly recognized texts,
+    /// it is disabled by default. The folded language models are held in memory separately
+    /// from the original ones.
+    pub fn with_cyrillic_ocr_normalization(&mut self) -> &mut Self {
+        self.is_cyrillic_ocr_normalization_enabled = true;
+        self
+    }
+
+    /// Configures `LanguageDetectorBuilder` to use the language models of the given pack.
+    ///
+    /// By default, the bundled models of [ModelPack::News] are used. [ModelPack::Custom] uses
+    /// the models registered with [ModelPack::register_models] for a language and the bundled
+    /// models for all remaining languages. The models of each pack are cached separately,
+    /// so detectors using different packs can be used side by side.
+    ///
+    /// ```no_run
+    /// use lingua::{LanguageDetectorBuilder, ModelPack};
+    /// use lingua::Language::{English, French, German};
+    ///
+    /// ModelPack::Custom
+    ///     .register_models(English, "/path/to/social-models/en")
+    ///     .unwrap();
+    ///
+    /// let detector = LanguageDetectorBuilder::from_languages(&[English, French, German])
+    ///     .with_model_pack(ModelPack::Custom)
+    ///     .build();
+    /// ```
+    pub fn with_model_pack(&mut self, model_pack: ModelPack) -> &mut Self {
+        self.model_pack = model_pack;
+        self
+    }
+
+    /// Configures `LanguageDetectorBuilder` to use the language models in the given directory
+    /// for the given language instead of the bundled ones, e.g. models of a specific domain
+    /// created with [LanguageModelFilesWriter](crate::LanguageModelFilesWriter).
+    ///
+    /// The directory must have the same layout as the directories `models` of the bundled
+    /// language model crates, i.e. it must contain the file `trigrams.json.br` and may
+    /// contain the files for the other ngram lengths. All languages without custom models
+    /// keep using the models of the selected [ModelPack]. This method can be called
+    /// multiple times for different languages, a later call for the same language
+    /// replaces the directory passed before.
+    ///
+    /// The custom models only apply to the detectors built by this builder. Such a detector
+    /// does not share any language models with other detectors, so the models of the
+    /// other languages are loaded into memory once more for it.
+    ///
+    /// ⚠ Building the detector fails with [BuilderError::MissingModels]
+    /// if the directory does not contain a valid trigram model.
+    ///
+    /// ```no_run
+    /// use lingua::LanguageDetectorBuilder;
+    /// use lingua::Language::{English, French, German};
+    ///
+    /// let detector = LanguageDetectorBuilder::from_languages(&[English, French, German])
+    ///     .with_custom_model_path(English, "/path/to/legal-models/en")
+    ///     .with_custom_model_path(German, "/path/to/legal-models/de")
+    ///     .build();
+    /// ```
+    pub fn with_custom_model_path<P: AsRef<Path>>(
+        &mut self,
+        language: Language,
+        directory: P,
+    ) -> &mut Self {
+        match CustomModels::from_directory(directory.as_ref()) {
+            Ok(models) => {
+                self.custom_models.insert(language, models);
+                self
+            }
+            Err(error) => self.fail(BuilderError::MissingModels {
+                language,
+                message: error.to_string(),
+            }),
+        }
+    }
+
+    /// Configures `LanguageDetectorBuilder` to use the given model of the given language
+    /// and ngram length instead of the bundled one, like
+    /// [LanguageDetectorBuilder::with_custom_model_path] does for model files.
+    ///
+    /// The bytes must be the contents of a Brotli-compressed binary model file such as
+    /// `trigrams.bin.br`, see [ModelPack::register_model_bytes] for details. This allows to
+    /// use models which have been fetched over the network, e.g. by a WebAssembly module.
+    /// The trigram model of a language must be passed first, passing it again replaces
+    /// all models of the language passed before.
+    ///
+    /// ⚠ Building the detector fails with [BuilderError::MissingModels]
+    /// if the bytes are not a valid model.
+    pub fn with_custom_model_bytes(
+        &mut self,
+        language: Language,
+        ngram_length: usize,
+        bytes: Vec<u8>,
+    ) -> &mut Self {
+        if let Err(error) = self.add_custom_model_bytes(language, ngram_length, bytes) {
+            return self.fail(BuilderError::MissingModels {
+                language,
+                message: error.to_string(),
+            });
+        }
+        self
+    }
+
+    pub(crate) fn add_custom_model_bytes(
+        &mut self,
+        language: Language,
+        ngram_length: usize,
+        bytes: Vec<u8>,
+    ) -> io::Result<()> {
+        let models = CustomModels::with_model_bytes(
+            self.custom_models.get(&language),
+            language,
+            ngram_length,
+            bytes,
+        )?;
+        self.custom_models.insert(language, models);
+        Ok(())
+    }
+
+    /// Sets the fallback chain which is consulted by [LanguageDetector::detect_language_of]
+    /// if the language of the given text cannot be reliably detected.
+    ///
+    /// Applications which must always output some language, such as indexing pipelines,
+    /// can encode this policy here instead of at every call site. The steps are tried
+    /// in the given order and the first one that applies determines the result.
+    /// Any previously configured fallback chain is replaced.
+    ///
+    /// ```
+    /// use lingua::{Fallback, LanguageDetectorBuilder};
+    /// use lingua::Language::{English, French, German};
+    ///
+    /// let detector = LanguageDetectorBuilder::from_languages(&[English, French, German])
+    ///     .with_fallback_chain(&[Fallback::MostLikelyLanguage, Fallback::Language(English)])
+    ///     .build();
+    ///
+    /// assert_eq!(detector.detect_language_of("123"), Some(English));
+    /// ```
+    pub fn with_fallback_chain(&mut self, fallback_chain: &[Fallback]) -> &mut Self {
+        self.fallback_chain = fallback_chain.to_vec();
+        self
+    }
+
+    /// Appends the given language to the end of the fallback chain, so that it is returned
+    /// whenever no language can be detected otherwise. See
+    /// [LanguageDetectorBuilder::with_fallback_chain] for details.
+    pub fn with_fallback_language(&mut self, language: Language) -> &mut Self {
+        self.fallback_chain.push(Fallback::Language(language));
+        self
+    }
+
+    /// Registers a preprocessor which rewrites the input text before it is split into words,
+    /// e.g. to strip markup, or which replaces the built-in splitting into words.
+    /// Preprocessors are applied in the order of their registration.
+    /// See [TextPreprocessor] for details and [MarkupStripper](crate::MarkupStripper) for a built-in preprocessor
+    /// which strips HTML and Markdown markup.
+    ///
+    /// ```
+    /// use lingua::Language::{English, German};
+    /// use lingua::LanguageDetectorBuilder;
+    ///
+    /// let detector = LanguageDetectorBuilder::from_languages(&[English, German])
+    ///     .with_preprocessor(|text: &str| text.replace("<b>", "").replace("</b>", ""))
+    ///     .build();
+    ///
+    /// assert_eq!(detector.detect_language_of("<b>Guten</b> Morgen"), Some(German));
+    /// ```
+    pub fn with_preprocessor<P: TextPreprocessor + 'static>(
+        &mut self,
+        preprocessor: P,
+    ) -> &mut Self {
+        self.preprocessors.push(Arc::new(preprocessor));
+        self
+    }
+
+    /// Registers a post-processor which inspects and rewrites the confidence values
+    /// before they are returned or used to determine the detected language.
+    /// Post-processors are applied in the order of their registration.
+    /// See [ConfidencePostProcessor] for details.
+    ///
+    /// ```
+    /// use lingua::Language::{Bokmal, English, Nynorsk};
+    /// use lingua::{Language, LanguageDetectorBuilder};
+    ///
+    /// let detector = LanguageDetectorBuilder::from_languages(&[Bokmal, English, Nynorsk])
+    ///     .with_post_processor(|confidence_values: &mut Vec<(Language, f64)>| {
+    ///         // Report Norwegian as Bokmal only
+    ///         let nynorsk_confidence = confidence_values
+    ///             .iter()
+    ///             .find(|(language, _)| *language == Nynorsk)
+    ///             .map_or(0.0, |(_, confidence)| *confidence);
+    ///         confidence_values.retain(|(language, _)| *language != Nynorsk);
+    ///         for (language, confidence) in confidence_values.iter_mut() {
+    ///             if *language == Bokmal {
+    ///                 *confidence += nynorsk_confidence;
+    ///             }
+    ///         }
+    ///     })
+    ///     .build();
+    ///
+    /// assert_eq!(detector.detect_language_of("Eg veit ikkje kva eg skal gjere"), Some(Bokmal));
+    /// ```
+    pub fn with_post_processor<P: ConfidencePostProcessor + 'static>(
+        &mut self,
+        post_processor: P,
+    ) -> &mut Self {
+        self.post_processors.push(Arc::new(post_processor));
+        self
+    }
+
+    /// Sets the strategy which combines the ngram log probabilities of the input text
+    /// into confidence values. See [ScoreCombiner] for details.
+    ///
+    /// By default, [DefaultScoreCombiner] is used. The post-processors registered with
+    /// [LanguageDetectorBuilder::with_post_processor] are applied to the combined values.
+    /// Languages detected unambiguously by the rule-based filter engine are not affected.
+    pub fn with_score_combiner<C: ScoreCombiner + 'static>(&mut self, combiner: C) -> &mut Self {
+        self.score_combiner = Arc::new(combiner);
+        self
+    }
+
+    /// Configures `LanguageDetectorBuilder` to run all parallel work of [LanguageDetector],
+    /// such as loading language models and computing the probabilities of ngrams,
+    /// in the given thread pool instead of in the global one of *rayon*.
+    ///
+    /// This is useful if the application manages its own thread pools and the detector
+    /// should not compete with them for CPU time.
+    #[cfg(not(target_family = "wasm"))]
+    pub fn with_thread_pool(&mut self, thread_pool: Arc<ThreadPool>) -> &mut Self {
+        self.thread_pool = Some(thread_pool);
+        self
+    }
+
+    /// Configures `LanguageDetectorBuilder` to run all parallel work of [LanguageDetector]
+    /// in a dedicated thread pool with the given maximum number of threads.
+    ///
+    /// By default, the global thread pool of *rayon* is used which spawns one thread
+    /// per logical CPU of the machine. In containers with low CPU limits, this can lead
+    /// to oversubscription.
+    ///
+    /// ⚠ Building the detector fails with [BuilderError::InvalidMaximumNumberOfThreads]
+    /// if `threads` is zero. Panics if the thread pool cannot be created.
+    #[cfg(not(target_family = "wasm"))]
+    pub fn with_maximum_number_of_threads(&mut self, threads: usize) -> &mut Self {
+        if threads == 0 {
+            return self.fail(BuilderError::InvalidMaximumNumberOfThreads);
+        }
+        let thread_pool = ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build()
+            .expect("thread pool could not be created");
+        self.with_thread_pool(Arc::new(thread_pool))
+    }
+
     /// Configures `LanguageDetectorBuilder` to preload all language models when creating
     /// the instance of [LanguageDetector].
     ///
@@ -162,6 +923,10 @@ impl LanguageDetectorBuilder {
     /// For web services, for instance, it is rather beneficial to preload all language
     /// models into memory to avoid unexpected latency while waiting for the
     /// service response. This method allows to switch between these two loading modes.
+    ///
+    /// All ngram models of the selected languages are loaded in parallel by
+    /// [LanguageDetectorBuilder::build], which only returns once they are in memory.
+    /// In low accuracy mode, only the trigram models are preloaded.
     pub fn with_preloaded_language_models(&mut self) -> &mut Self {
         self.is_every_language_model_preloaded = true;
         self
@@ -178,19 +943,168 @@ impl LanguageDetectorBuilder {
     /// for short texts consisting of less than 120 characters will drop significantly. However,
     /// detection accuracy for texts which are longer than 120 characters will remain mostly
     /// unaffected.
+    ///
+    /// In low accuracy mode, only the trigrams of a text are evaluated and only the trigram
+    /// models are loaded by [LanguageDetectorBuilder::with_preloaded_language_models].
+    /// Trigrams unknown to a language back off to their bigrams and unigrams, so that the
+    /// unigram and bigram models are still loaded lazily as soon as a text is evaluated.
+    /// The quadrigram and fivegram models, which make up the largest part of the memory
+    /// footprint, are never loaded. The memory actually used can be inspected with
+    /// [LanguageDetector::memory_usage].
     pub fn with_low_accuracy_mode(&mut self) -> &mut Self {
         self.is_low_accuracy_mode_enabled = true;
         self
     }
 
+    /// Configures `LanguageDetectorBuilder` with settings that work well for social media
+    /// content such as tweets, comments or chat messages.
+    ///
+    /// Such texts are usually short and noisy, so the high accuracy mode is used and even
+    /// the shortest posts are evaluated with all ngram lengths. A small minimum relative
+    /// distance of 0.1 rejects the most doubtful results, see
+    /// [LanguageDetectorBuilder::with_minimum_relative_distance]. All language models
+    /// are preloaded because this kind of content is typically classified by long-running
+    /// services which should not suffer from latency on the first request.
+    ///
+    /// As posts are often cluttered with emoji, spam evades filters with homoglyphs
+    /// and shouting is common, [LanguageDetectorBuilder::with_symbol_filtering],
+    /// [LanguageDetectorBuilder::with_homoglyph_normalization] and
+    /// [LanguageDetectorBuilder::with_all_caps_handling] are enabled as well.
+    pub fn for_social_media(&mut self) -> &mut Self {
+        self.minimum_relative_distance = 0.1;
+        self.is_every_language_model_preloaded = true;
+        self.is_low_accuracy_mode_enabled = false;
+        self.is_symbol_filtering_enabled = true;
+        self.is_homoglyph_normalization_enabled = true;
+        self.is_all_caps_handling_enabled = true;
+        self.short_text_threshold = 0;
+        self
+    }
+
+    /// Configures `LanguageDetectorBuilder` with settings that work well for search queries.
+    ///
+    /// Search queries often consist of only one or two words. In order to return a result for
+    /// as many queries as possible, the high accuracy mode is used and no minimum relative
+    /// distance is required. All language models are preloaded to avoid latency on the first
+    /// request.
+    ///
+    /// The short text threshold is reset to 0, so that queries are evaluated with all ngram
+    /// lengths, see [LanguageDetectorBuilder::with_short_text_threshold]. As queries are
+    /// frequently typed in all-caps, [LanguageDetectorBuilder::with_all_caps_handling]
+    /// is enabled as well.
+    pub fn for_search_queries(&mut self) -> &mut Self {
+        self.minimum_relative_distance = 0.0;
+        self.is_every_language_model_preloaded = true;
+        self.is_low_accuracy_mode_enabled = false;
+        self.is_all_caps_handling_enabled = true;
+        self.short_text_threshold = 0;
+        self
+    }
+
+    /// Configures `LanguageDetectorBuilder` with settings that work well for long documents
+    /// such as articles, e-mails or books.
+    ///
+    /// For texts which are longer than 120 characters, trigrams are sufficient to detect the
+    /// language reliably, so the low accuracy mode is enabled to save memory. As the distance
+    /// between the language probabilities grows with the length of the input text, a minimum
+    /// relative distance of 0.5 is required.
+    ///
+    /// Text extracted from PDF files or word processors often contains ligatures such as `ﬁ`
+    /// and decomposed characters, so the documents are brought into [NormalizationForm::Nfkc],
+    /// see [LanguageDetectorBuilder::with_unicode_normalization].
+    pub fn for_long_documents(&mut self) -> &mut Self {
+        self.minimum_relative_distance = 0.5;
+        self.is_every_language_model_preloaded = false;
+        self.is_low_accuracy_mode_enabled = true;
+        self.unicode_normalization = Some(NormalizationForm::Nfkc);
+        self
+    }
+
     /// Creates and returns the configured instance of [LanguageDetector].
+    ///
+    /// ⚠ Panics if the configuration is invalid, see [LanguageDetectorBuilder::try_build].
     pub fn build(&mut self) -> LanguageDetector {
-        LanguageDetector::from(
+        self.try_build().unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /// Creates and returns the configured instance of [LanguageDetector] like
+    /// [LanguageDetectorBuilder::build] does, but returns an error instead of panicking
+    /// if the configuration is invalid. If several settings are invalid, the error
+    /// describes the first one that has been made. Too few languages are reported
+    /// with [BuilderError::TooFewLanguages] only if all settings are valid.
+    ///
+    /// ```
+    /// use lingua::{BuilderError, LanguageDetectorBuilder};
+    /// use lingua::Language::{English, German};
+    ///
+    /// let result = LanguageDetectorBuilder::from_languages(&[English, German])
+    ///     .with_minimum_relative_distance(1.5)
+    ///     .try_build();
+    ///
+    /// assert_eq!(result.err(), Some(BuilderError::InvalidMinimumRelativeDistance));
+    /// ```
+    pub fn try_build(&mut self) -> Result<LanguageDetector, BuilderError> {
+        if let Some(error) = &self.error {
+            return Err(error.clone());
+        }
+        if self.languages.len() < 2 {
+            return Err(BuilderError::TooFewLanguages);
+        }
+
+        let mut detector = LanguageDetector::from(
             self.languages.clone(),
             self.minimum_relative_distance,
-            self.is_every_language_model_preloaded,
+            false,
             self.is_low_accuracy_mode_enabled,
-        )
+        );
+        detector.alphabet_dominance_ratio = self.alphabet_dominance_ratio;
+        detector.is_homoglyph_normalization_enabled = self.is_homoglyph_normalization_enabled;
+        detector.is_symbol_filtering_enabled = self.is_symbol_filtering_enabled;
+        detector.ignored_tokens_regex = compile_ignored_tokens_regex(&self.ignored_tokens);
+        detector.unicode_normalization = self.unicode_normalization;
+        detector.is_graded_rule_confidence_enabled = self.is_graded_rule_confidence_enabled;
+        detector.is_transcript_preprocessing_enabled = self.is_transcript_preprocessing_enabled;
+        detector.is_all_caps_handling_enabled = self.is_all_caps_handling_enabled;
+        detector.is_eszett_restoration_enabled = self.is_eszett_restoration_enabled;
+        detector.is_cyrillic_ocr_normalization_enabled = self.is_cyrillic_ocr_normalization_enabled;
+        detector.minimum_matched_ngram_count = self.minimum_matched_ngram_count;
+        detector.short_text_threshold = self.short_text_threshold;
+        detector.near_duplicate_threshold = self.near_duplicate_threshold;
+        detector.limits = self.limits;
+
+        if self.is_language_usage_tracking_enabled
+            || self.maximum_idle_time.is_some()
+            || self.maximum_resident_languages.is_some()
+        {
+            detector.usage_tracker = Some(LanguageUsageTracker::new(
+                &detector.languages,
+                self.maximum_idle_time,
+                self.maximum_resident_languages,
+            ));
+        }
+        detector.word_regexes = self.cleanup_policy.compile_word_regexes();
+        detector.fallback_chain = self.fallback_chain.clone();
+        detector.preprocessors = self.preprocessors.clone();
+        detector.post_processors = self.post_processors.clone();
+        detector.score_combiner = self.score_combiner.clone();
+        detector.use_model_pack(self.model_pack);
+
+        if !self.custom_models.is_empty() {
+            detector.use_custom_models(self.custom_models.clone());
+        } else if detector.usage_tracker.is_some() {
+            detector.use_own_language_models();
+        }
+
+        #[cfg(not(target_family = "wasm"))]
+        {
+            detector.thread_pool = self.thread_pool.clone();
+        }
+
+        if self.is_every_language_model_preloaded {
+            detector.preload_language_models(&detector.languages);
+        }
+
+        Ok(detector)
     }
 
     fn from(languages: HashSet<Language>) -> Self {
@@ -199,13 +1113,56 @@ impl LanguageDetectorBuilder {
             minimum_relative_distance: 0.0,
             is_every_language_model_preloaded: false,
             is_low_accuracy_mode_enabled: false,
+            alphabet_dominance_ratio: 0.0,
+            is_homoglyph_normalization_enabled: false,
+            is_symbol_filtering_enabled: false,
+            ignored_tokens: BTreeSet::new(),
+            unicode_normalization: None,
+            is_graded_rule_confidence_enabled: false,
+            is_transcript_preprocessing_enabled: false,
+            is_all_caps_handling_enabled: false,
+            is_eszett_restoration_enabled: false,
+            is_cyrillic_ocr_normalization_enabled: false,
+            model_pack: ModelPack::News,
+            custom_models: HashMap::new(),
+            minimum_matched_ngram_count: 0,
+            short_text_threshold: 0,
+            near_duplicate_threshold: None,
+            limits: DetectionLimits::default(),
+            is_language_usage_tracking_enabled: false,
+            maximum_idle_time: None,
+            maximum_resident_languages: None,
+            cleanup_policy: CleanupPolicy::new(),
+            fallback_chain: vec![],
+            preprocessors: vec![],
+            post_processors: vec![],
+            score_combiner: Arc::new(DefaultScoreCombiner),
+            #[cfg(not(target_family = "wasm"))]
+            thread_pool: None,
+            error: None,
+        }
+    }
+
+    /// Records the first configuration mistake, which is reported when building the detector.
+    fn fail(&mut self, error: BuilderError) -> &mut Self {
+        if self.error.is_none() {
+            self.error = Some(error);
         }
+        self
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::io::Write;
+
+    use brotli::CompressorWriter;
+    use tempfile::{tempdir, NamedTempFile};
+
     use super::*;
+    use crate::binary::encode_binary_model;
+    use crate::model::LanguageModel;
+    use crate::writer::LanguageModelFilesWriter;
 
     #[test]
     fn assert_detector_can_be_built_from_all_languages() {
@@ -251,6 +1208,34 @@ mod tests {
         assert_eq!(builder.languages, Language::all_with_latin_script());
     }
 
+    #[test]
+    fn assert_detector_can_be_built_from_languages_with_script() {
+        let builder = LanguageDetectorBuilder::from_all_languages_with_script(Alphabet::Han);
+        assert_eq!(
+            builder.languages,
+            hashset!(Language::Chinese, Language::Japanese)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "LanguageDetector needs at least 2 languages to choose from")]
+    fn assert_detector_cannot_be_built_from_script_of_single_language() {
+        LanguageDetectorBuilder::from_all_languages_with_script(Alphabet::Greek).build();
+    }
+
+    #[test]
+    fn assert_detector_can_be_built_from_languages_spoken_in_region() {
+        let builder = LanguageDetectorBuilder::from_languages_spoken_in(Region::Caucasus);
+        assert_eq!(
+            builder.languages,
+            hashset!(
+                Language::Armenian,
+                Language::Azerbaijani,
+                Language::Georgian
+            )
+        );
+    }
+
     #[test]
     fn assert_detector_can_be_built_from_blacklist() {
         let builder = LanguageDetectorBuilder::from_all_languages_without(&[
@@ -273,7 +1258,7 @@ mod tests {
             .cloned()
             .collect::<Vec<_>>();
 
-        LanguageDetectorBuilder::from_all_languages_without(&languages);
+        LanguageDetectorBuilder::from_all_languages_without(&languages).build();
     }
 
     #[test]
@@ -290,7 +1275,7 @@ mod tests {
     #[test]
     #[should_panic(expected = "LanguageDetector needs at least 2 languages to choose from")]
     fn assert_detector_cannot_be_built_from_too_short_whitelist() {
-        LanguageDetectorBuilder::from_languages(&[Language::German]);
+        LanguageDetectorBuilder::from_languages(&[Language::German]).build();
     }
 
     #[test]
@@ -307,7 +1292,7 @@ mod tests {
     #[test]
     #[should_panic(expected = "LanguageDetector needs at least 2 languages to choose from")]
     fn assert_detector_cannot_be_built_from_too_few_iso_639_1_codes() {
-        LanguageDetectorBuilder::from_iso_codes_639_1(&[IsoCode639_1::DE]);
+        LanguageDetectorBuilder::from_iso_codes_639_1(&[IsoCode639_1::DE]).build();
     }
 
     #[test]
@@ -324,18 +1309,585 @@ mod tests {
     #[test]
     #[should_panic(expected = "LanguageDetector needs at least 2 languages to choose from")]
     fn assert_detector_cannot_be_built_from_too_few_iso_639_3_codes() {
-        LanguageDetectorBuilder::from_iso_codes_639_3(&[IsoCode639_3::DEU]);
+        LanguageDetectorBuilder::from_iso_codes_639_3(&[IsoCode639_3::DEU]).build();
+    }
+
+    #[test]
+    fn assert_language_models_are_preloaded_when_building_detector() {
+        let languages = [Language::Basque, Language::Esperanto];
+        let report = LanguageDetectorBuilder::from_languages(&languages)
+            .with_preloaded_language_models()
+            .build()
+            .memory_usage();
+
+        for language in languages {
+            for ngram_length in 1..=5 {
+                assert!(report.language_model_bytes(language, ngram_length) > 0);
+            }
+        }
+    }
+
+    #[test]
+    fn assert_detector_can_be_configured_for_social_media() {
+        let mut builder = LanguageDetectorBuilder::from_all_languages();
+        builder.with_low_accuracy_mode().for_social_media();
+
+        assert_eq!(builder.minimum_relative_distance, 0.1);
+        assert!(builder.is_every_language_model_preloaded);
+        assert!(!builder.is_low_accuracy_mode_enabled);
+        assert!(builder.is_symbol_filtering_enabled);
+        assert!(builder.is_homoglyph_normalization_enabled);
+        assert!(builder.is_all_caps_handling_enabled);
+    }
+
+    #[test]
+    fn assert_social_media_preset_detects_language_of_emoji_heavy_post() {
+        let languages = [
+            Language::English,
+            Language::German,
+            Language::French,
+            Language::Spanish,
+            Language::Russian,
+            Language::Ukrainian,
+            Language::Turkish,
+        ];
+        // The first two words mix Cyrillic homoglyphs into Latin text.
+        let post = "\u{0440}aypal \u{0430}cc\u{043E}unt 😱😱";
+
+        assert_eq!(
+            LanguageDetectorBuilder::from_languages(&languages)
+                .build()
+                .detect_language_of(post),
+            Some(Language::Russian)
+        );
+        assert_eq!(
+            LanguageDetectorBuilder::from_languages(&languages)
+                .for_social_media()
+                .build()
+                .detect_language_of(post),
+            Some(Language::English)
+        );
+    }
+
+    #[test]
+    fn assert_detector_can_be_configured_for_search_queries() {
+        let mut builder = LanguageDetectorBuilder::from_all_languages();
+        builder
+            .with_minimum_relative_distance(0.5)
+            .for_search_queries();
+
+        assert_eq!(builder.minimum_relative_distance, 0.0);
+        assert!(builder.is_every_language_model_preloaded);
+        assert!(!builder.is_low_accuracy_mode_enabled);
+        assert!(builder.is_all_caps_handling_enabled);
+    }
+
+    #[test]
+    fn assert_search_query_preset_detects_language_of_two_word_query() {
+        let languages = [
+            Language::English,
+            Language::German,
+            Language::French,
+            Language::Spanish,
+            Language::Russian,
+            Language::Ukrainian,
+            Language::Turkish,
+        ];
+        let query = "billets avion";
+        let confidence_of_french = |builder: &mut LanguageDetectorBuilder| {
+            builder
+                .build()
+                .compute_language_confidence(query, Language::French)
+        };
+
+        let mut builder = LanguageDetectorBuilder::from_languages(&languages);
+        builder.with_short_text_threshold(120);
+
+        assert!(confidence_of_french(&mut builder) < 0.5);
+
+        builder.for_search_queries();
+
+        assert_eq!(builder.short_text_threshold, 0);
+        assert!(confidence_of_french(&mut builder) > 0.5);
+        assert_eq!(
+            builder.build().detect_language_of(query),
+            Some(Language::French)
+        );
+    }
+
+    #[test]
+    fn assert_detector_can_be_configured_for_long_documents() {
+        let mut builder = LanguageDetectorBuilder::from_all_languages();
+        builder.for_long_documents();
+
+        assert_eq!(builder.minimum_relative_distance, 0.5);
+        assert!(!builder.is_every_language_model_preloaded);
+        assert!(builder.is_low_accuracy_mode_enabled);
+        assert_eq!(builder.unicode_normalization, Some(NormalizationForm::Nfkc));
     }
 
     #[test]
     #[should_panic(expected = "Minimum relative distance must lie in between 0.0 and 0.99")]
     fn assert_detector_cannot_be_built_from_too_small_minimum_relative_distance() {
-        LanguageDetectorBuilder::from_all_languages().with_minimum_relative_distance(-2.3);
+        LanguageDetectorBuilder::from_all_languages()
+            .with_minimum_relative_distance(-2.3)
+            .build();
     }
 
     #[test]
     #[should_panic(expected = "Minimum relative distance must lie in between 0.0 and 0.99")]
     fn assert_detector_cannot_be_built_from_too_large_minimum_relative_distance() {
-        LanguageDetectorBuilder::from_all_languages().with_minimum_relative_distance(1.7);
+        LanguageDetectorBuilder::from_all_languages()
+            .with_minimum_relative_distance(1.7)
+            .build();
+    }
+
+    #[test]
+    fn assert_invalid_configuration_is_reported_by_try_build() {
+        assert!(
+            LanguageDetectorBuilder::from_languages(&[Language::English, Language::German])
+                .try_build()
+                .is_ok()
+        );
+        assert_eq!(
+            LanguageDetectorBuilder::from_languages(&[Language::German, Language::German])
+                .try_build()
+                .err(),
+            Some(BuilderError::TooFewLanguages)
+        );
+        assert_eq!(
+            LanguageDetectorBuilder::from_languages(&[Language::German])
+                .with_minimum_relative_distance(1.5)
+                .try_build()
+                .err(),
+            Some(BuilderError::InvalidMinimumRelativeDistance)
+        );
+        assert_eq!(
+            LanguageDetectorBuilder::from_all_languages()
+                .with_maximum_ngram_count(0)
+                .with_alphabet_dominance_ratio(1.5)
+                .try_build()
+                .err(),
+            Some(BuilderError::InvalidMaximumNgramCount)
+        );
+
+        let directory = tempdir().unwrap();
+        let error = LanguageDetectorBuilder::from_all_languages()
+            .with_custom_model_path(Language::English, directory.path())
+            .try_build()
+            .err()
+            .unwrap();
+        assert!(matches!(
+            error,
+            BuilderError::MissingModels {
+                language: Language::English,
+                ..
+            }
+        ));
+        assert!(error.to_string().starts_with("No trigram model found"));
+    }
+
+    #[test]
+    fn assert_detector_can_be_built_with_alphabet_dominance_ratio() {
+        let mut builder = LanguageDetectorBuilder::from_all_languages();
+        assert_eq!(builder.alphabet_dominance_ratio, 0.0);
+
+        builder.with_alphabet_dominance_ratio(0.7);
+        assert_eq!(builder.alphabet_dominance_ratio, 0.7);
+        assert_eq!(builder.build().alphabet_dominance_ratio, 0.7);
+    }
+
+    #[test]
+    #[should_panic(expected = "Alphabet dominance ratio must lie in between 0.0 and 1.0")]
+    fn assert_detector_cannot_be_built_from_too_small_alphabet_dominance_ratio() {
+        LanguageDetectorBuilder::from_all_languages()
+            .with_alphabet_dominance_ratio(-0.1)
+            .build();
+    }
+
+    #[test]
+    #[should_panic(expected = "Alphabet dominance ratio must lie in between 0.0 and 1.0")]
+    fn assert_detector_cannot_be_built_from_too_large_alphabet_dominance_ratio() {
+        LanguageDetectorBuilder::from_all_languages()
+            .with_alphabet_dominance_ratio(1.5)
+            .build();
+    }
+
+    #[test]
+    fn assert_detector_can_be_built_with_maximum_number_of_threads() {
+        let mut builder =
+            LanguageDetectorBuilder::from_languages(&[Language::English, Language::German]);
+        builder.with_maximum_number_of_threads(2);
+
+        let thread_pool = builder.thread_pool.clone().unwrap();
+        assert_eq!(thread_pool.current_num_threads(), 2);
+
+        let detector = builder.build();
+        assert!(Arc::ptr_eq(
+            detector.thread_pool.as_ref().unwrap(),
+            &thread_pool
+        ));
+        assert_eq!(
+            detector.detect_language_of("languages are awesome"),
+            Some(Language::English)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Maximum number of threads must be greater than zero")]
+    fn assert_detector_cannot_be_built_with_zero_threads() {
+        LanguageDetectorBuilder::from_all_languages()
+            .with_maximum_number_of_threads(0)
+            .build();
+    }
+
+    #[test]
+    fn assert_detector_can_be_built_with_fallback_chain() {
+        let mut builder = LanguageDetectorBuilder::from_all_languages();
+        builder
+            .with_fallback_language(Language::German)
+            .with_fallback_chain(&[Fallback::MostLikelyLanguage])
+            .with_fallback_language(Language::English);
+
+        assert_eq!(
+            builder.fallback_chain,
+            vec![
+                Fallback::MostLikelyLanguage,
+                Fallback::Language(Language::English)
+            ]
+        );
+        assert_eq!(builder.build().fallback_chain, builder.fallback_chain);
+    }
+
+    #[test]
+    fn assert_detector_can_be_built_with_homoglyph_normalization() {
+        let mut builder = LanguageDetectorBuilder::from_all_languages();
+        assert!(!builder.is_homoglyph_normalization_enabled);
+
+        builder.with_homoglyph_normalization();
+        assert!(builder.is_homoglyph_normalization_enabled);
+        assert!(builder.build().is_homoglyph_normalization_enabled);
+    }
+
+    #[test]
+    fn assert_detector_can_be_built_with_graded_rule_confidences() {
+        let mut builder = LanguageDetectorBuilder::from_all_languages();
+        assert!(!builder.is_graded_rule_confidence_enabled);
+
+        builder.with_graded_rule_confidences();
+        assert!(builder.is_graded_rule_confidence_enabled);
+        assert!(builder.build().is_graded_rule_confidence_enabled);
+    }
+
+    #[test]
+    fn assert_detector_can_be_built_with_transcript_preprocessing() {
+        let mut builder = LanguageDetectorBuilder::from_all_languages();
+        assert!(!builder.is_transcript_preprocessing_enabled);
+
+        builder.with_transcript_preprocessing();
+        assert!(builder.is_transcript_preprocessing_enabled);
+        assert!(builder.build().is_transcript_preprocessing_enabled);
+    }
+
+    #[test]
+    fn assert_detector_can_be_built_with_cyrillic_ocr_normalization() {
+        let mut builder = LanguageDetectorBuilder::from_all_languages();
+        assert!(!builder.is_cyrillic_ocr_normalization_enabled);
+
+        builder.with_cyrillic_ocr_normalization();
+        assert!(builder.is_cyrillic_ocr_normalization_enabled);
+        assert!(builder.build().is_cyrillic_ocr_normalization_enabled);
+    }
+
+    #[test]
+    fn assert_detector_can_be_built_with_all_caps_handling() {
+        let mut builder = LanguageDetectorBuilder::from_all_languages();
+        assert!(!builder.is_all_caps_handling_enabled);
+
+        builder.with_all_caps_handling();
+        assert!(builder.is_all_caps_handling_enabled);
+        assert!(builder.build().is_all_caps_handling_enabled);
+    }
+
+    #[test]
+    fn assert_detector_can_be_built_with_eszett_restoration() {
+        let mut builder = LanguageDetectorBuilder::from_all_languages();
+        assert!(!builder.is_eszett_restoration_enabled);
+
+        builder.with_eszett_restoration();
+        assert!(builder.is_eszett_restoration_enabled);
+        assert!(builder.build().is_eszett_restoration_enabled);
+    }
+
+    #[test]
+    fn assert_detector_can_be_built_with_model_pack() {
+        let mut builder = LanguageDetectorBuilder::from_all_languages();
+        assert_eq!(builder.model_pack, ModelPack::News);
+
+        builder.with_model_pack(ModelPack::Custom);
+        assert_eq!(builder.model_pack, ModelPack::Custom);
+        assert_eq!(builder.build().model_pack, ModelPack::Custom);
+    }
+
+    #[test]
+    fn assert_detector_can_be_built_with_custom_model_path() {
+        let write_english_models = |text: &str| {
+            let mut training_file = NamedTempFile::new().unwrap();
+            training_file.write_all(text.as_bytes()).unwrap();
+            let directory = tempdir().unwrap();
+
+            LanguageModelFilesWriter::create_and_write_language_model_files(
+                training_file.path(),
+                directory.path(),
+                &Language::English,
+                "\\p{L}",
+            )
+            .unwrap();
+
+            directory
+        };
+        let german_directory =
+            write_english_models("Ich bin heute Morgen mit dem Fahrrad zur Arbeit gefahren.");
+        let dutch_directory =
+            write_english_models("Ik ben vanmorgen met de fiets naar mijn werk gereden.");
+        let languages = [Language::English, Language::German];
+        let text = "Heute Morgen bin ich mit dem Fahrrad gefahren";
+
+        let mut builder = LanguageDetectorBuilder::from_languages(&languages);
+        builder.with_custom_model_path(Language::English, german_directory.path());
+        assert!(builder.custom_models.contains_key(&Language::English));
+        assert_eq!(builder.model_pack, ModelPack::News);
+
+        let detector = builder.build();
+        let confidence_values = detector.compute_language_confidence_values(text);
+        assert_eq!(detector.detect_language_of(text), Some(Language::English));
+
+        let other_detector = LanguageDetectorBuilder::from_languages(&languages)
+            .with_custom_model_path(Language::English, dutch_directory.path())
+            .build();
+        let bundled_detector = LanguageDetectorBuilder::from_languages(&languages).build();
+
+        assert_ne!(
+            other_detector.compute_language_confidence_values(text),
+            confidence_values
+        );
+        assert_eq!(
+            bundled_detector.detect_language_of(text),
+            Some(Language::German)
+        );
+        assert_eq!(
+            detector.compute_language_confidence_values(text),
+            confidence_values
+        );
+        assert!(!ModelPack::Custom.languages().contains(&Language::English));
+    }
+
+    #[test]
+    #[should_panic(expected = "No trigram model found")]
+    fn assert_detector_cannot_be_built_with_invalid_custom_model_path() {
+        let directory = tempdir().unwrap();
+        LanguageDetectorBuilder::from_all_languages()
+            .with_custom_model_path(Language::English, directory.path())
+            .build();
+    }
+
+    #[test]
+    fn assert_detector_can_be_built_with_custom_model_bytes() {
+        let json = r#"{"language":"WELSH","ngrams":{"1/2":"qqx xqq"}}"#;
+        let mut bytes = vec![];
+        let mut writer = CompressorWriter::new(&mut bytes, 4096, 11, 22);
+        writer
+            .write_all(&encode_binary_model(
+                &LanguageModel::from_json(json).unwrap(),
+            ))
+            .unwrap();
+        drop(writer);
+
+        let mut builder =
+            LanguageDetectorBuilder::from_languages(&[Language::English, Language::Welsh]);
+        builder.with_custom_model_bytes(Language::Welsh, 3, bytes);
+        assert!(builder.custom_models.contains_key(&Language::Welsh));
+        assert_eq!(
+            builder.build().detect_language_of("qqx"),
+            Some(Language::Welsh)
+        );
+        assert!(!ModelPack::Custom.languages().contains(&Language::Welsh));
+
+        let error = LanguageDetectorBuilder::from_languages(&[Language::English, Language::Welsh])
+            .with_custom_model_bytes(Language::Welsh, 3, b"invalid".to_vec())
+            .try_build()
+            .err()
+            .unwrap();
+        assert!(matches!(
+            error,
+            BuilderError::MissingModels {
+                language: Language::Welsh,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn assert_detector_can_be_built_with_minimum_matched_ngrams() {
+        let mut builder = LanguageDetectorBuilder::from_all_languages();
+        assert_eq!(builder.minimum_matched_ngram_count, 0);
+
+        builder.with_minimum_matched_ngrams(5);
+        assert_eq!(builder.minimum_matched_ngram_count, 5);
+        assert_eq!(builder.build().minimum_matched_ngram_count, 5);
+    }
+
+    #[test]
+    fn assert_detector_can_be_built_with_short_text_threshold() {
+        let mut builder = LanguageDetectorBuilder::from_all_languages();
+        assert_eq!(builder.short_text_threshold, 0);
+
+        builder.with_short_text_threshold(20);
+        assert_eq!(builder.short_text_threshold, 20);
+        assert_eq!(builder.build().short_text_threshold, 20);
+    }
+
+    #[test]
+    fn assert_detector_can_be_built_with_preprocessors() {
+        let mut builder = LanguageDetectorBuilder::from_all_languages();
+        assert!(builder.preprocessors.is_empty());
+
+        builder
+            .with_preprocessor(|text: &str| text.trim().to_string())
+            .with_preprocessor(|text: &str| text.replace('_', " "));
+        assert_eq!(builder.preprocessors.len(), 2);
+        assert_eq!(builder.build().preprocessors.len(), 2);
+    }
+
+    #[test]
+    fn assert_detector_can_be_built_with_symbol_filtering() {
+        let mut builder = LanguageDetectorBuilder::from_all_languages();
+        assert!(!builder.is_symbol_filtering_enabled);
+
+        builder.with_symbol_filtering();
+        assert!(builder.is_symbol_filtering_enabled);
+        assert!(builder.build().is_symbol_filtering_enabled);
+    }
+
+    #[test]
+    fn assert_detector_can_be_built_with_unicode_normalization() {
+        let mut builder = LanguageDetectorBuilder::from_all_languages();
+        assert_eq!(builder.unicode_normalization, None);
+
+        builder.with_unicode_normalization(NormalizationForm::Nfkc);
+        assert_eq!(builder.unicode_normalization, Some(NormalizationForm::Nfkc));
+        assert_eq!(
+            builder.build().unicode_normalization,
+            Some(NormalizationForm::Nfkc)
+        );
+    }
+
+    #[test]
+    fn assert_detector_can_be_built_with_ignored_tokens() {
+        let mut builder = LanguageDetectorBuilder::from_all_languages();
+        assert!(builder.ignored_tokens.is_empty());
+        assert!(builder.build().ignored_tokens_regex.is_none());
+
+        builder
+            .with_ignored_tokens(&["iPhone", "SKU-1234"])
+            .with_ignored_tokens(&["iPhone"]);
+        assert_eq!(builder.ignored_tokens.len(), 2);
+        assert!(builder.build().ignored_tokens_regex.is_some());
+    }
+
+    #[test]
+    fn assert_detector_can_be_built_with_limits() {
+        let mut builder = LanguageDetectorBuilder::from_all_languages();
+        assert_eq!(builder.limits, DetectionLimits::default());
+
+        builder
+            .with_maximum_input_bytes(1000)
+            .with_maximum_ngram_count(500)
+            .with_maximum_processing_time(Duration::from_millis(20));
+
+        let expected_limits = DetectionLimits {
+            maximum_input_bytes: Some(1000),
+            maximum_ngram_count: Some(500),
+            maximum_processing_time: Some(Duration::from_millis(20)),
+        };
+        assert_eq!(builder.limits, expected_limits);
+        assert_eq!(builder.build().limits, expected_limits);
+    }
+
+    #[test]
+    #[should_panic(expected = "Maximum number of input bytes must be greater than zero")]
+    fn assert_detector_cannot_be_built_with_zero_maximum_input_bytes() {
+        LanguageDetectorBuilder::from_all_languages()
+            .with_maximum_input_bytes(0)
+            .build();
+    }
+
+    #[test]
+    fn assert_detector_can_be_built_with_language_usage_tracking() {
+        let mut builder = LanguageDetectorBuilder::from_all_languages();
+        assert!(!builder.is_language_usage_tracking_enabled);
+        assert!(builder.build().usage_tracker.is_none());
+
+        builder.with_language_usage_tracking();
+        assert!(builder.is_language_usage_tracking_enabled);
+        assert!(builder.build().usage_tracker.is_some());
+    }
+
+    #[test]
+    fn assert_detector_can_be_built_with_language_eviction() {
+        let mut builder = LanguageDetectorBuilder::from_all_languages();
+        assert_eq!(builder.maximum_idle_time, None);
+
+        builder.with_language_eviction(Duration::from_secs(3600));
+        assert_eq!(builder.maximum_idle_time, Some(Duration::from_secs(3600)));
+        assert!(builder.build().usage_tracker.is_some());
+    }
+
+    #[test]
+    fn assert_detector_can_be_built_with_maximum_resident_languages() {
+        let mut builder = LanguageDetectorBuilder::from_all_languages();
+        assert_eq!(builder.maximum_resident_languages, None);
+
+        builder.with_maximum_resident_languages(10);
+        assert_eq!(builder.maximum_resident_languages, Some(10));
+        assert!(builder.build().usage_tracker.is_some());
+    }
+
+    #[test]
+    #[should_panic(expected = "Maximum number of resident languages must be greater than zero")]
+    fn assert_detector_cannot_be_built_without_resident_languages() {
+        LanguageDetectorBuilder::from_all_languages()
+            .with_maximum_resident_languages(0)
+            .build();
+    }
+
+    #[test]
+    fn assert_detector_can_be_built_with_near_duplicate_threshold() {
+        let mut builder = LanguageDetectorBuilder::from_all_languages();
+        assert_eq!(builder.near_duplicate_threshold, None);
+
+        builder.with_near_duplicate_threshold(0.9);
+        assert_eq!(builder.near_duplicate_threshold, Some(0.9));
+        assert_eq!(builder.build().near_duplicate_threshold, Some(0.9));
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Near-duplicate threshold must lie in between 0.0 and 1.0, excluding 0.0"
+    )]
+    fn assert_detector_cannot_be_built_with_zero_near_duplicate_threshold() {
+        LanguageDetectorBuilder::from_all_languages()
+            .with_near_duplicate_threshold(0.0)
+            .build();
+    }
+
+    #[test]
+    fn assert_detector_can_be_built_with_cleanup_policy() {
+        let mut builder = LanguageDetectorBuilder::from_all_languages();
+        assert!(builder.cleanup_policy.is_empty());
+
+        builder.with_cleanup_policy(CleanupPolicy::recommended());
+        assert_eq!(builder.cleanup_policy, CleanupPolicy::recommended());
+        assert_eq!(builder.build().word_regexes.len(), 3);
     }
 }
@@ -0,0 +1,119 @@
+/*
+ * Copyright © 2020-present Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+/// The minimum number of cased letters a text must contain to be considered all-caps.
+const MINIMUM_CASED_LETTER_COUNT: usize = 4;
+
+/// The minimum ratio of uppercase letters among the cased letters of an all-caps text,
+/// so that mixed-in units or abbreviations such as `km` or `iPhone` are tolerated.
+const MINIMUM_UPPERCASE_RATIO: f64 = 0.9;
+
+/// The maximum number of `ss` sequences per word for which all spellings
+/// with `ß` are tried, limiting the number of spelling variants to 2^3.
+const MAXIMUM_ESZETT_CANDIDATE_COUNT: usize = 3;
+
+/// Returns `true` if the cased letters of the given text are predominantly uppercase,
+/// as is the case for headlines, signs or shouted social media posts.
+pub(crate) fn is_all_caps(text: &str) -> bool {
+    let mut uppercase_count = 0;
+    let mut cased_count = 0;
+
+    for ch in text.chars() {
+        if ch.is_uppercase() {
+            uppercase_count += 1;
+            cased_count += 1;
+        } else if ch.is_lowercase() {
+            cased_count += 1;
+        }
+    }
+
+    cased_count >= MINIMUM_CASED_LETTER_COUNT
+        && uppercase_count as f64 / cased_count as f64 >= MINIMUM_UPPERCASE_RATIO
+}
+
+/// Converts the given all-caps text to lowercase, avoiding artifacts of the default
+/// case mapping. The Turkish and Azerbaijani `İ` is mapped to `i` instead of `i`
+/// followed by a combining dot above, which would otherwise split words in two.
+pub(crate) fn decapitalize(text: &str) -> String {
+    text.replace('İ', "I").to_lowercase()
+}
+
+/// Returns the spellings of the given lowercase word that arise from replacing
+/// any of its `ss` sequences with `ß`, excluding the word itself.
+///
+/// Uppercase German text writes `ß` as `SS`, so `STRASSE` may stand for `straße`.
+pub(crate) fn compute_eszett_variants(word: &str) -> Vec<String> {
+    let positions = word.match_indices("ss").map(|(i, _)| i).collect::<Vec<_>>();
+
+    if positions.is_empty() || positions.len() > MAXIMUM_ESZETT_CANDIDATE_COUNT {
+        return vec![];
+    }
+
+    (1..1usize << positions.len())
+        .map(|mask| {
+            let mut variant = String::with_capacity(word.len());
+            let mut start = 0;
+            for (bit, position) in positions.iter().enumerate() {
+                if mask & (1 << bit) != 0 {
+                    variant.push_str(&word[start..*position]);
+                    variant.push('ß');
+                    start = position + 2;
+                }
+            }
+            variant.push_str(&word[start..]);
+            variant
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[rstest(
+        text,
+        expected,
+        case("BREAKING NEWS: PARLAMENT STIMMT ZU", true),
+        case("NEUES IPHONE FÜR 1000 EURO, 5 km ENTFERNT", true),
+        case("Breaking News: Parlament stimmt zu", false),
+        case("NATO", true),
+        case("UN", false),
+        case("123 !?", false)
+    )]
+    fn assert_all_caps_text_is_recognized(text: &str, expected: bool) {
+        assert_eq!(is_all_caps(text), expected);
+    }
+
+    #[test]
+    fn assert_text_is_decapitalized() {
+        assert_eq!(decapitalize("İSTANBUL'DA İKİ"), "istanbul'da iki");
+        assert_eq!(decapitalize("ΟΔΟΣ"), "οδος");
+        assert_eq!(decapitalize("GROẞE STRASSE"), "große strasse");
+    }
+
+    #[rstest(
+        word,
+        expected_variants,
+        case("haus", vec![]),
+        case("strasse", vec!["straße"]),
+        case("schlossstrasse", vec!["schloßstrasse", "schlossstraße", "schloßstraße"]),
+        case("sss", vec!["ßs"])
+    )]
+    fn assert_eszett_variants_are_computed(word: &str, expected_variants: Vec<&str>) {
+        assert_eq!(compute_eszett_variants(word), expected_variants);
+    }
+}
@@ -0,0 +1,176 @@
+/*
+ * Copyright © 2020-present Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::detector::LanguageDetector;
+use crate::language::Language;
+
+pub(crate) const MINIMUM_CONFIDENCE_MESSAGE: &str =
+    "Minimum confidence must lie in between 0.0 and 1.0";
+
+/// A detector which first runs a cheap [LanguageDetector] and only escalates to an
+/// expensive one if the cheap detector is not confident enough.
+///
+/// A typical cheap detector is built from the few languages which make up most of the
+/// traffic, with the low accuracy mode enabled so that only the rules and trigrams are
+/// evaluated. The expensive detector evaluates all ngram lengths for all languages of
+/// interest. Most requests are then answered with low latency, while hard cases such as
+/// short texts or rare languages still get the accuracy of the expensive detector.
+///
+/// The cheap detector's answer is accepted if it detects a language, taking its minimum
+/// relative distance into account, with a confidence value of at least the configured
+/// minimum confidence. Texts whose language is detected unambiguously by the rule engine
+/// therefore never escalate.
+///
+/// ```
+/// use lingua::{CascadeDetector, LanguageDetectorBuilder};
+/// use lingua::Language::{English, French, German, Spanish};
+///
+/// let cheap_detector = LanguageDetectorBuilder::from_languages(&[English, German])
+///     .with_low_accuracy_mode()
+///     .build();
+/// let full_detector = LanguageDetectorBuilder::from_languages(&[English, French, German, Spanish])
+///     .build();
+/// let cascade_detector = CascadeDetector::new(&cheap_detector, &full_detector, 0.9);
+///
+/// assert_eq!(cascade_detector.detect_language_of("Sprachen sind großartig"), Some(German));
+/// assert_eq!(cascade_detector.detect_language_of("languages are awesome"), Some(English));
+/// ```
+pub struct CascadeDetector<'a> {
+    cheap_detector: &'a LanguageDetector,
+    full_detector: &'a LanguageDetector,
+    minimum_confidence: f64,
+}
+
+impl<'a> CascadeDetector<'a> {
+    /// Creates a new cascade which escalates from `cheap_detector` to `full_detector`
+    /// if the confidence value of the language detected by `cheap_detector` is
+    /// smaller than `minimum_confidence`.
+    ///
+    /// ⚠ Panics if `minimum_confidence` is smaller than 0.0 or greater than 1.0.
+    pub fn new(
+        cheap_detector: &'a LanguageDetector,
+        full_detector: &'a LanguageDetector,
+        minimum_confidence: f64,
+    ) -> Self {
+        if !(0.0..=1.0).contains(&minimum_confidence) {
+            panic!("{}", MINIMUM_CONFIDENCE_MESSAGE);
+        }
+        Self {
+            cheap_detector,
+            full_detector,
+            minimum_confidence,
+        }
+    }
+
+    /// Detects the language of given input text like [LanguageDetector::detect_language_of]
+    /// does. If the language cannot be reliably detected by either detector, [None] is returned.
+    pub fn detect_language_of<T: Into<String>>(&self, text: T) -> Option<Language> {
+        let text = text.into();
+
+        match self.run_cheap_detector(&text) {
+            Some(confidence_values) => self
+                .cheap_detector
+                .select_most_likely_language(&confidence_values),
+            None => self.full_detector.detect_language_of(text),
+        }
+    }
+
+    /// Computes confidence values like [LanguageDetector::compute_language_confidence_values]
+    /// does. The values are those of the detector whose answer is accepted, so they only
+    /// contain the languages of the cheap detector if no escalation has been necessary.
+    pub fn compute_language_confidence_values<T: Into<String>>(
+        &self,
+        text: T,
+    ) -> Vec<(Language, f64)> {
+        let text = text.into();
+
+        match self.run_cheap_detector(&text) {
+            Some(confidence_values) => confidence_values,
+            None => self.full_detector.compute_language_confidence_values(text),
+        }
+    }
+
+    /// Returns `true` if the given text would be escalated to the full detector.
+    pub fn is_escalated<T: Into<String>>(&self, text: T) -> bool {
+        self.run_cheap_detector(&text.into()).is_none()
+    }
+
+    /// Returns the confidence values of the cheap detector if its answer is accepted.
+    fn run_cheap_detector(&self, text: &str) -> Option<Vec<(Language, f64)>> {
+        let confidence_values = self.cheap_detector.compute_language_confidence_values(text);
+        let most_likely_language = self
+            .cheap_detector
+            .select_most_likely_language(&confidence_values)?;
+
+        confidence_values
+            .iter()
+            .any(|(language, confidence)| {
+                *language == most_likely_language && *confidence >= self.minimum_confidence
+            })
+            .then_some(confidence_values)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::language::Language::{English, French, German, Spanish};
+
+    #[test]
+    fn assert_confident_texts_are_not_escalated() {
+        let cheap_detector = LanguageDetector::from(hashset!(English, German), 0.0, false, true);
+        let full_detector = LanguageDetector::from(
+            hashset!(English, French, German, Spanish),
+            0.0,
+            false,
+            false,
+        );
+        let cascade_detector = CascadeDetector::new(&cheap_detector, &full_detector, 0.9);
+
+        // Detected by the rule engine
+        assert!(!cascade_detector.is_escalated("Fußball"));
+        assert_eq!(
+            cascade_detector.compute_language_confidence_values("Fußball"),
+            vec![(German, 1.0), (English, 0.0)]
+        );
+
+        // Not confidently detected by the cheap detector
+        let text = "bonjour";
+        assert!(cascade_detector.is_escalated(text));
+        assert_eq!(cascade_detector.detect_language_of(text), Some(French));
+        assert_eq!(
+            cascade_detector.compute_language_confidence_values(text),
+            full_detector.compute_language_confidence_values(text)
+        );
+    }
+
+    #[test]
+    fn assert_every_text_is_escalated_with_maximum_confidence() {
+        let cheap_detector = LanguageDetector::from(hashset!(English, German), 0.0, false, true);
+        let full_detector = LanguageDetector::from(hashset!(English, German), 0.0, false, false);
+        let cascade_detector = CascadeDetector::new(&cheap_detector, &full_detector, 1.0);
+
+        assert!(cascade_detector.is_escalated("languages are awesome"));
+        assert!(!cascade_detector.is_escalated("Fußball"));
+    }
+
+    #[test]
+    #[should_panic(expected = "Minimum confidence must lie in between 0.0 and 1.0")]
+    fn assert_cascade_detector_cannot_be_created_with_invalid_minimum_confidence() {
+        let detector = LanguageDetector::from(hashset!(English, German), 0.0, false, false);
+        CascadeDetector::new(&detector, &detector, 1.5);
+    }
+}
@@ -0,0 +1,535 @@
+/*
+ * Copyright © 2020-present Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Mutex;
+
+use itertools::Itertools;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use strum::IntoEnumIterator;
+
+use crate::alphabet::Alphabet;
+
+/// The word regexes compiled so far, keyed by pattern. Detectors built with equal cleanup
+/// policies share the compiled regexes instead of compiling them once per instance.
+static COMPILED_WORD_REGEXES: Lazy<Mutex<HashMap<String, Regex>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+pub(crate) const ZERO_WIDTH_NON_JOINER: char = '\u{200C}';
+const ZERO_WIDTH_SPACE: char = '\u{200B}';
+
+/// Matches sequences of symbols and emoji: all codepoints of the Unicode category Symbol,
+/// pictographs, skin tone modifiers, variation selectors, the combining enclosing keycap
+/// and the tag characters of flag sequences.
+static SYMBOLS: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"[\p{S}\p{Extended_Pictographic}\p{Emoji_Modifier}\u{20E3}\u{FE0E}\u{FE0F}\u{E0020}-\u{E007F}]+")
+        .unwrap()
+});
+
+/// The invisible characters which are removed from words: soft hyphen, zero-width non-joiner,
+/// zero-width joiner, word joiner and zero-width no-break space, also known as byte order mark.
+const INVISIBLE_CHARACTERS: [char; 5] = [
+    '\u{00AD}',
+    ZERO_WIDTH_NON_JOINER,
+    '\u{200D}',
+    '\u{2060}',
+    '\u{FEFF}',
+];
+
+/// The bidirectional formatting characters of Unicode Standard Annex #9: the Arabic letter mark,
+/// the left-to-right and right-to-left marks, embeddings, overrides and isolates.
+const BIDI_CONTROL_CHARACTERS: [char; 12] = [
+    '\u{061C}', '\u{200E}', '\u{200F}', '\u{202A}', '\u{202B}', '\u{202C}', '\u{202D}', '\u{202E}',
+    '\u{2066}', '\u{2067}', '\u{2068}', '\u{2069}',
+];
+
+/// This struct configures how input text is split into words before its language
+/// is detected, depending on the alphabet that most letters of the text belong to.
+///
+/// By default, words consist of letters only, so every other character such as
+/// punctuation separates words. For some writing systems, this is too strict:
+/// Hebrew uses geresh and gershayim within abbreviations, Persian uses the zero-width
+/// non-joiner within words and Uzbek uses apostrophes as part of its Latin alphabet.
+/// A cleanup policy lists the characters which are kept within words
+/// if the text is dominated by a particular alphabet.
+///
+/// Texts such as product titles or chat messages may carry words mixing letters with
+/// digits or punctuation, e.g. model numbers or hashtags. A cleanup policy can keep
+/// the characters of a regex character class within words of all alphabets, such as
+/// digits with [CleanupPolicy::keep_digits] or punctuation with [CleanupPolicy::keep_punctuation].
+/// Words which do not contain any letter are removed nevertheless,
+/// as they do not carry any evidence of a language.
+///
+/// Independently of the policy, bidirectional formatting characters such as the
+/// right-to-left mark (U+200F) are always removed, so that they neither split words
+/// of mixed-direction texts into fragments nor merge words of different alphabets.
+/// The same applies to invisible characters such as soft hyphens, zero-width joiners and
+/// byte order marks. The zero-width non-joiner (U+200C) is removed as well, unless
+/// the policy keeps it within words.
+///
+/// ```
+/// use lingua::{Alphabet, CleanupPolicy};
+///
+/// let mut policy = CleanupPolicy::new();
+/// policy.keep_characters(Alphabet::Hebrew, &['\u{05F3}', '\u{05F4}']);
+///
+/// assert_eq!(policy.kept_characters(Alphabet::Hebrew), vec!['\u{05F3}', '\u{05F4}']);
+/// assert!(policy.kept_characters(Alphabet::Latin).is_empty());
+///
+/// policy.keep_digits().keep_character_class("#@");
+///
+/// assert_eq!(policy.kept_character_classes(), vec!["#@", "\\p{Nd}"]);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CleanupPolicy {
+    kept_characters: HashMap<Alphabet, BTreeSet<char>>,
+    kept_character_classes: BTreeSet<String>,
+}
+
+impl CleanupPolicy {
+    /// Creates a cleanup policy which does not keep any characters other than letters.
+    /// This is the behavior of a [LanguageDetector](crate::LanguageDetector)
+    /// if no cleanup policy is configured.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a cleanup policy which keeps the following characters within words:
+    ///
+    /// - geresh (U+05F3) and gershayim (U+05F4) for texts in Hebrew script
+    /// - zero-width non-joiner (U+200C) for texts in Arabic script, as used in Persian
+    /// - modifier letter turned comma (U+02BB), right single quotation mark (U+2019)
+    ///   and apostrophe (U+0027) for texts in Latin script, as used in Uzbek
+    ///
+    /// Note that keeping apostrophes affects all languages written in Latin script,
+    /// e.g. English contractions such as `don't` are then treated as a single word.
+    pub fn recommended() -> Self {
+        let mut policy = Self::new();
+        policy
+            .keep_characters(Alphabet::Hebrew, &['\u{05F3}', '\u{05F4}'])
+            .keep_characters(Alphabet::Arabic, &['\u{200C}'])
+            .keep_characters(Alphabet::Latin, &['\u{02BB}', '\u{2019}', '\'']);
+        policy
+    }
+
+    /// Adds the given characters to the ones kept within words
+    /// of texts dominated by the given alphabet.
+    pub fn keep_characters(&mut self, alphabet: Alphabet, characters: &[char]) -> &mut Self {
+        self.kept_characters
+            .entry(alphabet)
+            .or_default()
+            .extend(characters.iter().copied());
+        self
+    }
+
+    /// Adds the characters of the given regex character class, such as `\\p{Sc}` for currency
+    /// symbols, to the ones kept within words of texts dominated by any alphabet.
+    ///
+    /// # Panics
+    ///
+    /// If `char_class` cannot be compiled to a valid regular expression.
+    pub fn keep_character_class(&mut self, char_class: &str) -> &mut Self {
+        if Regex::new(&format!("[{char_class}]")).is_err() {
+            panic!(
+                "The character class '{char_class}' cannot be compiled to a valid regular expression"
+            );
+        }
+        self.kept_character_classes.insert(char_class.to_string());
+        self
+    }
+
+    /// Keeps decimal digits within words of texts dominated by any alphabet,
+    /// e.g. in product codes such as `iphone15`.
+    pub fn keep_digits(&mut self) -> &mut Self {
+        self.keep_character_class("\\p{Nd}")
+    }
+
+    /// Keeps punctuation within words of texts dominated by any alphabet,
+    /// e.g. hyphens, apostrophes and hashtags in chat messages.
+    pub fn keep_punctuation(&mut self) -> &mut Self {
+        self.keep_character_class("\\p{P}")
+    }
+
+    /// Returns the regex character classes kept within words of texts dominated by
+    /// any alphabet, sorted lexicographically.
+    pub fn kept_character_classes(&self) -> Vec<String> {
+        self.kept_character_classes.iter().cloned().collect()
+    }
+
+    /// Returns the characters kept within words of texts dominated by the given alphabet,
+    /// sorted by their code points.
+    pub fn kept_characters(&self, alphabet: Alphabet) -> Vec<char> {
+        self.kept_characters
+            .get(&alphabet)
+            .map(|characters| characters.iter().copied().collect())
+            .unwrap_or_default()
+    }
+
+    /// Returns `true` if no characters other than letters are kept for any alphabet.
+    pub fn is_empty(&self) -> bool {
+        self.kept_character_classes.is_empty()
+            && self.kept_characters.values().all(|it| it.is_empty())
+    }
+
+    /// Compiles a regular expression matching single words for each alphabet of this policy.
+    /// If character classes are kept, a regular expression is compiled for every alphabet.
+    pub(crate) fn compile_word_regexes(&self) -> HashMap<Alphabet, Regex> {
+        let kept_character_classes = self.kept_character_classes.iter().join("");
+
+        Alphabet::iter()
+            .filter(|alphabet| {
+                !kept_character_classes.is_empty()
+                    || self
+                        .kept_characters
+                        .get(alphabet)
+                        .is_some_and(|characters| !characters.is_empty())
+            })
+            .map(|alphabet| {
+                let escaped_characters = self
+                    .kept_characters(alphabet)
+                    .iter()
+                    .map(|ch| regex::escape(&ch.to_string()))
+                    .collect::<String>();
+                let pattern = format!(
+                    "\\p{{Han}}|\\p{{Hangul}}|\\p{{Hiragana}}|\\p{{Katakana}}|[\\p{{L}}{escaped_characters}{kept_character_classes}]+"
+                );
+                let regex = COMPILED_WORD_REGEXES
+                    .lock()
+                    .unwrap()
+                    .entry(pattern)
+                    .or_insert_with_key(|pattern| Regex::new(pattern).unwrap())
+                    .clone();
+                (alphabet, regex)
+            })
+            .collect()
+    }
+}
+
+/// Returns the alphabet that most letters of the given text belong to.
+/// Ties are broken by the order of the [Alphabet] enum.
+pub(crate) fn find_dominant_alphabet(text: &str) -> Option<Alphabet> {
+    let mut alphabet_counts = HashMap::new();
+
+    for ch in text.chars().filter(|ch| ch.is_alphabetic()) {
+        if let Some(alphabet) = Alphabet::iter().find(|alphabet| alphabet.matches_char(ch)) {
+            *alphabet_counts.entry(alphabet).or_insert(0u32) += 1;
+        }
+    }
+
+    alphabet_counts
+        .into_iter()
+        .max_by(
+            |(first_alphabet, first_count), (second_alphabet, second_count)| {
+                first_count
+                    .cmp(second_count)
+                    .then_with(|| second_alphabet.cmp(first_alphabet))
+            },
+        )
+        .map(|(alphabet, _)| alphabet)
+}
+
+/// Removes the bidirectional formatting characters from the given text, which are
+/// invisible but commonly inserted into mixed-direction text such as Hebrew or Arabic with
+/// embedded Latin words or numbers.
+///
+/// A formatting character between two letters of the same alphabet is removed, so that
+/// the word it is placed in is not split into garbled fragments. In any other position,
+/// it is replaced with a space, so that words of different alphabets are not merged.
+pub(crate) fn remove_bidi_control_characters(text: &str) -> String {
+    if !text.contains(BIDI_CONTROL_CHARACTERS) {
+        return text.to_string();
+    }
+
+    let chars = text.chars().collect::<Vec<_>>();
+    let is_control_character = |ch: &char| BIDI_CONTROL_CHARACTERS.contains(ch);
+    let alphabet_of = |ch: Option<&char>| {
+        ch.filter(|ch| ch.is_alphabetic())
+            .and_then(|ch| Alphabet::iter().find(|alphabet| alphabet.matches_char(*ch)))
+    };
+    let mut result = String::with_capacity(text.len());
+
+    for (i, ch) in chars.iter().enumerate() {
+        if !is_control_character(ch) {
+            result.push(*ch);
+            continue;
+        }
+
+        let previous_alphabet =
+            alphabet_of(chars[..i].iter().rev().find(|it| !is_control_character(it)));
+        let next_alphabet = alphabet_of(chars[i + 1..].iter().find(|it| !is_control_character(it)));
+
+        if previous_alphabet.is_none() || previous_alphabet != next_alphabet {
+            result.push(' ');
+        }
+    }
+
+    result
+}
+
+/// Compiles a case-insensitive regular expression matching the given tokens as a whole,
+/// i.e. not as part of longer words, or returns [None] if no tokens are given.
+/// Longer tokens are preferred, so that `coca-cola` is matched before `coca`.
+/// Whitespace within tokens matches any whitespace.
+pub(crate) fn compile_ignored_tokens_regex(tokens: &BTreeSet<String>) -> Option<Regex> {
+    let is_word_char = |ch: Option<char>| ch.is_some_and(|ch| ch.is_alphanumeric() || ch == '_');
+    let alternatives = tokens
+        .iter()
+        .map(|token| token.trim())
+        .filter(|token| !token.is_empty())
+        .sorted_by_key(|token| std::cmp::Reverse(token.chars().count()))
+        .map(|token| {
+            let escaped_token = token.split_whitespace().map(regex::escape).join("\\s+");
+            let start = if is_word_char(token.chars().next()) {
+                "\\b"
+            } else {
+                ""
+            };
+            let end = if is_word_char(token.chars().last()) {
+                "\\b"
+            } else {
+                ""
+            };
+            format!("{start}{escaped_token}{end}")
+        })
+        .join("|");
+
+    if alternatives.is_empty() {
+        return None;
+    }
+
+    Some(Regex::new(&format!("(?i){alternatives}")).unwrap())
+}
+
+/// Splits words which mix letters of several alphabets, such as `iphoneпривет`, into one word
+/// per run of letters of the same alphabet, so that the rule-based filter engine sees a single
+/// alphabet per word. Characters which do not belong to any alphabet, such as kept punctuation,
+/// stay with the preceding letters. Han, Hiragana and Katakana are not separated from each other,
+/// as they are mixed within Japanese words.
+pub(crate) fn split_mixed_alphabet_words(words: Vec<String>) -> Vec<String> {
+    let is_mixed = |word: &str| {
+        word.chars()
+            .filter_map(script_group_of)
+            .tuple_windows()
+            .any(|(first, second)| first != second)
+    };
+
+    if !words.iter().any(|word| is_mixed(word)) {
+        return words;
+    }
+
+    let mut split_words = Vec::with_capacity(words.len());
+
+    for word in words {
+        if !is_mixed(&word) {
+            split_words.push(word);
+            continue;
+        }
+
+        let mut current_word = String::new();
+        let mut current_group = None;
+
+        for ch in word.chars() {
+            let group = script_group_of(ch);
+            if group.is_some() && current_group.is_some() && group != current_group {
+                split_words.push(std::mem::take(&mut current_word));
+            }
+            if group.is_some() {
+                current_group = group;
+            }
+            current_word.push(ch);
+        }
+
+        split_words.push(current_word);
+    }
+
+    split_words
+}
+
+/// Returns the alphabet of the given character, treating the Japanese scripts as one.
+fn script_group_of(ch: char) -> Option<Alphabet> {
+    match Alphabet::of_char(ch)? {
+        Alphabet::Hiragana | Alphabet::Katakana => Some(Alphabet::Han),
+        alphabet => Some(alphabet),
+    }
+}
+
+/// Replaces emoji, pictographs and other symbols in the given text with spaces,
+/// so that they neither count towards the alphabets of the text nor merge
+/// the words around them.
+pub(crate) fn remove_symbols(text: &str) -> String {
+    SYMBOLS.replace_all(text, " ").into_owned()
+}
+
+/// Removes invisible characters which are common in text copied from the web, but split words
+/// into fragments: soft hyphens, zero-width non-joiners and joiners, word joiners and byte
+/// order marks. Zero-width spaces are replaced with spaces because they separate words,
+/// e.g. in Thai. The zero-width non-joiner is kept if `is_zero_width_non_joiner_kept` is `true`,
+/// as it is part of the orthography of Persian.
+pub(crate) fn remove_invisible_characters(
+    text: &str,
+    is_zero_width_non_joiner_kept: bool,
+) -> String {
+    text.chars()
+        .filter_map(|ch| match ch {
+            ZERO_WIDTH_SPACE => Some(' '),
+            ZERO_WIDTH_NON_JOINER if is_zero_width_non_joiner_kept => Some(ch),
+            _ if INVISIBLE_CHARACTERS.contains(&ch) => None,
+            _ => Some(ch),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn assert_recommended_policy_keeps_script_specific_characters() {
+        let policy = CleanupPolicy::recommended();
+
+        assert_eq!(
+            policy.kept_characters(Alphabet::Hebrew),
+            vec!['\u{05F3}', '\u{05F4}']
+        );
+        assert_eq!(policy.kept_characters(Alphabet::Arabic), vec!['\u{200C}']);
+        assert_eq!(
+            policy.kept_characters(Alphabet::Latin),
+            vec!['\'', '\u{02BB}', '\u{2019}']
+        );
+        assert!(policy.kept_characters(Alphabet::Cyrillic).is_empty());
+        assert!(!policy.is_empty());
+        assert!(CleanupPolicy::new().is_empty());
+    }
+
+    #[test]
+    fn assert_character_classes_are_kept_for_all_alphabets() {
+        let mut policy = CleanupPolicy::new();
+        policy
+            .keep_characters(Alphabet::Latin, &['\''])
+            .keep_digits()
+            .keep_punctuation();
+
+        assert_eq!(policy.kept_character_classes(), vec!["\\p{Nd}", "\\p{P}"]);
+        assert!(!policy.is_empty());
+
+        let word_regexes = policy.compile_word_regexes();
+        let words = |alphabet, text| {
+            word_regexes[&alphabet]
+                .find_iter(text)
+                .map(|mat| mat.as_str())
+                .collect_vec()
+        };
+
+        assert_eq!(word_regexes.len(), Alphabet::iter().count());
+        assert_eq!(
+            words(Alphabet::Latin, "iphone15 pro-max"),
+            vec!["iphone15", "pro-max"]
+        );
+        assert_eq!(words(Alphabet::Cyrillic, "ч.1 €5"), vec!["ч.1", "5"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "The character class '\\p{Foo}' cannot be compiled")]
+    fn assert_invalid_character_class_is_rejected() {
+        CleanupPolicy::new().keep_character_class("\\p{Foo}");
+    }
+
+    #[test]
+    fn assert_dominant_alphabet_is_found_correctly() {
+        assert_eq!(find_dominant_alphabet("Hello мир"), Some(Alphabet::Latin));
+        assert_eq!(find_dominant_alphabet("Hi мир"), Some(Alphabet::Cyrillic));
+        assert_eq!(find_dominant_alphabet("ab вг"), Some(Alphabet::Cyrillic));
+        assert_eq!(find_dominant_alphabet("123 !?"), None);
+    }
+
+    #[test]
+    fn assert_ignored_tokens_are_matched_as_a_whole() {
+        let tokens = ["Coca", "coca-cola", "C++", "Nike Air", " "]
+            .iter()
+            .map(|it| it.to_string())
+            .collect();
+        let regex = compile_ignored_tokens_regex(&tokens).unwrap();
+        let removed = |text| regex.replace_all(text, " ").split_whitespace().join(" ");
+
+        assert_eq!(removed("Я пью COCA-COLA и coca"), "Я пью и");
+        assert_eq!(
+            removed("Кокаколa cocaine C++ и c++17"),
+            "Кокаколa cocaine и 17"
+        );
+        assert_eq!(removed("Кроссовки NIKE  AIR Max"), "Кроссовки Max");
+        assert!(compile_ignored_tokens_regex(&BTreeSet::new()).is_none());
+    }
+
+    #[test]
+    fn assert_mixed_alphabet_words_are_split() {
+        let words = |words: &[&str]| words.iter().map(|it| it.to_string()).collect_vec();
+
+        assert_eq!(
+            split_mixed_alphabet_words(words(&["iphoneпривет", "и", "ok"])),
+            words(&["iphone", "привет", "и", "ok"])
+        );
+        assert_eq!(
+            split_mixed_alphabet_words(words(&["ελλάδαgreece", "o'zbekистон", "食べる"])),
+            words(&["ελλάδα", "greece", "o'zbek", "истон", "食べる"])
+        );
+        assert_eq!(
+            split_mixed_alphabet_words(words(&["'abc", "abc'"])),
+            words(&["'abc", "abc'"])
+        );
+    }
+
+    #[test]
+    fn assert_symbols_are_removed() {
+        assert_eq!(remove_symbols("Guten Morgen 😀☀️"), "Guten Morgen  ");
+        assert_eq!(remove_symbols("👍🏽ok👨‍👩‍👧"), " ok \u{200D} \u{200D} ");
+        assert_eq!(remove_symbols("🇩🇪 #1 €5"), "  #1  5");
+        assert_eq!(remove_symbols("Привет, мир!"), "Привет, мир!");
+    }
+
+    #[test]
+    fn assert_bidi_control_characters_are_removed() {
+        assert_eq!(
+            remove_bidi_control_characters("של\u{200F}ום \u{200E}Google\u{200F} שלום"),
+            "שלום  Google  שלום"
+        );
+        assert_eq!(
+            remove_bidi_control_characters("\u{2067}مرحبا\u{2069}\u{2066}world\u{2069}"),
+            " مرحبا  world "
+        );
+        assert_eq!(
+            remove_bidi_control_characters("سنة\u{061C}2023"),
+            "سنة 2023"
+        );
+        assert_eq!(remove_bidi_control_characters("plain text"), "plain text");
+    }
+
+    #[test]
+    fn assert_invisible_characters_are_removed() {
+        assert_eq!(
+            remove_invisible_characters("\u{FEFF}Fuß\u{00AD}ball\u{200B}spielen\u{2060}", false),
+            "Fußball spielen"
+        );
+        assert_eq!(
+            remove_invisible_characters("می\u{200C}خواهم\u{200D}", false),
+            "میخواهم"
+        );
+        assert_eq!(
+            remove_invisible_characters("می\u{200C}خواهم\u{200D}", true),
+            "می\u{200C}خواهم"
+        );
+    }
+}
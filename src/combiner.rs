@@ -0,0 +1,250 @@
+/*
+ * Copyright © 2020-present Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::{HashMap, HashSet};
+
+use fraction::Zero;
+use itertools::Itertools;
+
+use crate::language::{Language, LanguageCounts};
+
+/// The summed log probabilities of the ngrams of an input text, per language and ngram length,
+/// which a [ScoreCombiner] combines into confidence values.
+pub struct NgramScores<'a> {
+    pub(crate) languages: &'a HashSet<Language>,
+    pub(crate) ngram_lengths: &'a [usize],
+    pub(crate) log_probabilities: Vec<&'a LanguageCounts<f64>>,
+    pub(crate) unigram_counts: Option<&'a LanguageCounts>,
+}
+
+impl NgramScores<'_> {
+    /// Returns the languages which have not been ruled out by the rule-based filter engine.
+    pub fn languages(&self) -> &HashSet<Language> {
+        self.languages
+    }
+
+    /// Returns the evaluated ngram lengths in ascending order. These depend on the length
+    /// of the input text and on whether the low accuracy mode is enabled.
+    pub fn ngram_lengths(&self) -> &[usize] {
+        self.ngram_lengths
+    }
+
+    /// Returns the summed log probabilities of the ngrams of the given length for the given
+    /// language, or [None] if this ngram length has not been evaluated or if none of the
+    /// ngrams is known to the language's models.
+    pub fn log_probability(&self, language: Language, ngram_length: usize) -> Option<f64> {
+        self.ngram_lengths
+            .iter()
+            .position(|it| *it == ngram_length)
+            .and_then(|i| self.log_probabilities[i].find(language))
+    }
+
+    /// Returns the number of unigrams of the input text which are known to the unigram
+    /// model of the given language, or [None] if unigrams have not been evaluated.
+    pub fn unigram_count(&self, language: Language) -> Option<u32> {
+        self.unigram_counts.and_then(|counts| counts.find(language))
+    }
+}
+
+/// A strategy which combines the ngram log probabilities computed by a
+/// [LanguageDetector](crate::LanguageDetector) into the final confidence values.
+///
+/// The default strategy is [DefaultScoreCombiner]. Alternative schemes such as
+/// length-normalized perplexity or rank fusion can be plugged in with
+/// [LanguageDetectorBuilder::with_score_combiner](crate::LanguageDetectorBuilder::with_score_combiner)
+/// without changing the rest of the detection pipeline. Every closure taking a
+/// reference to [NgramScores] and returning confidence values implements this trait.
+///
+/// The returned map contains a confidence value between 0.0 and 1.0 for each language
+/// which could be scored. Languages which are missing in the map receive a value of 0.0.
+/// If the map is empty, the language of the text is considered undetermined.
+///
+/// ```
+/// use std::collections::HashMap;
+/// use lingua::{Language, NgramScores, ScoreCombiner};
+///
+/// /// Ranks the languages by their trigram log probabilities only.
+/// struct TrigramRanking;
+///
+/// impl ScoreCombiner for TrigramRanking {
+///     fn combine(&self, scores: &NgramScores) -> HashMap<Language, f64> {
+///         let mut ranking = scores
+///             .languages()
+///             .iter()
+///             .filter_map(|language| {
+///                 scores
+///                     .log_probability(*language, 3)
+///                     .map(|probability| (*language, probability))
+///             })
+///             .collect::<Vec<_>>();
+///         ranking.sort_by(|first, second| second.1.total_cmp(&first.1));
+///
+///         let total: f64 = (1..=ranking.len()).map(|rank| 1.0 / rank as f64).sum();
+///         ranking
+///             .into_iter()
+///             .enumerate()
+///             .map(|(i, (language, _))| (language, 1.0 / (i + 1) as f64 / total))
+///             .collect()
+///     }
+/// }
+/// ```
+pub trait ScoreCombiner: Send + Sync {
+    /// Combines the given ngram scores into confidence values.
+    fn combine(&self, scores: &NgramScores) -> HashMap<Language, f64>;
+}
+
+impl<F> ScoreCombiner for F
+where
+    F: Fn(&NgramScores) -> HashMap<Language, f64> + Send + Sync,
+{
+    fn combine(&self, scores: &NgramScores) -> HashMap<Language, f64> {
+        self(scores)
+    }
+}
+
+/// The score combiner used by default.
+///
+/// For each language, the log probabilities of all ngram lengths are summed up. If unigrams
+/// have been evaluated, the sum is divided by the number of unigrams known to the language,
+/// so that languages with small alphabets are not preferred. The resulting probabilities
+/// are normalized with the softmax function.
+///
+/// The exponential function is computed with a portable implementation instead of the one of
+/// the platform's math library, so that the confidence values are bit-identical on all platforms.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DefaultScoreCombiner;
+
+impl ScoreCombiner for DefaultScoreCombiner {
+    fn combine(&self, scores: &NgramScores) -> HashMap<Language, f64> {
+        let probabilities = sum_up_probabilities(scores);
+
+        let denominator: f64 = probabilities
+            .iter()
+            .sorted_by_key(|(language, _)| *language)
+            .map(|(_, probability)| probability)
+            .sum();
+
+        // If the denominator is still zero, the exponent of the summed
+        // log probabilities is too large to be computed for very long input strings.
+        // So we simply set the probability of the most likely language to 1.0 and
+        // leave the other languages at 0.0.
+        if denominator.is_zero() && !probabilities.is_empty() {
+            // For very long inputs, only trigrams are used, so we safely access them at index 0.
+            let most_likely_language = scores.log_probabilities[0]
+                .iter()
+                .max_by(
+                    |(first_language, first_probability), (second_language, second_probability)| {
+                        first_probability
+                            .total_cmp(second_probability)
+                            .then_with(|| second_language.cmp(first_language))
+                    },
+                )
+                .unwrap()
+                .0;
+
+            return HashMap::from([(most_likely_language, 1.0)]);
+        }
+
+        probabilities
+            .into_iter()
+            // Apply softmax function
+            .map(|(language, probability)| (language, probability / denominator))
+            .collect()
+    }
+}
+
+fn sum_up_probabilities(scores: &NgramScores) -> HashMap<Language, f64> {
+    let mut summed_up_probabilities = HashMap::new();
+    for language in scores.languages.iter() {
+        let mut sum: f64 = scores
+            .log_probabilities
+            .iter()
+            .map(|it| it.get(*language))
+            .sum();
+
+        if let Some(count) = scores.unigram_count(*language) {
+            sum /= count as f64;
+        }
+
+        if sum != 0.0 {
+            summed_up_probabilities.insert(*language, libm::exp(sum));
+        }
+    }
+
+    summed_up_probabilities
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::language::Language::{English, French, German};
+
+    #[test]
+    fn assert_ngram_scores_are_looked_up_correctly() {
+        let languages = HashSet::from([English, German]);
+        let unigrams = LanguageCounts::from_iter([(English, -10.0), (German, -12.0)]);
+        let trigrams = LanguageCounts::from_iter([(English, -20.0)]);
+        let unigram_counts = LanguageCounts::from_iter([(English, 4), (German, 5)]);
+        let scores = NgramScores {
+            languages: &languages,
+            ngram_lengths: &[1, 3],
+            log_probabilities: vec![&unigrams, &trigrams],
+            unigram_counts: Some(&unigram_counts),
+        };
+
+        assert_eq!(scores.log_probability(German, 1), Some(-12.0));
+        assert_eq!(scores.log_probability(German, 3), None);
+        assert_eq!(scores.log_probability(English, 2), None);
+        assert_eq!(scores.unigram_count(German), Some(5));
+        assert_eq!(scores.unigram_count(French), None);
+    }
+
+    #[test]
+    fn assert_default_score_combiner_normalizes_probabilities() {
+        let languages = HashSet::from([English, German, French]);
+        let trigrams = LanguageCounts::from_iter([(English, -2.0), (German, -3.0)]);
+        let scores = NgramScores {
+            languages: &languages,
+            ngram_lengths: &[3],
+            log_probabilities: vec![&trigrams],
+            unigram_counts: None,
+        };
+
+        let confidence_values = DefaultScoreCombiner.combine(&scores);
+        let expected_english = 1.0 / (1.0 + (-1.0_f64).exp());
+
+        assert_eq!(confidence_values.len(), 2);
+        assert!((confidence_values[&English] - expected_english).abs() < 1e-12);
+        assert!((confidence_values[&German] - (1.0 - expected_english)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn assert_default_score_combiner_handles_underflow() {
+        let languages = HashSet::from([English, German]);
+        let trigrams = LanguageCounts::from_iter([(English, -5000.0), (German, -4000.0)]);
+        let scores = NgramScores {
+            languages: &languages,
+            ngram_lengths: &[3],
+            log_probabilities: vec![&trigrams],
+            unigram_counts: None,
+        };
+
+        assert_eq!(
+            DefaultScoreCombiner.combine(&scores),
+            HashMap::from([(German, 1.0)])
+        );
+    }
+}
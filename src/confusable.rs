@@ -0,0 +1,179 @@
+/*
+ * Copyright © 2020-present Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+use crate::alphabet::Alphabet;
+
+const HOMOGLYPH_ALPHABETS: [Alphabet; 3] = [Alphabet::Latin, Alphabet::Cyrillic, Alphabet::Greek];
+
+/// Characters which are visually identical across the Latin, Cyrillic and Greek alphabets,
+/// taken from the confusables data of Unicode Technical Standard #39.
+/// Each row lists the Latin, Cyrillic and Greek variant of a character, if any.
+const HOMOGLYPHS: &[(char, Option<char>, Option<char>)] = &[
+    ('A', Some('А'), Some('Α')),
+    ('B', Some('В'), Some('Β')),
+    ('C', Some('С'), Some('Ϲ')),
+    ('E', Some('Е'), Some('Ε')),
+    ('H', Some('Н'), Some('Η')),
+    ('I', Some('І'), Some('Ι')),
+    ('J', Some('Ј'), None),
+    ('K', Some('К'), Some('Κ')),
+    ('M', Some('М'), Some('Μ')),
+    ('N', None, Some('Ν')),
+    ('O', Some('О'), Some('Ο')),
+    ('P', Some('Р'), Some('Ρ')),
+    ('S', Some('Ѕ'), None),
+    ('T', Some('Т'), Some('Τ')),
+    ('X', Some('Х'), Some('Χ')),
+    ('Y', Some('Ү'), Some('Υ')),
+    ('Z', None, Some('Ζ')),
+    ('a', Some('а'), None),
+    ('c', Some('с'), Some('ϲ')),
+    ('d', Some('ԁ'), None),
+    ('e', Some('е'), None),
+    ('h', Some('һ'), None),
+    ('i', Some('і'), Some('ι')),
+    ('j', Some('ј'), Some('ϳ')),
+    ('o', Some('о'), Some('ο')),
+    ('p', Some('р'), Some('ρ')),
+    ('q', Some('ԛ'), None),
+    ('s', Some('ѕ'), None),
+    ('u', None, Some('υ')),
+    ('v', None, Some('ν')),
+    ('w', Some('ԝ'), None),
+    ('x', Some('х'), None),
+    ('y', Some('у'), Some('γ')),
+    ('Ä', Some('Ӓ'), None),
+    ('Ö', Some('Ӧ'), None),
+    ('ä', Some('ӓ'), None),
+    ('ö', Some('ӧ'), None),
+    ('ë', Some('ё'), None),
+];
+
+/// If the given word mixes the Latin, Cyrillic and Greek alphabets, replaces its characters
+/// with their homoglyphs in a single one of these alphabets, preferably one which all mixed-in
+/// characters have a homoglyph in. Characters without such a homoglyph are kept as they are.
+///
+/// Spam often mixes homoglyphs of different alphabets, such as the Cyrillic `р`
+/// in `рaypal`, in order to evade filters. As the rule-based filter engine counts
+/// the alphabets of each word, such words would otherwise confuse it.
+pub(crate) fn normalize_homoglyphs(word: &str) -> String {
+    match find_target_alphabet_of_mixed_word(word) {
+        Some(alphabet) => word
+            .chars()
+            .map(|ch| find_homoglyph(ch, alphabet).unwrap_or(ch))
+            .collect(),
+        None => word.to_string(),
+    }
+}
+
+/// Returns `true` if the given word mixes the Latin, Cyrillic and Greek alphabets
+/// in a way that [normalize_homoglyphs] would change it.
+pub(crate) fn contains_homoglyph_mixing(word: &str) -> bool {
+    match find_target_alphabet_of_mixed_word(word) {
+        Some(alphabet) => word
+            .chars()
+            .any(|ch| find_homoglyph(ch, alphabet).is_some_and(|homoglyph| homoglyph != ch)),
+        None => false,
+    }
+}
+
+/// Returns the alphabet the characters of a word mixing several alphabets should be
+/// converted to. Alphabets which all characters of the word can be converted to are preferred,
+/// so `ассоunt` with four Cyrillic and three Latin characters is converted to Latin.
+/// Otherwise, the alphabet of the majority of the word's characters is returned.
+fn find_target_alphabet_of_mixed_word(word: &str) -> Option<Alphabet> {
+    let mut alphabet_counts = HashMap::new();
+
+    for ch in word.chars() {
+        if let Some(alphabet) = HOMOGLYPH_ALPHABETS
+            .iter()
+            .find(|alphabet| alphabet.matches_char(ch))
+        {
+            *alphabet_counts.entry(*alphabet).or_insert(0) += 1;
+        }
+    }
+
+    if alphabet_counts.len() < 2 {
+        return None;
+    }
+
+    // Ties are broken by the order of HOMOGLYPH_ALPHABETS, so Latin wins over the others.
+    HOMOGLYPH_ALPHABETS
+        .iter()
+        .enumerate()
+        .filter(|(_, alphabet)| alphabet_counts.contains_key(alphabet))
+        .max_by_key(|(i, alphabet)| {
+            let is_convertible = word.chars().all(|ch| {
+                !HOMOGLYPH_ALPHABETS.iter().any(|it| it.matches_char(ch))
+                    || alphabet.matches_char(ch)
+                    || find_homoglyph(ch, **alphabet).is_some()
+            });
+            (is_convertible, alphabet_counts[*alphabet], Reverse(*i))
+        })
+        .map(|(_, alphabet)| *alphabet)
+}
+
+fn find_homoglyph(ch: char, alphabet: Alphabet) -> Option<char> {
+    HOMOGLYPHS
+        .iter()
+        .find(|(latin, cyrillic, greek)| {
+            *latin == ch || *cyrillic == Some(ch) || *greek == Some(ch)
+        })
+        .and_then(|(latin, cyrillic, greek)| match alphabet {
+            Alphabet::Latin => Some(*latin),
+            Alphabet::Cyrillic => *cyrillic,
+            Alphabet::Greek => *greek,
+            _ => None,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[rstest(
+        word,
+        expected_word,
+        case::cyrillic_in_latin("рaypal", "paypal"),
+        case::latin_in_cyrillic("пpивeт", "привет"),
+        case::greek_in_latin("Αpple", "Apple"),
+        case::latin_in_greek("καλημέpα", "καλημέρα"),
+        case::without_homoglyph("бank", "бank"),
+        case::single_alphabet("привет", "привет"),
+        case::non_letters("p4y-pаl", "p4y-pal"),
+        case::cyrillic_majority_in_latin("ассоunt", "account")
+    )]
+    fn assert_homoglyphs_are_normalized_correctly(word: &str, expected_word: &str) {
+        assert_eq!(normalize_homoglyphs(word), expected_word);
+    }
+
+    #[rstest(
+        word,
+        expected_result,
+        case("рaypal", true),
+        case("пpивeт", true),
+        case("paypal", false),
+        case("привет", false),
+        case("бank", false)
+    )]
+    fn assert_homoglyph_mixing_is_detected_correctly(word: &str, expected_result: bool) {
+        assert_eq!(contains_homoglyph_mixing(word), expected_result);
+    }
+}
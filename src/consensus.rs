@@ -0,0 +1,102 @@
+/*
+ * Copyright © 2020-present Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::language::Language;
+
+pub(crate) const CHUNK_SIZE_MESSAGE: &str = "Chunk size must be greater than zero";
+pub(crate) const MAXIMUM_DISAGREEMENT_MESSAGE: &str =
+    "Maximum disagreement must lie in between 0.0 and 1.0";
+
+/// This struct describes the outcome of detecting the language of a long text
+/// chunk by chunk, as returned by
+/// [LanguageDetector::detect_language_by_consensus_of](crate::LanguageDetector::detect_language_by_consensus_of).
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConsensusResult {
+    pub(crate) language: Option<Language>,
+    pub(crate) chunk_languages: Vec<Option<Language>>,
+    pub(crate) agreement: f64,
+    pub(crate) is_mixed: bool,
+}
+
+impl ConsensusResult {
+    /// Returns the language detected in most of the chunks,
+    /// or [None] if the language of no chunk could be detected.
+    pub fn language(&self) -> Option<Language> {
+        self.language
+    }
+    /// Returns the language detected for each chunk in the order of the chunks.
+    pub fn chunk_languages(&self) -> &[Option<Language>] {
+        &self.chunk_languages
+    }
+    /// Returns the number of chunks the text has been split into.
+    pub fn chunk_count(&self) -> usize {
+        self.chunk_languages.len()
+    }
+    /// Returns the share of chunks whose detected language equals the consensus language,
+    /// a value between 0.0 and 1.0.
+    pub fn agreement(&self) -> f64 {
+        self.agreement
+    }
+    /// Returns `true` if the share of chunks disagreeing with the consensus language
+    /// exceeds the maximum disagreement, i.e. if the text is likely written in
+    /// more than one language.
+    pub fn is_mixed(&self) -> bool {
+        self.is_mixed
+    }
+}
+
+/// Splits the given text at whitespace into chunks of at least `chunk_size` characters,
+/// without breaking any words. The last chunk may be shorter.
+pub(crate) fn split_text_into_chunks(text: &str, chunk_size: usize) -> Vec<String> {
+    let mut chunks = vec![];
+    let mut current_chunk = String::new();
+    let mut current_char_count = 0;
+
+    for word in text.split_whitespace() {
+        if !current_chunk.is_empty() {
+            current_chunk.push(' ');
+            current_char_count += 1;
+        }
+        current_chunk.push_str(word);
+        current_char_count += word.chars().count();
+
+        if current_char_count >= chunk_size {
+            chunks.push(std::mem::take(&mut current_chunk));
+            current_char_count = 0;
+        }
+    }
+
+    if !current_chunk.is_empty() {
+        chunks.push(current_chunk);
+    }
+
+    chunks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn assert_text_is_split_into_chunks_correctly() {
+        assert_eq!(
+            split_text_into_chunks("This is  a\nlonger text, isn't it?", 10),
+            vec!["This is a longer", "text, isn't", "it?"]
+        );
+        assert_eq!(split_text_into_chunks("short", 10), vec!["short"]);
+        assert!(split_text_into_chunks(" \n ", 10).is_empty());
+    }
+}
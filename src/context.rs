@@ -0,0 +1,206 @@
+/*
+ * Copyright © 2020-present Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::HashSet;
+
+use once_cell::unsync::OnceCell;
+
+use crate::detector::{compute_undetermined_confidence_values, LanguageDetector};
+use crate::language::Language;
+use crate::limits::DetectionError;
+
+/// The words of a text after the preprocessing steps which do not depend
+/// on the candidate languages.
+pub(crate) struct PreprocessedText {
+    pub(crate) words: Vec<String>,
+    pub(crate) is_all_caps: bool,
+}
+
+/// A text prepared once for repeated queries against a [LanguageDetector].
+///
+/// Creating a context splits the text into words and applies all preprocessing steps
+/// which do not depend on the candidate languages. The confidence values are computed
+/// on first use and cached, so that calling both [detect_language](DetectionContext::detect_language)
+/// and [compute_language_confidence_values](DetectionContext::compute_language_confidence_values)
+/// evaluates the language models only once. Queries restricted to other languages
+/// reuse the preprocessed words.
+///
+/// The results are identical to those of the corresponding methods of [LanguageDetector]
+/// for the same text.
+///
+/// ```
+/// use lingua::{DetectionContext, LanguageDetectorBuilder};
+/// use lingua::Language::{English, French, German, Spanish};
+///
+/// let detector = LanguageDetectorBuilder::from_languages(&[English, French, German, Spanish]).build();
+/// let context = DetectionContext::new(&detector, "languages are awesome");
+///
+/// assert_eq!(context.detect_language(), Some(English));
+/// assert_eq!(context.compute_language_confidence_values()[0].0, English);
+/// assert_eq!(
+///     context.detect_language_with_languages(&[French, Spanish]),
+///     Some(French)
+/// );
+/// ```
+pub struct DetectionContext<'a> {
+    detector: &'a LanguageDetector,
+    text: Result<PreprocessedText, DetectionError>,
+    confidence_values: OnceCell<Vec<(Language, f64)>>,
+}
+
+impl<'a> DetectionContext<'a> {
+    /// Creates a new context for the given text which delegates to the given [LanguageDetector].
+    pub fn new<T: Into<String>>(detector: &'a LanguageDetector, text: T) -> Self {
+        Self {
+            detector,
+            text: detector.preprocess_text(text.into()),
+            confidence_values: OnceCell::new(),
+        }
+    }
+
+    /// Detects the language of the text like [LanguageDetector::detect_language_of] does.
+    pub fn detect_language(&self) -> Option<Language> {
+        self.detector
+            .select_language_or_fallback(self.compute_language_confidence_values())
+    }
+
+    /// Detects the language of the text like [LanguageDetector::detect_language_of_with_languages]
+    /// does. The result is not cached, but the preprocessed words are reused.
+    pub fn detect_language_with_languages(&self, languages: &[Language]) -> Option<Language> {
+        let languages = languages
+            .iter()
+            .filter(|language| self.detector.languages.contains(language))
+            .copied()
+            .collect::<HashSet<_>>();
+
+        if languages.is_empty() {
+            return None;
+        }
+
+        let confidence_values = self.compute_confidence_values_for_languages(&languages);
+
+        self.detector
+            .select_language_or_fallback(&confidence_values)
+    }
+
+    /// Returns the confidence values of the text like
+    /// [LanguageDetector::compute_language_confidence_values] does.
+    /// They are computed on the first call and cached afterwards.
+    pub fn compute_language_confidence_values(&self) -> &[(Language, f64)] {
+        self.confidence_values
+            .get_or_init(|| self.compute_confidence_values_for_languages(&self.detector.languages))
+    }
+
+    /// Returns the confidence value of the given language like
+    /// [LanguageDetector::compute_language_confidence] does, taken from the cached
+    /// confidence values.
+    pub fn compute_language_confidence(&self, language: Language) -> f64 {
+        self.compute_language_confidence_values()
+            .iter()
+            .find(|(lang, _)| *lang == language)
+            .map_or(0.0, |(_, confidence_value)| *confidence_value)
+    }
+
+    fn compute_confidence_values_for_languages(
+        &self,
+        languages: &HashSet<Language>,
+    ) -> Vec<(Language, f64)> {
+        let text = match &self.text {
+            Ok(text) => text,
+            Err(_) => return compute_undetermined_confidence_values(languages),
+        };
+        let deadline = self.detector.limits.start();
+
+        self.detector
+            .compute_unprocessed_language_confidence_values_of_words(
+                text.words.clone(),
+                text.is_all_caps,
+                languages,
+                None,
+                None,
+                &deadline,
+            )
+            .map(|values| {
+                self.detector
+                    .process_language_confidence_values(values, languages.len())
+            })
+            .unwrap_or_else(|_| compute_undetermined_confidence_values(languages))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::LanguageDetectorBuilder;
+    use crate::language::Language::*;
+
+    #[test]
+    fn assert_context_results_equal_detector_results() {
+        let detector =
+            LanguageDetectorBuilder::from_languages(&[English, French, German, Spanish]).build();
+
+        for text in [
+            "Das ist ein schöner Tag",
+            "languages are awesome",
+            "123",
+            "",
+        ] {
+            let context = DetectionContext::new(&detector, text);
+
+            assert_eq!(context.detect_language(), detector.detect_language_of(text));
+            assert_eq!(
+                context.compute_language_confidence_values(),
+                detector.compute_language_confidence_values(text)
+            );
+            assert_eq!(
+                context.compute_language_confidence(German),
+                detector.compute_language_confidence(text, German)
+            );
+            assert_eq!(
+                context.detect_language_with_languages(&[French, Spanish]),
+                detector.detect_language_of_with_languages(text, &[French, Spanish])
+            );
+        }
+    }
+
+    #[test]
+    fn assert_confidence_values_are_computed_once() {
+        let detector = LanguageDetectorBuilder::from_languages(&[English, German]).build();
+        let context = DetectionContext::new(&detector, "languages are awesome");
+
+        assert!(context.confidence_values.get().is_none());
+        context.detect_language();
+        let confidence_values = context.confidence_values.get().unwrap();
+        assert!(std::ptr::eq(
+            confidence_values.as_slice(),
+            context.compute_language_confidence_values()
+        ));
+    }
+
+    #[test]
+    fn assert_text_exceeding_limits_is_undetermined() {
+        let detector = LanguageDetectorBuilder::from_languages(&[English, German])
+            .with_maximum_input_bytes(4)
+            .build();
+        let context = DetectionContext::new(&detector, "languages are awesome");
+
+        assert_eq!(context.detect_language(), None);
+        assert_eq!(
+            context.compute_language_confidence_values(),
+            detector.compute_language_confidence_values("languages are awesome")
+        );
+    }
+}
@@ -0,0 +1,272 @@
+/*
+ * Copyright © 2020-present Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+
+use crate::alphabet::Alphabet;
+use crate::isocode::{IsoCode639_1, IsoCode639_3};
+use crate::json::{compressed_model_size, load_language_model};
+use crate::language::Language;
+
+/// This struct describes what a [LanguageDetector](crate::LanguageDetector)
+/// knows about a single one of its languages.
+///
+/// It can be serialized with any serde backend, e.g. to JSON,
+/// in order to document a deployment in a machine-readable way.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct LanguageCoverage {
+    pub(crate) language: Language,
+    pub(crate) iso_code_639_1: IsoCode639_1,
+    pub(crate) iso_code_639_3: IsoCode639_3,
+    pub(crate) alphabets: Vec<Alphabet>,
+    pub(crate) model_sizes: ModelSizes,
+    pub(crate) training_corpus: Option<String>,
+}
+
+/// This struct holds the size in bytes of each compressed ngram model
+/// embedded for a specific language.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ModelSizes {
+    pub(crate) unigrams: usize,
+    pub(crate) bigrams: usize,
+    pub(crate) trigrams: usize,
+    pub(crate) quadrigrams: usize,
+    pub(crate) fivegrams: usize,
+}
+
+impl LanguageCoverage {
+    pub(crate) fn from(language: Language) -> Self {
+        let mut alphabets = language.alphabets().into_iter().collect::<Vec<_>>();
+        alphabets.sort();
+
+        Self {
+            language,
+            iso_code_639_1: language.iso_code_639_1(),
+            iso_code_639_3: language.iso_code_639_3(),
+            alphabets,
+            model_sizes: ModelSizes::from(language),
+            training_corpus: None,
+        }
+    }
+
+    /// Returns the language this entry describes.
+    pub fn language(&self) -> Language {
+        self.language
+    }
+
+    /// Returns the ISO 639-1 code of the language.
+    pub fn iso_code_639_1(&self) -> IsoCode639_1 {
+        self.iso_code_639_1
+    }
+
+    /// Returns the ISO 639-3 code of the language.
+    pub fn iso_code_639_3(&self) -> IsoCode639_3 {
+        self.iso_code_639_3
+    }
+
+    /// Returns the alphabets the language is written in, in sorted order.
+    pub fn alphabets(&self) -> &[Alphabet] {
+        &self.alphabets
+    }
+
+    /// Returns the sizes of the compressed ngram models of the language.
+    pub fn model_sizes(&self) -> ModelSizes {
+        self.model_sizes
+    }
+
+    /// Returns a description of the corpus the language models have been trained on,
+    /// or [None] if it is unknown.
+    ///
+    /// The language model crates do not record the corpus their models have been
+    /// trained on, so the training corpus of the bundled languages is unknown.
+    pub fn training_corpus(&self) -> Option<&str> {
+        self.training_corpus.as_deref()
+    }
+
+    /// Computes the share of the characters in the training data of the language which
+    /// belong to one of its [alphabets](LanguageCoverage::alphabets), weighted by their
+    /// relative frequencies. Returns [None] if the unigram model of the language
+    /// cannot be loaded.
+    ///
+    /// For the bundled languages, this is at least 0.99. A lower value indicates that
+    /// the alphabets declared for the language do not match its language models,
+    /// so applications can use this method to validate their setup at startup.
+    /// This loads the unigram model of the language and is therefore slow.
+    ///
+    /// ```
+    /// use lingua::Language::{English, Greek};
+    /// use lingua::LanguageDetectorBuilder;
+    ///
+    /// let detector = LanguageDetectorBuilder::from_languages(&[English, Greek]).build();
+    ///
+    /// for coverage in detector.coverage_report() {
+    ///     assert!(coverage.compute_alphabet_coverage().unwrap() >= 0.99);
+    /// }
+    /// ```
+    pub fn compute_alphabet_coverage(&self) -> Option<f64> {
+        compute_alphabet_coverage(self.language, &self.alphabets.iter().copied().collect())
+    }
+}
+
+impl ModelSizes {
+    fn from(language: Language) -> Self {
+        let size = |ngram_length| compressed_model_size(language, ngram_length).unwrap_or(0);
+
+        Self {
+            unigrams: size(1),
+            bigrams: size(2),
+            trigrams: size(3),
+            quadrigrams: size(4),
+            fivegrams: size(5),
+        }
+    }
+
+    /// Returns the size in bytes of the compressed unigram model.
+    pub fn unigrams(&self) -> usize {
+        self.unigrams
+    }
+
+    /// Returns the size in bytes of the compressed bigram model.
+    pub fn bigrams(&self) -> usize {
+        self.bigrams
+    }
+
+    /// Returns the size in bytes of the compressed trigram model.
+    pub fn trigrams(&self) -> usize {
+        self.trigrams
+    }
+
+    /// Returns the size in bytes of the compressed quadrigram model.
+    pub fn quadrigrams(&self) -> usize {
+        self.quadrigrams
+    }
+
+    /// Returns the size in bytes of the compressed fivegram model.
+    pub fn fivegrams(&self) -> usize {
+        self.fivegrams
+    }
+
+    /// Returns the sum of the sizes of all compressed ngram models.
+    pub fn total(&self) -> usize {
+        self.unigrams + self.bigrams + self.trigrams + self.quadrigrams + self.fivegrams
+    }
+}
+
+/// Returns the share of the characters of the training data of the given language which
+/// belong to one of the given alphabets. The share is computed from the relative
+/// frequencies stored in the unigram model of the language.
+/// Returns [None] if the unigram model is not available.
+fn compute_alphabet_coverage(language: Language, alphabets: &HashSet<Alphabet>) -> Option<f64> {
+    let unigrams = load_language_model(language, 1).ok()?;
+    let mut total_frequency = 0.0;
+    let mut covered_frequency = 0.0;
+
+    for (unigram, frequency) in unigrams.iter() {
+        total_frequency += frequency;
+        if unigram
+            .chars()
+            .all(|ch| alphabets.iter().any(|alphabet| alphabet.matches_char(ch)))
+        {
+            covered_frequency += frequency;
+        }
+    }
+
+    if total_frequency > 0.0 {
+        Some(covered_frequency / total_frequency)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use strum::IntoEnumIterator;
+
+    const MINIMUM_ALPHABET_COVERAGE: f64 = 0.99;
+
+    #[test]
+    fn assert_declared_alphabets_cover_training_data() {
+        let violations = Language::iter()
+            .filter_map(|language| {
+                let coverage = LanguageCoverage::from(language)
+                    .compute_alphabet_coverage()
+                    .unwrap_or_else(|| panic!("unigram model of {language} is missing"));
+                if coverage < MINIMUM_ALPHABET_COVERAGE {
+                    Some(format!(
+                        "{language}: {:.2} % of the training data is written in {:?}",
+                        coverage * 100.0,
+                        language.alphabets()
+                    ))
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<_>>();
+
+        assert!(
+            violations.is_empty(),
+            "declared alphabets do not cover the training data:\n{}",
+            violations.join("\n")
+        );
+    }
+
+    #[test]
+    fn assert_wrongly_declared_alphabets_are_detected() {
+        let coverage =
+            compute_alphabet_coverage(Language::Russian, &hashset!(Alphabet::Latin)).unwrap();
+        assert!(coverage < MINIMUM_ALPHABET_COVERAGE);
+
+        let coverage =
+            compute_alphabet_coverage(Language::Japanese, &hashset!(Alphabet::Han)).unwrap();
+        assert!(coverage < MINIMUM_ALPHABET_COVERAGE);
+    }
+
+    #[test]
+    fn assert_language_coverage_is_computed_correctly() {
+        let coverage = LanguageCoverage::from(Language::Japanese);
+
+        assert_eq!(coverage.language(), Language::Japanese);
+        assert_eq!(coverage.iso_code_639_1(), IsoCode639_1::JA);
+        assert_eq!(coverage.iso_code_639_3(), IsoCode639_3::JPN);
+        assert_eq!(
+            coverage.alphabets(),
+            &[Alphabet::Han, Alphabet::Hiragana, Alphabet::Katakana]
+        );
+        assert_eq!(coverage.training_corpus(), None);
+
+        let model_sizes = coverage.model_sizes();
+
+        assert!(model_sizes.unigrams() > 0);
+        assert_eq!(model_sizes.bigrams(), 0);
+        assert_eq!(model_sizes.trigrams(), 0);
+        assert_eq!(model_sizes.quadrigrams(), 0);
+        assert_eq!(model_sizes.fivegrams(), 0);
+        assert_eq!(model_sizes.total(), model_sizes.unigrams());
+    }
+
+    #[test]
+    fn assert_language_coverage_can_be_serialized_to_json() {
+        let coverage = LanguageCoverage::from(Language::German);
+        let json = serde_json::to_string(&coverage).unwrap();
+
+        assert!(json.starts_with(
+            r#"{"language":"GERMAN","iso_code_639_1":"DE","iso_code_639_3":"DEU","alphabets":["Latin"],"model_sizes":{"unigrams":"#
+        ));
+    }
+}
@@ -0,0 +1,209 @@
+/*
+ * Copyright © 2020-present Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+
+pub(crate) const NEAR_DUPLICATE_THRESHOLD_MESSAGE: &str =
+    "Near-duplicate threshold must lie in between 0.0 and 1.0, excluding 0.0";
+
+const SHINGLE_LENGTH: usize = 3;
+const MINHASH_BAND_COUNT: usize = 16;
+const MINHASH_ROWS_PER_BAND: usize = 4;
+const MINHASH_SIGNATURE_LENGTH: usize = MINHASH_BAND_COUNT * MINHASH_ROWS_PER_BAND;
+
+/// The outcome of deduplicating a batch of texts.
+pub(crate) struct Deduplication {
+    /// The indices of the texts whose language is actually detected.
+    pub(crate) representatives: Vec<usize>,
+    /// For each text of the batch, the position of its representative in `representatives`.
+    pub(crate) assignments: Vec<usize>,
+}
+
+impl Deduplication {
+    /// Maps the results computed for the representatives back to all texts of the batch.
+    pub(crate) fn expand<R: Clone>(&self, results: Vec<R>) -> Vec<R> {
+        self.assignments
+            .iter()
+            .map(|position| results[*position].clone())
+            .collect()
+    }
+}
+
+/// Groups duplicate texts of a batch, so that the language of each group is computed once.
+///
+/// Exact duplicates are always grouped. If a `near_duplicate_threshold` is given,
+/// texts whose MinHash-estimated Jaccard similarity of character trigrams with an
+/// earlier text reaches the threshold are grouped with this text as well.
+/// Candidate pairs are found with locality-sensitive hashing, so the effort
+/// grows linearly with the size of the batch.
+pub(crate) fn deduplicate_texts(
+    texts: &[String],
+    near_duplicate_threshold: Option<f64>,
+) -> Deduplication {
+    let mut representatives = vec![];
+    let mut assignments = Vec::with_capacity(texts.len());
+    let mut exact_positions: HashMap<&str, usize> = HashMap::new();
+    let mut signatures: Vec<Option<[u64; MINHASH_SIGNATURE_LENGTH]>> = vec![];
+    let mut band_buckets: HashMap<(usize, u64), Vec<usize>> = HashMap::new();
+
+    for (i, text) in texts.iter().enumerate() {
+        if let Some(position) = exact_positions.get(text.as_str()) {
+            assignments.push(*position);
+            continue;
+        }
+
+        let signature = near_duplicate_threshold.and_then(|_| compute_minhash_signature(text));
+
+        let near_duplicate_position = signature.as_ref().and_then(|signature| {
+            let threshold = near_duplicate_threshold.unwrap();
+            compute_band_hashes(signature)
+                .into_iter()
+                .filter_map(|band_hash| band_buckets.get(&band_hash))
+                .flatten()
+                .copied()
+                .collect::<HashSet<_>>()
+                .into_iter()
+                .filter(|position| {
+                    let other_signature = signatures[*position].as_ref().unwrap();
+                    estimate_similarity(signature, other_signature) >= threshold
+                })
+                .min()
+        });
+
+        let position = match near_duplicate_position {
+            Some(position) => position,
+            None => {
+                let position = representatives.len();
+                representatives.push(i);
+                if let Some(signature) = signature.as_ref() {
+                    for band_hash in compute_band_hashes(signature) {
+                        band_buckets.entry(band_hash).or_default().push(position);
+                    }
+                }
+                signatures.push(signature);
+                position
+            }
+        };
+
+        exact_positions.insert(text, position);
+        assignments.push(position);
+    }
+
+    Deduplication {
+        representatives,
+        assignments,
+    }
+}
+
+/// Computes the MinHash signature of the set of character trigrams of the given text,
+/// ignoring case and whitespace differences. Returns [None] for texts which are too short.
+fn compute_minhash_signature(text: &str) -> Option<[u64; MINHASH_SIGNATURE_LENGTH]> {
+    let normalized_text = text
+        .split_whitespace()
+        .map(|word| word.to_lowercase())
+        .collect::<Vec<_>>()
+        .join(" ");
+    let chars = normalized_text.chars().collect::<Vec<_>>();
+
+    if chars.len() < SHINGLE_LENGTH {
+        return None;
+    }
+
+    let mut signature = [u64::MAX; MINHASH_SIGNATURE_LENGTH];
+
+    for shingle in chars.windows(SHINGLE_LENGTH) {
+        for (seed, minimum) in signature.iter_mut().enumerate() {
+            let mut hasher = DefaultHasher::new();
+            seed.hash(&mut hasher);
+            shingle.hash(&mut hasher);
+            *minimum = (*minimum).min(hasher.finish());
+        }
+    }
+
+    Some(signature)
+}
+
+fn compute_band_hashes(signature: &[u64; MINHASH_SIGNATURE_LENGTH]) -> Vec<(usize, u64)> {
+    signature
+        .chunks(MINHASH_ROWS_PER_BAND)
+        .enumerate()
+        .map(|(band, rows)| {
+            let mut hasher = DefaultHasher::new();
+            rows.hash(&mut hasher);
+            (band, hasher.finish())
+        })
+        .collect()
+}
+
+fn estimate_similarity(
+    first_signature: &[u64; MINHASH_SIGNATURE_LENGTH],
+    second_signature: &[u64; MINHASH_SIGNATURE_LENGTH],
+) -> f64 {
+    let equal_count = first_signature
+        .iter()
+        .zip(second_signature.iter())
+        .filter(|(first, second)| first == second)
+        .count();
+
+    equal_count as f64 / MINHASH_SIGNATURE_LENGTH as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_strings(texts: &[&str]) -> Vec<String> {
+        texts.iter().map(|text| text.to_string()).collect()
+    }
+
+    #[test]
+    fn assert_exact_duplicates_are_grouped() {
+        let texts = to_strings(&["hello", "world", "hello", "hello", "world", "again"]);
+        let deduplication = deduplicate_texts(&texts, None);
+
+        assert_eq!(deduplication.representatives, vec![0, 1, 5]);
+        assert_eq!(deduplication.assignments, vec![0, 1, 0, 0, 1, 2]);
+        assert_eq!(
+            deduplication.expand(vec!['a', 'b', 'c']),
+            vec!['a', 'b', 'a', 'a', 'b', 'c']
+        );
+    }
+
+    #[test]
+    fn assert_near_duplicates_are_grouped_if_enabled() {
+        let texts = to_strings(&[
+            "Get 50% off all shoes today only at our online store!",
+            "get 50% off all shoes  today only at our online store!!",
+            "Der Hund schläft den ganzen Tag auf dem Sofa.",
+        ]);
+
+        assert_eq!(deduplicate_texts(&texts, None).assignments, vec![0, 1, 2]);
+        assert_eq!(
+            deduplicate_texts(&texts, Some(0.8)).assignments,
+            vec![0, 0, 1]
+        );
+    }
+
+    #[test]
+    fn assert_short_texts_are_only_grouped_if_identical() {
+        let texts = to_strings(&["ab", "Ab", "ab"]);
+        let deduplication = deduplicate_texts(&texts, Some(0.5));
+
+        assert_eq!(deduplication.assignments, vec![0, 1, 0]);
+    }
+}
@@ -0,0 +1,243 @@
+/*
+ * Copyright © 2020-present Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::detector::{split_text_into_words, LanguageDetector};
+use crate::language::Language;
+
+/// This struct holds the words of a Hunspell dictionary.
+///
+/// Only the word list of the `.dic` file is read. Affix rules of the accompanying
+/// `.aff` file are not applied, so inflected forms which are not listed explicitly
+/// are not recognized. For verifying a detection result, this is usually sufficient
+/// because frequent words such as articles and prepositions are listed as they are.
+#[derive(Clone, Debug, Default)]
+pub struct HunspellDictionary {
+    words: HashSet<String>,
+}
+
+impl HunspellDictionary {
+    /// Reads the words of the given Hunspell `.dic` file which must be encoded in UTF-8.
+    pub fn from_dic_file<P: AsRef<Path>>(file_path: P) -> io::Result<Self> {
+        Ok(Self::from_dic_content(&fs::read_to_string(file_path)?))
+    }
+
+    /// Parses the content of a Hunspell `.dic` file. The optional word count in the first
+    /// line, affix flags after a slash and morphological fields after whitespace are ignored.
+    pub fn from_dic_content(content: &str) -> Self {
+        let mut lines = content.lines().peekable();
+
+        if let Some(first_line) = lines.peek() {
+            if first_line.trim().parse::<usize>().is_ok() {
+                lines.next();
+            }
+        }
+
+        Self::from_words(lines.filter_map(|line| {
+            let entry = line.split_whitespace().next()?;
+            let word = entry.split('/').next()?;
+            Some(word.to_string())
+        }))
+    }
+
+    /// Creates a dictionary from the given words.
+    pub fn from_words<I: IntoIterator<Item = S>, S: AsRef<str>>(words: I) -> Self {
+        Self {
+            words: words
+                .into_iter()
+                .map(|word| word.as_ref().trim().to_lowercase())
+                .filter(|word| !word.is_empty())
+                .collect(),
+        }
+    }
+
+    /// Returns `true` if the given word is contained in this dictionary, ignoring case.
+    pub fn contains(&self, word: &str) -> bool {
+        self.words.contains(&word.to_lowercase())
+    }
+
+    /// Returns the number of words in this dictionary.
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    /// Returns `true` if this dictionary does not contain any words.
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+}
+
+/// This struct verifies the results of a [LanguageDetector] against dictionaries.
+///
+/// Statistical detection can systematically fail on jargon-heavy texts whose ngrams
+/// happen to resemble another language. After the language has been detected, a sample
+/// of the text's words is looked up in the dictionary of this language. If too few of
+/// them are found, the result is demoted in favor of the next most likely language
+/// whose dictionary confirms it.
+///
+/// ```
+/// use lingua::{DictionaryVerifier, HunspellDictionary, LanguageDetectorBuilder};
+/// use lingua::Language::{English, German};
+///
+/// let detector = LanguageDetectorBuilder::from_languages(&[English, German]).build();
+/// let mut verifier = DictionaryVerifier::new(0.5, 20);
+/// verifier.add_dictionary(English, HunspellDictionary::from_words(["the", "is", "on"]));
+///
+/// assert_eq!(verifier.hit_rate(English, "The cat is on the mat"), Some(4.0 / 6.0));
+/// assert_eq!(verifier.detect_language_of(&detector, "The cat is on the mat"), Some(English));
+/// ```
+#[derive(Clone, Debug)]
+pub struct DictionaryVerifier {
+    dictionaries: HashMap<Language, HunspellDictionary>,
+    minimum_hit_rate: f64,
+    sample_size: usize,
+}
+
+impl DictionaryVerifier {
+    /// Creates a verifier which demotes a detected language if less than `minimum_hit_rate`
+    /// of the first `sample_size` words of the text are found in its dictionary.
+    ///
+    /// ⚠ Panics if `minimum_hit_rate` is smaller than 0.0 or greater than 1.0
+    /// or if `sample_size` is zero.
+    pub fn new(minimum_hit_rate: f64, sample_size: usize) -> Self {
+        if !(0.0..=1.0).contains(&minimum_hit_rate) {
+            panic!("Minimum hit rate must lie in between 0.0 and 1.0");
+        }
+        if sample_size == 0 {
+            panic!("Sample size must be greater than zero");
+        }
+        Self {
+            dictionaries: HashMap::new(),
+            minimum_hit_rate,
+            sample_size,
+        }
+    }
+
+    /// Adds the dictionary used to verify the given language, replacing any previous one.
+    pub fn add_dictionary(&mut self, language: Language, dictionary: HunspellDictionary) {
+        self.dictionaries.insert(language, dictionary);
+    }
+
+    /// Returns the share of the sampled words of the given text which are contained in the
+    /// dictionary of the given language, or [None] if there is no dictionary for the
+    /// language or the text does not contain any words.
+    pub fn hit_rate(&self, language: Language, text: &str) -> Option<f64> {
+        let dictionary = self.dictionaries.get(&language)?;
+        let words = split_text_into_words(text);
+        let sample = &words[..words.len().min(self.sample_size)];
+
+        if sample.is_empty() {
+            return None;
+        }
+
+        let hit_count = sample
+            .iter()
+            .filter(|word| dictionary.contains(word))
+            .count();
+
+        Some(hit_count as f64 / sample.len() as f64)
+    }
+
+    /// Returns `true` if the given language is confirmed for the given text, i.e. if there
+    /// is no dictionary for it or if the hit rate reaches the minimum hit rate.
+    pub fn is_confirmed(&self, language: Language, text: &str) -> bool {
+        self.hit_rate(language, text)
+            .is_none_or(|hit_rate| hit_rate >= self.minimum_hit_rate)
+    }
+
+    /// Detects the language of the given text with the given detector and verifies the result.
+    ///
+    /// If the detected language is not confirmed by its dictionary, the remaining languages
+    /// with a confidence value greater than zero are tried in descending order of their
+    /// confidence values. The first one whose dictionary confirms it is returned. Languages
+    /// without dictionary cannot replace a demoted language. If no language is confirmed,
+    /// [None] is returned.
+    pub fn detect_language_of(&self, detector: &LanguageDetector, text: &str) -> Option<Language> {
+        let detected_language = detector.detect_language_of(text)?;
+
+        if self.is_confirmed(detected_language, text) {
+            return Some(detected_language);
+        }
+
+        detector
+            .compute_language_confidence_values(text)
+            .into_iter()
+            .filter(|(language, confidence)| *language != detected_language && *confidence > 0.0)
+            .map(|(language, _)| language)
+            .find(|language| {
+                self.dictionaries.contains_key(language) && self.is_confirmed(*language, text)
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::language::Language::*;
+
+    #[test]
+    fn assert_dic_content_is_parsed_correctly() {
+        let dictionary =
+            HunspellDictionary::from_dic_content("4\nHaus/ABC\ngehen/XY po:verb\nder\n\nBaum/\n");
+
+        assert_eq!(dictionary.len(), 4);
+        assert!(dictionary.contains("haus"));
+        assert!(dictionary.contains("Gehen"));
+        assert!(dictionary.contains("der"));
+        assert!(dictionary.contains("baum"));
+        assert!(!dictionary.contains("4"));
+    }
+
+    #[test]
+    fn assert_hit_rate_is_computed_from_sample() {
+        let mut verifier = DictionaryVerifier::new(0.5, 3);
+        verifier.add_dictionary(English, HunspellDictionary::from_words(["a", "b"]));
+
+        assert_eq!(verifier.hit_rate(English, "a x b a a"), Some(2.0 / 3.0));
+        assert_eq!(verifier.hit_rate(English, "123"), None);
+        assert_eq!(verifier.hit_rate(German, "a b"), None);
+        assert!(verifier.is_confirmed(German, "x y"));
+        assert!(!verifier.is_confirmed(English, "x y"));
+    }
+
+    #[test]
+    fn assert_unconfirmed_language_is_demoted() {
+        let detector = LanguageDetector::from(hashset!(English, German), 0.0, false, false);
+        let text = "Die Katze sitzt auf der Matte";
+        let mut verifier = DictionaryVerifier::new(0.5, 10);
+
+        assert_eq!(verifier.detect_language_of(&detector, text), Some(German));
+
+        verifier.add_dictionary(German, HunspellDictionary::from_words(["haus"]));
+        assert_eq!(verifier.detect_language_of(&detector, text), None);
+
+        verifier.add_dictionary(
+            English,
+            HunspellDictionary::from_words(["die", "katze", "sitzt", "auf"]),
+        );
+        assert_eq!(verifier.detect_language_of(&detector, text), Some(English));
+    }
+
+    #[test]
+    #[should_panic(expected = "Minimum hit rate must lie in between 0.0 and 1.0")]
+    fn assert_verifier_cannot_be_created_with_invalid_minimum_hit_rate() {
+        DictionaryVerifier::new(1.5, 10);
+    }
+}
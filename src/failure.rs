@@ -0,0 +1,168 @@
+/*
+ * Copyright © 2020-present Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::HashMap;
+use std::io;
+use std::io::ErrorKind;
+use std::sync::RwLock;
+use std::thread;
+use std::time::Duration;
+
+use once_cell::sync::Lazy;
+
+use crate::language::Language;
+
+/// The injected failures, keyed by language and by ngram length if restricted to it.
+type InjectedFailureMap = RwLock<HashMap<(Language, Option<usize>), ModelFailure>>;
+
+static INJECTED_FAILURES: Lazy<InjectedFailureMap> = Lazy::new(|| RwLock::new(HashMap::new()));
+
+/// This enum specifies the failures which can be simulated when a language model
+/// file is loaded.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ModelFailure {
+    /// The model file does not exist. The model is skipped as if its language
+    /// had no ngrams of the respective length.
+    Missing,
+    /// The model file is truncated, so that decompressing it fails.
+    /// The model is skipped like a missing one.
+    Corrupted,
+    /// The model file is read only after the given delay has passed.
+    SlowIo(Duration),
+}
+
+/// This struct simulates failures of the lazy language model loaders.
+///
+/// It is meant for applications embedding *Lingua* which want to verify their error handling
+/// and timeouts around detector initialization. Injected failures apply to all detectors
+/// in the process, but only affect models which are loaded afterwards. Models which are
+/// already in memory must be unloaded first with
+/// [LanguageDetector::unload_language_models](crate::LanguageDetector::unload_language_models).
+///
+/// This struct is only available if the `failure-injection` feature is enabled,
+/// which should never be the case in production builds.
+///
+/// ```
+/// use lingua::{FailureInjector, LanguageDetectorBuilder, ModelFailure};
+/// use lingua::Language::{English, German};
+///
+/// FailureInjector::inject(German, ModelFailure::Missing);
+///
+/// let detector = LanguageDetectorBuilder::from_languages(&[English, German]).build();
+/// detector.unload_language_models();
+///
+/// assert_eq!(detector.detect_language_of("Ich bin hier"), Some(English));
+///
+/// FailureInjector::clear();
+/// ```
+pub struct FailureInjector;
+
+impl FailureInjector {
+    /// Simulates the given failure for all language model files of the given language.
+    pub fn inject(language: Language, failure: ModelFailure) {
+        INJECTED_FAILURES
+            .write()
+            .unwrap()
+            .insert((language, None), failure);
+    }
+
+    /// Simulates the given failure for the language model file of the given language
+    /// which contains ngrams of the given length. This takes precedence over a failure
+    /// injected for all files of the language.
+    ///
+    /// ⚠ Panics if `ngram_length` is not in the range 1 to 5.
+    pub fn inject_for_ngram_length(language: Language, ngram_length: usize, failure: ModelFailure) {
+        if !(1..=5).contains(&ngram_length) {
+            panic!("Ngram length must be in between 1 and 5");
+        }
+        INJECTED_FAILURES
+            .write()
+            .unwrap()
+            .insert((language, Some(ngram_length)), failure);
+    }
+
+    /// Removes all injected failures.
+    pub fn clear() {
+        INJECTED_FAILURES.write().unwrap().clear();
+    }
+}
+
+/// Simulates the failure injected for the language model file of the given language
+/// and ngram length, if any, and returns the possibly corrupted file contents.
+pub(crate) fn apply_injected_failure(
+    language: Language,
+    ngram_length: usize,
+    contents: &[u8],
+) -> io::Result<&[u8]> {
+    let failures = INJECTED_FAILURES.read().unwrap();
+    let failure = failures
+        .get(&(language, Some(ngram_length)))
+        .or_else(|| failures.get(&(language, None)))
+        .copied();
+
+    drop(failures);
+
+    match failure {
+        Some(ModelFailure::Missing) => Err(ErrorKind::NotFound.into()),
+        Some(ModelFailure::Corrupted) => Ok(&contents[..contents.len() / 2]),
+        Some(ModelFailure::SlowIo(delay)) => {
+            thread::sleep(delay);
+            Ok(contents)
+        }
+        None => Ok(contents),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::json::load_json;
+    use crate::language::Language::*;
+    use std::time::Instant;
+
+    #[test]
+    fn assert_missing_model_is_simulated() {
+        FailureInjector::inject(Yoruba, ModelFailure::Missing);
+
+        assert_eq!(
+            load_json(Yoruba, 3).map_err(|error| error.kind()),
+            Err(ErrorKind::NotFound)
+        );
+
+        FailureInjector::inject_for_ngram_length(Yoruba, 3, ModelFailure::SlowIo(Duration::ZERO));
+
+        assert!(load_json(Yoruba, 3).is_ok());
+        assert!(load_json(Yoruba, 2).is_err());
+    }
+
+    #[test]
+    fn assert_corrupted_model_is_simulated() {
+        FailureInjector::inject_for_ngram_length(Zulu, 1, ModelFailure::Corrupted);
+
+        assert!(load_json(Zulu, 1).is_err());
+        assert!(load_json(Zulu, 2).is_ok());
+    }
+
+    #[test]
+    fn assert_slow_io_is_simulated() {
+        FailureInjector::inject(Xhosa, ModelFailure::SlowIo(Duration::from_millis(200)));
+
+        let start = Instant::now();
+
+        assert!(load_json(Xhosa, 1).is_ok());
+        assert!(start.elapsed() >= Duration::from_millis(200));
+    }
+}
@@ -0,0 +1,119 @@
+/*
+ * Copyright © 2020-present Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::language::Language;
+
+/// A single step of the fallback chain of a [LanguageDetector](crate::LanguageDetector).
+///
+/// If the language of some text cannot be reliably detected, the steps of the chain are
+/// tried in the configured order and the first step that applies determines the result.
+/// If no step applies, [None] is returned as usual.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Fallback {
+    /// Returns the most likely language, even if its confidence value is not sufficiently
+    /// distinct from the one of the second most likely language. This step does not apply
+    /// if all confidence values are zero, e.g. for text without any letters.
+    MostLikelyLanguage,
+
+    /// Returns `language` if the two most likely languages with a confidence value greater
+    /// than zero are both contained in `languages`. This is useful for closely related
+    /// languages which can be summarized under a macro-language, such as Bokmal and Nynorsk
+    /// or Bosnian, Croatian and Serbian.
+    MacroLanguage {
+        language: Language,
+        languages: Vec<Language>,
+    },
+
+    /// Returns the given language unconditionally.
+    Language(Language),
+}
+
+impl Fallback {
+    pub(crate) fn resolve(&self, confidence_values: &[(Language, f64)]) -> Option<Language> {
+        let candidates = confidence_values
+            .iter()
+            .filter(|(_, confidence)| *confidence > 0.0)
+            .map(|(language, _)| *language)
+            .collect::<Vec<_>>();
+
+        match self {
+            Fallback::MostLikelyLanguage => candidates.first().copied(),
+            Fallback::MacroLanguage {
+                language,
+                languages,
+            } => {
+                if candidates.len() < 2 {
+                    return None;
+                }
+                let is_macro_language = candidates[..2].iter().all(|it| languages.contains(it));
+
+                if is_macro_language {
+                    Some(*language)
+                } else {
+                    None
+                }
+            }
+            Fallback::Language(language) => Some(*language),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::language::Language::*;
+
+    #[test]
+    fn assert_most_likely_language_fallback_resolves_correctly() {
+        let fallback = Fallback::MostLikelyLanguage;
+
+        assert_eq!(
+            fallback.resolve(&[(Bokmal, 0.5), (Nynorsk, 0.5)]),
+            Some(Bokmal)
+        );
+        assert_eq!(fallback.resolve(&[(Bokmal, 0.0), (Nynorsk, 0.0)]), None);
+        assert_eq!(fallback.resolve(&[]), None);
+    }
+
+    #[test]
+    fn assert_macro_language_fallback_resolves_correctly() {
+        let fallback = Fallback::MacroLanguage {
+            language: Serbian,
+            languages: vec![Bosnian, Croatian, Serbian],
+        };
+
+        assert_eq!(
+            fallback.resolve(&[(Croatian, 0.4), (Bosnian, 0.4), (English, 0.2)]),
+            Some(Serbian)
+        );
+        assert_eq!(
+            fallback.resolve(&[(Croatian, 0.5), (English, 0.5), (Bosnian, 0.0)]),
+            None
+        );
+        assert_eq!(fallback.resolve(&[(Croatian, 1.0)]), None);
+    }
+
+    #[test]
+    fn assert_language_fallback_resolves_correctly() {
+        let fallback = Fallback::Language(English);
+
+        assert_eq!(fallback.resolve(&[]), Some(English));
+        assert_eq!(
+            fallback.resolve(&[(German, 0.5), (French, 0.5)]),
+            Some(English)
+        );
+    }
+}
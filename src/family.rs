@@ -0,0 +1,47 @@
+/*
+ * Copyright © 2020-present Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use serde::{Deserialize, Serialize};
+use strum_macros::EnumIter;
+
+/// This enum specifies the language families the languages supported by *Lingua*
+/// belong to, as returned by [Language::language_family](crate::Language::language_family).
+///
+/// Only the top-level families are distinguished. Languages without any
+/// known relatives and constructed languages have variants of their own.
+#[derive(
+    Clone, Copy, Debug, Serialize, Deserialize, EnumIter, Eq, PartialEq, Hash, Ord, PartialOrd,
+)]
+pub enum LanguageFamily {
+    Afroasiatic,
+    Austroasiatic,
+    Austronesian,
+    /// Languages which have been designed deliberately, such as Esperanto.
+    Constructed,
+    Dravidian,
+    IndoEuropean,
+    Japonic,
+    Kartvelian,
+    Koreanic,
+    KraDai,
+    /// Languages without any known relatives, such as Basque.
+    LanguageIsolate,
+    Mongolic,
+    NigerCongo,
+    SinoTibetan,
+    Turkic,
+    Uralic,
+}
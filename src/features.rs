@@ -0,0 +1,87 @@
+/*
+ * Copyright © 2020-present Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use strum::IntoEnumIterator;
+
+use crate::alphabet::Alphabet;
+use crate::language::Language;
+use crate::ngram::Ngram;
+
+/// Returns the names of the features computed by
+/// [LanguageDetector::compute_feature_vector](crate::LanguageDetector::compute_feature_vector)
+/// for the given languages, which must be sorted.
+pub(crate) fn feature_names(languages: &[Language]) -> Vec<String> {
+    let mut names = vec![];
+
+    for language in languages {
+        let iso_code = language.iso_code_639_3();
+        for ngram_length in 1..=5 {
+            let ngram_name = Ngram::find_ngram_name_by_length(ngram_length);
+            names.push(format!("{iso_code}_{ngram_name}_log_probability"));
+        }
+        names.push(format!("{iso_code}_matched_ngram_count"));
+        names.push(format!("{iso_code}_rule_candidate"));
+    }
+
+    for alphabet in Alphabet::iter() {
+        names.push(format!("script_{}", format!("{alphabet:?}").to_lowercase()));
+    }
+
+    names.push("character_count".to_string());
+    names.push("word_count".to_string());
+
+    names
+}
+
+/// Returns for each alphabet the fraction of the characters of the given words
+/// which belong to this alphabet, in the order of [Alphabet::iter].
+pub(crate) fn compute_script_ratios(words: &[String]) -> Vec<f64> {
+    let text = words.concat();
+    Alphabet::iter()
+        .map(|alphabet| alphabet.match_ratio(&text))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::language::Language::{English, German};
+
+    #[test]
+    fn assert_feature_names_are_computed_correctly() {
+        let names = feature_names(&[English, German]);
+
+        assert_eq!(names.len(), 2 * 7 + Alphabet::iter().count() + 2);
+        assert_eq!(names[0], "eng_unigram_log_probability");
+        assert_eq!(names[4], "eng_fivegram_log_probability");
+        assert_eq!(names[5], "eng_matched_ngram_count");
+        assert_eq!(names[6], "eng_rule_candidate");
+        assert_eq!(names[7], "deu_unigram_log_probability");
+        assert_eq!(names[14], "script_arabic");
+        assert_eq!(names.last().unwrap(), "word_count");
+    }
+
+    #[test]
+    fn assert_script_ratios_are_computed_correctly() {
+        let words = vec!["hello".to_string(), "мир".to_string()];
+        let ratios = compute_script_ratios(&words);
+        let ratio_of = |alphabet| ratios[Alphabet::iter().position(|it| it == alphabet).unwrap()];
+
+        assert_eq!(ratio_of(Alphabet::Latin), 5.0 / 8.0);
+        assert_eq!(ratio_of(Alphabet::Cyrillic), 3.0 / 8.0);
+        assert_eq!(ratio_of(Alphabet::Greek), 0.0);
+    }
+}
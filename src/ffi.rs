@@ -0,0 +1,331 @@
+/*
+ * Copyright © 2020-present Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! The stable C ABI of *Lingua*, declared in `include/lingua.h`.
+//!
+//! All exported symbols carry the major ABI version in their names, e.g. `lingua_v1_`.
+//! This way, plugins linked against different major versions of the library can
+//! coexist in the same process, independent of the platform's support for symbol
+//! versioning. Within a major version, symbols are only ever added, never changed
+//! or removed.
+
+use std::collections::{HashMap, HashSet};
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::str::FromStr;
+
+use once_cell::sync::Lazy;
+
+use crate::builder::LanguageDetectorBuilder;
+use crate::detector::LanguageDetector;
+use crate::isocode::IsoCode639_1;
+use crate::language::Language;
+
+/// The major version of the C ABI. It is incremented on every incompatible change
+/// and is part of the names of all exported symbols.
+pub const LINGUA_ABI_VERSION_MAJOR: u32 = 1;
+
+/// The minor version of the C ABI. It is incremented whenever symbols are added.
+pub const LINGUA_ABI_VERSION_MINOR: u32 = 1;
+
+/// The patch version of the C ABI. It is incremented on compatible bug fixes.
+pub const LINGUA_ABI_VERSION_PATCH: u32 = 0;
+
+static ISO_CODES: Lazy<HashMap<Language, CString>> = Lazy::new(|| {
+    Language::all()
+        .into_iter()
+        .map(|language| {
+            let iso_code = CString::new(language.iso_code_639_1().to_string()).unwrap();
+            (language, iso_code)
+        })
+        .collect()
+});
+
+/// The version of the C ABI implemented by a loaded library.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct LinguaAbiVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+/// Returns the version of the C ABI implemented by this library. Callers which load the
+/// library dynamically should check that the major version equals the one of their header
+/// and that the minor version is not smaller than the one of their header.
+#[no_mangle]
+pub extern "C" fn lingua_v1_abi_version() -> LinguaAbiVersion {
+    LinguaAbiVersion {
+        major: LINGUA_ABI_VERSION_MAJOR,
+        minor: LINGUA_ABI_VERSION_MINOR,
+        patch: LINGUA_ABI_VERSION_PATCH,
+    }
+}
+
+/// Creates a language detector for the languages given as comma-separated ISO 639-1 codes,
+/// or for all spoken languages if `iso_codes` is null. Returns null if the codes are invalid,
+/// fewer than two languages are given or the detector cannot be created.
+/// The detector must be released with [lingua_v1_detector_free].
+///
+/// # Safety
+///
+/// `iso_codes` must be null or point to a valid null-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn lingua_v1_detector_new(
+    iso_codes: *const c_char,
+    low_accuracy_mode: bool,
+) -> *mut LanguageDetector {
+    let languages = if iso_codes.is_null() {
+        Language::all_spoken_ones()
+    } else {
+        match parse_languages(CStr::from_ptr(iso_codes)) {
+            Some(languages) => languages,
+            None => return std::ptr::null_mut(),
+        }
+    };
+
+    if languages.len() < 2 {
+        return std::ptr::null_mut();
+    }
+
+    let languages = languages.into_iter().collect::<Vec<_>>();
+
+    catch_unwind(|| {
+        let mut builder = LanguageDetectorBuilder::from_languages(&languages);
+        if low_accuracy_mode {
+            builder.with_low_accuracy_mode();
+        }
+        builder
+            .try_build()
+            .map_or(std::ptr::null_mut(), |detector| {
+                Box::into_raw(Box::new(detector))
+            })
+    })
+    .unwrap_or(std::ptr::null_mut())
+}
+
+/// Releases a language detector created by [lingua_v1_detector_new].
+///
+/// # Safety
+///
+/// `detector` must be null or a pointer returned by [lingua_v1_detector_new]
+/// which has not been released yet.
+#[no_mangle]
+pub unsafe extern "C" fn lingua_v1_detector_free(detector: *mut LanguageDetector) {
+    if !detector.is_null() {
+        drop(Box::from_raw(detector));
+    }
+}
+
+/// Clears all language models loaded by the given language detector and frees the memory
+/// consumed by them. The detector remains usable and reloads the models on demand.
+///
+/// # Safety
+///
+/// `detector` must be null or a valid pointer returned by [lingua_v1_detector_new].
+#[no_mangle]
+pub unsafe extern "C" fn lingua_v1_detector_unload_language_models(
+    detector: *const LanguageDetector,
+) {
+    if !detector.is_null() {
+        let detector = &*detector;
+        let _ = catch_unwind(AssertUnwindSafe(|| detector.unload_language_models()));
+    }
+}
+
+/// Detects the language of the given UTF-8 encoded text and returns its ISO 639-1 code.
+/// Returns null if the language cannot be reliably detected or the arguments are invalid.
+/// The returned string is statically allocated and must not be freed.
+///
+/// # Safety
+///
+/// `detector` must be null or a valid pointer returned by [lingua_v1_detector_new].
+/// `text` must be null or point to a valid null-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn lingua_v1_detect_language_of(
+    detector: *const LanguageDetector,
+    text: *const c_char,
+) -> *const c_char {
+    if detector.is_null() || text.is_null() {
+        return std::ptr::null();
+    }
+
+    let detector = &*detector;
+    let text = match CStr::from_ptr(text).to_str() {
+        Ok(text) => text,
+        Err(_) => return std::ptr::null(),
+    };
+
+    catch_unwind(AssertUnwindSafe(|| detector.detect_language_of(text)))
+        .ok()
+        .flatten()
+        .and_then(|language| ISO_CODES.get(&language))
+        .map_or(std::ptr::null(), |iso_code| iso_code.as_ptr())
+}
+
+/// Computes the confidence value between 0.0 and 1.0 for the given UTF-8 encoded text
+/// to be written in the language given as ISO 639-1 code. Returns -1.0 if the arguments
+/// are invalid. Returns 0.0 if the language is not supported by the detector.
+///
+/// # Safety
+///
+/// `detector` must be null or a valid pointer returned by [lingua_v1_detector_new].
+/// `text` and `iso_code` must be null or point to valid null-terminated strings.
+#[no_mangle]
+pub unsafe extern "C" fn lingua_v1_compute_language_confidence(
+    detector: *const LanguageDetector,
+    text: *const c_char,
+    iso_code: *const c_char,
+) -> f64 {
+    if detector.is_null() || text.is_null() || iso_code.is_null() {
+        return -1.0;
+    }
+
+    let detector = &*detector;
+    let (text, language) = match (
+        CStr::from_ptr(text).to_str(),
+        parse_language(CStr::from_ptr(iso_code)),
+    ) {
+        (Ok(text), Some(language)) => (text, language),
+        _ => return -1.0,
+    };
+
+    catch_unwind(AssertUnwindSafe(|| {
+        detector.compute_language_confidence(text, language)
+    }))
+    .unwrap_or(-1.0)
+}
+
+fn parse_languages(iso_codes: &CStr) -> Option<HashSet<Language>> {
+    iso_codes
+        .to_str()
+        .ok()?
+        .split(',')
+        .map(|iso_code| IsoCode639_1::from_str(iso_code.trim()).ok())
+        .map(|iso_code| iso_code.map(|it| Language::from_iso_code_639_1(&it)))
+        .collect()
+}
+
+fn parse_language(iso_code: &CStr) -> Option<Language> {
+    let iso_code = IsoCode639_1::from_str(iso_code.to_str().ok()?.trim()).ok()?;
+    Some(Language::from_iso_code_639_1(&iso_code))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use regex::Regex;
+
+    const HEADER: &str = include_str!("../include/lingua.h");
+    const EXPORTED_SYMBOLS: [&str; 6] = [
+        "lingua_v1_abi_version",
+        "lingua_v1_detector_new",
+        "lingua_v1_detector_free",
+        "lingua_v1_detector_unload_language_models",
+        "lingua_v1_detect_language_of",
+        "lingua_v1_compute_language_confidence",
+    ];
+
+    fn header_define(name: &str) -> u32 {
+        Regex::new(&format!("#define {name} (\\d+)"))
+            .unwrap()
+            .captures(HEADER)
+            .unwrap_or_else(|| panic!("{name} is not defined in lingua.h"))[1]
+            .parse()
+            .unwrap()
+    }
+
+    #[test]
+    fn assert_header_version_matches_abi_version() {
+        assert_eq!(
+            header_define("LINGUA_ABI_VERSION_MAJOR"),
+            LINGUA_ABI_VERSION_MAJOR
+        );
+        assert_eq!(
+            header_define("LINGUA_ABI_VERSION_MINOR"),
+            LINGUA_ABI_VERSION_MINOR
+        );
+        assert_eq!(
+            header_define("LINGUA_ABI_VERSION_PATCH"),
+            LINGUA_ABI_VERSION_PATCH
+        );
+    }
+
+    #[test]
+    fn assert_exported_symbols_are_versioned_and_declared() {
+        let prefix = format!("lingua_v{LINGUA_ABI_VERSION_MAJOR}_");
+        let declared_symbols = Regex::new("\\b(lingua_v\\d+_\\w+)\\(")
+            .unwrap()
+            .captures_iter(HEADER)
+            .map(|captures| captures[1].to_string())
+            .collect::<HashSet<_>>();
+
+        assert_eq!(declared_symbols.len(), EXPORTED_SYMBOLS.len());
+
+        for symbol in EXPORTED_SYMBOLS {
+            assert!(symbol.starts_with(&prefix), "{symbol} is not versioned");
+            assert!(
+                declared_symbols.contains(symbol),
+                "{symbol} is not declared in lingua.h"
+            );
+        }
+    }
+
+    #[test]
+    fn assert_language_is_detected_via_c_abi() {
+        let iso_codes = CString::new("en, de").unwrap();
+        let text = CString::new("languages are awesome").unwrap();
+        let english = CString::new("en").unwrap();
+
+        unsafe {
+            let detector = lingua_v1_detector_new(iso_codes.as_ptr(), false);
+            assert!(!detector.is_null());
+
+            let iso_code = lingua_v1_detect_language_of(detector, text.as_ptr());
+            assert_eq!(CStr::from_ptr(iso_code).to_str(), Ok("en"));
+
+            let confidence =
+                lingua_v1_compute_language_confidence(detector, text.as_ptr(), english.as_ptr());
+            assert!(confidence > 0.5);
+
+            lingua_v1_detector_unload_language_models(detector);
+            let iso_code = lingua_v1_detect_language_of(detector, text.as_ptr());
+            assert_eq!(CStr::from_ptr(iso_code).to_str(), Ok("en"));
+
+            lingua_v1_detector_free(detector);
+        }
+    }
+
+    #[test]
+    fn assert_invalid_arguments_are_rejected() {
+        let invalid_iso_codes = CString::new("en,xx").unwrap();
+        let single_iso_code = CString::new("en").unwrap();
+
+        unsafe {
+            assert!(lingua_v1_detector_new(invalid_iso_codes.as_ptr(), false).is_null());
+            assert!(lingua_v1_detector_new(single_iso_code.as_ptr(), false).is_null());
+            assert!(lingua_v1_detect_language_of(std::ptr::null(), std::ptr::null()).is_null());
+            assert_eq!(
+                lingua_v1_compute_language_confidence(
+                    std::ptr::null(),
+                    std::ptr::null(),
+                    std::ptr::null()
+                ),
+                -1.0
+            );
+        }
+    }
+}
@@ -22,6 +22,9 @@ use itertools::Itertools;
 use serde::de::{Error, Visitor};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
+/// The denominator of approximated fractions, small enough to fit into `u32`.
+const APPROXIMATION_DENOMINATOR: u32 = 1_000_000_000;
+
 #[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub(crate) struct Fraction {
     numerator: u32,
@@ -37,6 +40,21 @@ impl Fraction {
         }
     }
 
+    /// Approximates the given probability as a fraction with a fixed denominator,
+    /// as required for probabilities which do not stem from counted frequencies.
+    pub(crate) fn approximate(probability: f64) -> Self {
+        let numerator = (probability * APPROXIMATION_DENOMINATOR as f64).round() as u32;
+        Self::new(numerator, APPROXIMATION_DENOMINATOR)
+    }
+
+    pub(crate) fn numerator(&self) -> u32 {
+        self.numerator
+    }
+
+    pub(crate) fn denominator(&self) -> u32 {
+        self.denominator
+    }
+
     pub(crate) fn to_f64(self) -> f64 {
         self.numerator as f64 / self.denominator as f64
     }
@@ -92,6 +110,16 @@ mod tests {
         assert_eq!(Fraction::new(12, 144), Fraction::new(1, 12));
     }
 
+    #[test]
+    fn test_fraction_approximation() {
+        assert_eq!(Fraction::approximate(0.25), Fraction::new(1, 4));
+        assert_eq!(
+            Fraction::approximate(1.0 / 3.0),
+            Fraction::new(333333333, 1000000000)
+        );
+        assert_eq!(Fraction::approximate(1e-12).to_f64(), 0.0);
+    }
+
     #[test]
     fn test_fraction_serializer() {
         let fraction = Fraction::new(3, 5);
@@ -16,7 +16,9 @@
 
 use std::io::{Cursor, ErrorKind, Read};
 
+use ahash::AHashMap;
 use brotli::Decompressor;
+use compact_str::CompactString;
 use include_dir::Dir;
 
 #[cfg(feature = "afrikaans")]
@@ -170,6 +172,10 @@ use lingua_yoruba_language_model::YORUBA_MODELS_DIRECTORY;
 #[cfg(feature = "zulu")]
 use lingua_zulu_language_model::ZULU_MODELS_DIRECTORY;
 
+use crate::binary::load_binary_model;
+#[cfg(feature = "failure-injection")]
+use crate::failure::apply_injected_failure;
+use crate::model::TrainingDataLanguageModel;
 use crate::ngram::Ngram;
 use crate::Language;
 
@@ -178,13 +184,63 @@ pub(crate) fn load_json(language: Language, ngram_length: usize) -> std::io::Res
     let file_path = format!("{ngram_name}s.json.br");
     let directory = get_language_models_directory(language);
     let compressed_file = directory.get_file(file_path).ok_or(ErrorKind::NotFound)?;
-    let compressed_file_reader = Cursor::new(compressed_file.contents());
+    let contents = compressed_file.contents();
+    #[cfg(feature = "failure-injection")]
+    let contents = apply_injected_failure(language, ngram_length, contents)?;
+    decompress_json(contents)
+}
+
+/// Loads the ngram probabilities of the bundled language model of the given language
+/// and ngram length, preferring a binary model file over a JSON one.
+pub(crate) fn load_language_model(
+    language: Language,
+    ngram_length: usize,
+) -> std::io::Result<AHashMap<CompactString, f64>> {
+    let ngram_name = Ngram::find_ngram_name_by_length(ngram_length);
+    let binary_file_path = format!("{ngram_name}s.bin.br");
+    let directory = get_language_models_directory(language);
+
+    match directory.get_file(binary_file_path) {
+        Some(compressed_file) => {
+            let contents = compressed_file.contents();
+            #[cfg(feature = "failure-injection")]
+            let contents = apply_injected_failure(language, ngram_length, contents)?;
+            load_binary_model(&decompress(contents)?)
+        }
+        None => load_json(language, ngram_length)
+            .map(|json| TrainingDataLanguageModel::from_json(&json)),
+    }
+}
+
+/// Decompresses the contents of a Brotli-compressed binary language model file.
+pub(crate) fn decompress(contents: &[u8]) -> std::io::Result<Vec<u8>> {
+    let compressed_file_reader = Cursor::new(contents);
+    let mut uncompressed_file = Decompressor::new(compressed_file_reader, 4096);
+    let mut uncompressed_file_content = vec![];
+    uncompressed_file.read_to_end(&mut uncompressed_file_content)?;
+    Ok(uncompressed_file_content)
+}
+
+/// Decompresses the contents of a Brotli-compressed language model file.
+pub(crate) fn decompress_json(contents: &[u8]) -> std::io::Result<String> {
+    let compressed_file_reader = Cursor::new(contents);
     let mut uncompressed_file = Decompressor::new(compressed_file_reader, 4096);
     let mut uncompressed_file_content = String::new();
     uncompressed_file.read_to_string(&mut uncompressed_file_content)?;
     Ok(uncompressed_file_content)
 }
 
+/// Returns the size of the bundled model file of the given language and ngram length
+/// which is loaded by [load_language_model], i.e. preferably the binary one.
+pub(crate) fn compressed_model_size(language: Language, ngram_length: usize) -> Option<usize> {
+    let ngram_name = Ngram::find_ngram_name_by_length(ngram_length);
+    let directory = get_language_models_directory(language);
+    directory
+        .get_file(format!("{ngram_name}s.bin.br"))
+        .or_else(|| directory.get_file(format!("{ngram_name}s.json.br")))
+        .map(|compressed_file| compressed_file.contents().len())
+}
+
 fn get_language_models_directory(language: Language) -> Dir<'static> {
     match language {
         #[cfg(feature = "afrikaans")]
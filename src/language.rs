@@ -16,14 +16,20 @@
 
 use std::collections::HashSet;
 use std::fmt::{Debug, Display, Formatter, Result};
+use std::ops::AddAssign;
 use std::str::FromStr;
 
 use serde::{Deserialize, Serialize};
-use strum::IntoEnumIterator;
-use strum_macros::{EnumIter, EnumString};
-
-use crate::alphabet::Alphabet;
+use strum::ParseError;
+use strum::{EnumCount, IntoEnumIterator};
+use strum_macros::{EnumCount, EnumIter};
+
+use crate::accuracy::{expected_accuracy, TextLength};
+use crate::alphabet::{Alphabet, WritingDirection};
+use crate::constant::CHARS_TO_LANGUAGES_MAPPING;
+use crate::family::LanguageFamily;
 use crate::isocode::{IsoCode639_1, IsoCode639_3};
+use crate::region::Region;
 
 /// This enum specifies the so far 75 supported languages which can be detected by *Lingua*.
 #[derive(
@@ -32,8 +38,8 @@ use crate::isocode::{IsoCode639_1, IsoCode639_3};
     Debug,
     Serialize,
     Deserialize,
+    EnumCount,
     EnumIter,
-    EnumString,
     Eq,
     PartialEq,
     Hash,
@@ -41,7 +47,6 @@ use crate::isocode::{IsoCode639_1, IsoCode639_3};
     PartialOrd,
 )]
 #[serde(rename_all(serialize = "UPPERCASE", deserialize = "UPPERCASE"))]
-#[strum(ascii_case_insensitive)]
 pub enum Language {
     #[cfg(feature = "afrikaans")]
     Afrikaans,
@@ -276,6 +281,36 @@ impl Display for Language {
     }
 }
 
+/// Parses a language from its English name or from its ISO 639-1 or ISO 639-3 code,
+/// ignoring ASCII case.
+///
+/// ```
+/// use std::str::FromStr;
+/// use lingua::Language;
+///
+/// assert_eq!(Language::from_str("german"), Ok(Language::German));
+/// assert_eq!(Language::from_str("DE"), Ok(Language::German));
+/// assert_eq!(Language::from_str("deu"), Ok(Language::German));
+/// ```
+impl FromStr for Language {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        if let Some(language) =
+            Language::iter().find(|it| format!("{it:?}").eq_ignore_ascii_case(s))
+        {
+            return Ok(language);
+        }
+        if let Ok(iso_code) = IsoCode639_1::from_str(s) {
+            return Ok(Language::from_iso_code_639_1(&iso_code));
+        }
+        if let Ok(iso_code) = IsoCode639_3::from_str(s) {
+            return Ok(Language::from_iso_code_639_3(&iso_code));
+        }
+        Err(ParseError::VariantNotFound)
+    }
+}
+
 impl Language {
     pub fn all() -> HashSet<Language> {
         Language::iter().collect()
@@ -293,42 +328,110 @@ impl Language {
             .collect()
     }
 
-    pub fn all_with_arabic_script() -> HashSet<Language> {
+    /// Returns all languages written in the given alphabet.
+    ///
+    /// ```
+    /// use lingua::{Alphabet, Language};
+    ///
+    /// assert_eq!(Language::all_with_script(Alphabet::Greek), [Language::Greek].into());
+    /// ```
+    pub fn all_with_script(alphabet: Alphabet) -> HashSet<Language> {
         Language::iter()
-            .filter(|it| it.alphabets().contains(&Alphabet::Arabic))
+            .filter(|it| it.alphabets().contains(&alphabet))
             .collect()
     }
 
-    pub fn all_with_cyrillic_script() -> HashSet<Language> {
+    /// Returns all languages spoken in the given region.
+    ///
+    /// ```
+    /// use lingua::{Language, Region};
+    /// use lingua::Language::{Armenian, Azerbaijani, Georgian};
+    ///
+    /// assert_eq!(
+    ///     Language::all_spoken_in(Region::Caucasus),
+    ///     [Armenian, Azerbaijani, Georgian].into()
+    /// );
+    /// ```
+    pub fn all_spoken_in(region: Region) -> HashSet<Language> {
         Language::iter()
-            .filter(|it| it.alphabets().contains(&Alphabet::Cyrillic))
+            .filter(|it| it.spoken_in().contains(&region))
             .collect()
     }
 
+    pub fn all_with_arabic_script() -> HashSet<Language> {
+        Language::all_with_script(Alphabet::Arabic)
+    }
+
+    pub fn all_with_cyrillic_script() -> HashSet<Language> {
+        Language::all_with_script(Alphabet::Cyrillic)
+    }
+
     pub fn all_with_devanagari_script() -> HashSet<Language> {
-        Language::iter()
-            .filter(|it| it.alphabets().contains(&Alphabet::Devanagari))
-            .collect()
+        Language::all_with_script(Alphabet::Devanagari)
     }
 
     pub fn all_with_latin_script() -> HashSet<Language> {
-        Language::iter()
-            .filter(|it| it.alphabets().contains(&Alphabet::Latin))
-            .collect()
+        Language::all_with_script(Alphabet::Latin)
     }
 
+    /// Returns the language identified by the given ISO 639-1 code.
+    ///
+    /// ```
+    /// use lingua::{IsoCode639_1, Language};
+    ///
+    /// assert_eq!(Language::from_iso_code_639_1(&IsoCode639_1::DE), Language::German);
+    /// ```
     pub fn from_iso_code_639_1(iso_code: &IsoCode639_1) -> Language {
         Language::iter()
             .find(|it| &it.iso_code_639_1() == iso_code)
             .unwrap()
     }
 
+    /// Returns the language identified by the given ISO 639-3 code.
+    ///
+    /// ```
+    /// use lingua::{IsoCode639_3, Language};
+    ///
+    /// assert_eq!(Language::from_iso_code_639_3(&IsoCode639_3::DEU), Language::German);
+    /// ```
     pub fn from_iso_code_639_3(iso_code: &IsoCode639_3) -> Language {
         Language::iter()
             .find(|it| &it.iso_code_639_3() == iso_code)
             .unwrap()
     }
 
+    /// Returns the languages whose curated character tables contain the given character.
+    ///
+    /// These are the tables used by the rule-based filter engine: the characters unique to
+    /// a single language as returned by [Language::unique_characters] and the characters
+    /// shared by a small group of languages only. For characters not contained in any table,
+    /// such as the basic Latin letters, an empty set is returned.
+    ///
+    /// ```
+    /// use lingua::Language;
+    /// use lingua::Language::{German, Lithuanian, Polish};
+    ///
+    /// assert_eq!(Language::languages_with_character('ß'), [German].into());
+    /// assert_eq!(Language::languages_with_character('ą'), [Lithuanian, Polish].into());
+    /// assert!(Language::languages_with_character('a').is_empty());
+    /// ```
+    pub fn languages_with_character(ch: char) -> HashSet<Language> {
+        let mut languages = Language::iter()
+            .filter(|it| {
+                it.unique_characters()
+                    .is_some_and(|characters| characters.contains(ch))
+            })
+            .collect::<HashSet<_>>();
+
+        for (characters, langs) in CHARS_TO_LANGUAGES_MAPPING.iter() {
+            if characters.contains(ch) {
+                languages.extend(langs);
+            }
+        }
+
+        languages
+    }
+
     pub fn iso_code_639_1(&self) -> IsoCode639_1 {
         match self {
             #[cfg(feature = "afrikaans")]
@@ -787,7 +890,7 @@ impl Language {
         }
     }
 
-    pub(crate) fn alphabets(&self) -> HashSet<Alphabet> {
+    pub fn alphabets(&self) -> HashSet<Alphabet> {
         match self {
             #[cfg(feature = "afrikaans")]
             Language::Afrikaans => hashset!(Alphabet::Latin),
@@ -1016,102 +1119,1063 @@ impl Language {
         }
     }
 
-    pub(crate) fn unique_characters(&self) -> Option<&str> {
+    /// Returns the regions in which this language is spoken by a considerable part of
+    /// the population, in the order of [Region]. The list is empty for languages without
+    /// native speakers, such as Esperanto and Latin.
+    ///
+    /// ```
+    /// use lingua::Language::Georgian;
+    /// use lingua::Region;
+    ///
+    /// assert_eq!(Georgian.spoken_in(), &[Region::Caucasus]);
+    /// ```
+    pub fn spoken_in(&self) -> &'static [Region] {
         match self {
+            #[cfg(feature = "afrikaans")]
+            Language::Afrikaans => &[Region::SubSaharanAfrica],
+
+            #[cfg(feature = "albanian")]
+            Language::Albanian => &[Region::SouthernEurope],
+
+            #[cfg(feature = "arabic")]
+            Language::Arabic => &[Region::MiddleEast, Region::NorthAfrica],
+
+            #[cfg(feature = "armenian")]
+            Language::Armenian => &[Region::Caucasus],
+
             #[cfg(feature = "azerbaijani")]
-            Language::Azerbaijani => Some("Əə"),
+            Language::Azerbaijani => &[Region::Caucasus, Region::MiddleEast],
+
+            #[cfg(feature = "basque")]
+            Language::Basque => &[Region::SouthernEurope, Region::WesternEurope],
+
+            #[cfg(feature = "belarusian")]
+            Language::Belarusian => &[Region::EasternEurope],
+
+            #[cfg(feature = "bengali")]
+            Language::Bengali => &[Region::SouthAsia],
+
+            #[cfg(feature = "bokmal")]
+            Language::Bokmal => &[Region::NorthernEurope],
+
+            #[cfg(feature = "bosnian")]
+            Language::Bosnian => &[Region::SouthernEurope],
+
+            #[cfg(feature = "bulgarian")]
+            Language::Bulgarian => &[Region::EasternEurope],
 
             #[cfg(feature = "catalan")]
-            Language::Catalan => Some("Ïï"),
+            Language::Catalan => &[Region::SouthernEurope],
+
+            #[cfg(feature = "chinese")]
+            Language::Chinese => &[Region::EastAsia, Region::SoutheastAsia],
+
+            #[cfg(feature = "croatian")]
+            Language::Croatian => &[Region::SouthernEurope],
 
             #[cfg(feature = "czech")]
-            Language::Czech => Some("ĚěŘřŮů"),
+            Language::Czech => &[Region::EasternEurope],
+
+            #[cfg(feature = "danish")]
+            Language::Danish => &[Region::NorthernEurope],
+
+            #[cfg(feature = "dutch")]
+            Language::Dutch => &[Region::LatinAmerica, Region::WesternEurope],
+
+            #[cfg(feature = "english")]
+            Language::English => &[
+                Region::NorthAmerica,
+                Region::NorthernEurope,
+                Region::Oceania,
+                Region::SouthAsia,
+                Region::SoutheastAsia,
+                Region::SubSaharanAfrica,
+            ],
 
             #[cfg(feature = "esperanto")]
-            Language::Esperanto => Some("ĈĉĜĝĤĥĴĵŜŝŬŭ"),
+            Language::Esperanto => &[],
+
+            #[cfg(feature = "estonian")]
+            Language::Estonian => &[Region::NorthernEurope],
+
+            #[cfg(feature = "finnish")]
+            Language::Finnish => &[Region::NorthernEurope],
+
+            #[cfg(feature = "french")]
+            Language::French => &[
+                Region::NorthAmerica,
+                Region::NorthAfrica,
+                Region::SubSaharanAfrica,
+                Region::WesternEurope,
+            ],
+
+            #[cfg(feature = "ganda")]
+            Language::Ganda => &[Region::SubSaharanAfrica],
+
+            #[cfg(feature = "georgian")]
+            Language::Georgian => &[Region::Caucasus],
 
             #[cfg(feature = "german")]
-            Language::German => Some("ß"),
+            Language::German => &[Region::WesternEurope],
+
+            #[cfg(feature = "greek")]
+            Language::Greek => &[Region::SouthernEurope],
+
+            #[cfg(feature = "gujarati")]
+            Language::Gujarati => &[Region::SouthAsia],
+
+            #[cfg(feature = "hebrew")]
+            Language::Hebrew => &[Region::MiddleEast],
+
+            #[cfg(feature = "hindi")]
+            Language::Hindi => &[Region::SouthAsia],
 
             #[cfg(feature = "hungarian")]
-            Language::Hungarian => Some("ŐőŰű"),
+            Language::Hungarian => &[Region::EasternEurope],
+
+            #[cfg(feature = "icelandic")]
+            Language::Icelandic => &[Region::NorthernEurope],
+
+            #[cfg(feature = "indonesian")]
+            Language::Indonesian => &[Region::SoutheastAsia],
+
+            #[cfg(feature = "irish")]
+            Language::Irish => &[Region::NorthernEurope],
+
+            #[cfg(feature = "italian")]
+            Language::Italian => &[Region::SouthernEurope, Region::WesternEurope],
+
+            #[cfg(feature = "japanese")]
+            Language::Japanese => &[Region::EastAsia],
 
             #[cfg(feature = "kazakh")]
-            Language::Kazakh => Some("ӘәҒғҚқҢңҰұ"),
+            Language::Kazakh => &[Region::CentralAsia],
+
+            #[cfg(feature = "korean")]
+            Language::Korean => &[Region::EastAsia],
+
+            #[cfg(feature = "latin")]
+            Language::Latin => &[],
 
             #[cfg(feature = "latvian")]
-            Language::Latvian => Some("ĢģĶķĻļŅņ"),
+            Language::Latvian => &[Region::NorthernEurope],
 
             #[cfg(feature = "lithuanian")]
-            Language::Lithuanian => Some("ĖėĮįŲų"),
+            Language::Lithuanian => &[Region::NorthernEurope],
 
             #[cfg(feature = "macedonian")]
-            Language::Macedonian => Some("ЃѓЅѕЌќЏџ"),
+            Language::Macedonian => &[Region::SouthernEurope],
+
+            #[cfg(feature = "malay")]
+            Language::Malay => &[Region::SoutheastAsia],
+
+            #[cfg(feature = "maori")]
+            Language::Maori => &[Region::Oceania],
 
             #[cfg(feature = "marathi")]
-            Language::Marathi => Some("ळ"),
+            Language::Marathi => &[Region::SouthAsia],
 
             #[cfg(feature = "mongolian")]
-            Language::Mongolian => Some("ӨөҮү"),
+            Language::Mongolian => &[Region::EastAsia],
+
+            #[cfg(feature = "nynorsk")]
+            Language::Nynorsk => &[Region::NorthernEurope],
+
+            #[cfg(feature = "persian")]
+            Language::Persian => &[Region::CentralAsia, Region::MiddleEast, Region::SouthAsia],
 
             #[cfg(feature = "polish")]
-            Language::Polish => Some("ŁłŃńŚśŹź"),
+            Language::Polish => &[Region::EasternEurope],
+
+            #[cfg(feature = "portuguese")]
+            Language::Portuguese => &[
+                Region::LatinAmerica,
+                Region::SouthernEurope,
+                Region::SubSaharanAfrica,
+            ],
+
+            #[cfg(feature = "punjabi")]
+            Language::Punjabi => &[Region::SouthAsia],
 
             #[cfg(feature = "romanian")]
-            Language::Romanian => Some("Țţ"),
+            Language::Romanian => &[Region::EasternEurope],
+
+            #[cfg(feature = "russian")]
+            Language::Russian => &[Region::CentralAsia, Region::EasternEurope],
 
             #[cfg(feature = "serbian")]
-            Language::Serbian => Some("ЂђЋћ"),
+            Language::Serbian => &[Region::SouthernEurope],
+
+            #[cfg(feature = "shona")]
+            Language::Shona => &[Region::SubSaharanAfrica],
 
             #[cfg(feature = "slovak")]
-            Language::Slovak => Some("ĹĺĽľŔŕ"),
+            Language::Slovak => &[Region::EasternEurope],
+
+            #[cfg(feature = "slovene")]
+            Language::Slovene => &[Region::SouthernEurope],
+
+            #[cfg(feature = "somali")]
+            Language::Somali => &[Region::SubSaharanAfrica],
+
+            #[cfg(feature = "sotho")]
+            Language::Sotho => &[Region::SubSaharanAfrica],
 
             #[cfg(feature = "spanish")]
-            Language::Spanish => Some("¿¡"),
+            Language::Spanish => &[
+                Region::LatinAmerica,
+                Region::NorthAmerica,
+                Region::SouthernEurope,
+            ],
+
+            #[cfg(feature = "swahili")]
+            Language::Swahili => &[Region::SubSaharanAfrica],
+
+            #[cfg(feature = "swedish")]
+            Language::Swedish => &[Region::NorthernEurope],
+
+            #[cfg(feature = "tagalog")]
+            Language::Tagalog => &[Region::SoutheastAsia],
+
+            #[cfg(feature = "tamil")]
+            Language::Tamil => &[Region::SouthAsia, Region::SoutheastAsia],
+
+            #[cfg(feature = "telugu")]
+            Language::Telugu => &[Region::SouthAsia],
+
+            #[cfg(feature = "thai")]
+            Language::Thai => &[Region::SoutheastAsia],
+
+            #[cfg(feature = "tsonga")]
+            Language::Tsonga => &[Region::SubSaharanAfrica],
+
+            #[cfg(feature = "tswana")]
+            Language::Tswana => &[Region::SubSaharanAfrica],
+
+            #[cfg(feature = "turkish")]
+            Language::Turkish => &[Region::MiddleEast],
 
             #[cfg(feature = "ukrainian")]
-            Language::Ukrainian => Some("ҐґЄєЇї"),
+            Language::Ukrainian => &[Region::EasternEurope],
+
+            #[cfg(feature = "urdu")]
+            Language::Urdu => &[Region::SouthAsia],
 
             #[cfg(feature = "vietnamese")]
-            Language::Vietnamese => Some("ẰằẦầẲẳẨẩẴẵẪẫẮắẤấẠạẶặẬậỀềẺẻỂểẼẽỄễẾếỆệỈỉĨĩỊịƠơỒồỜờỎỏỔổỞởỖỗỠỡỐốỚớỘộỢợƯưỪừỦủỬửŨũỮữỨứỤụỰựỲỳỶỷỸỹỴỵ"),
+            Language::Vietnamese => &[Region::SoutheastAsia],
+
+            #[cfg(feature = "welsh")]
+            Language::Welsh => &[Region::NorthernEurope],
+
+            #[cfg(feature = "xhosa")]
+            Language::Xhosa => &[Region::SubSaharanAfrica],
 
             #[cfg(feature = "yoruba")]
-            Language::Yoruba => Some("Ṣṣ"),
+            Language::Yoruba => &[Region::SubSaharanAfrica],
 
-            _ => None,
+            #[cfg(feature = "zulu")]
+            Language::Zulu => &[Region::SubSaharanAfrica],
         }
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use std::str::FromStr;
+    /// Returns the name of this language in the language itself, i.e. its endonym.
+    ///
+    /// ```
+    /// use lingua::Language::{German, Russian};
+    ///
+    /// assert_eq!(German.native_name(), "Deutsch");
+    /// assert_eq!(Russian.native_name(), "Русский");
+    /// ```
+    pub fn native_name(&self) -> &'static str {
+        match self {
+            #[cfg(feature = "afrikaans")]
+            Language::Afrikaans => "Afrikaans",
 
-    use crate::language::Language::*;
+            #[cfg(feature = "albanian")]
+            Language::Albanian => "Shqip",
 
-    use super::*;
+            #[cfg(feature = "arabic")]
+            Language::Arabic => "العربية",
 
-    #[test]
-    fn assert_language_string_representation_is_correct() {
-        assert_eq!(English.to_string(), "English");
-    }
+            #[cfg(feature = "armenian")]
+            Language::Armenian => "Հայերեն",
 
-    #[test]
-    fn test_language_serializer() {
-        let serialized = serde_json::to_string(&English).unwrap();
-        assert_eq!(serialized, "\"ENGLISH\"");
-    }
+            #[cfg(feature = "azerbaijani")]
+            Language::Azerbaijani => "Azərbaycan dili",
 
-    #[test]
-    fn test_language_deserializer() {
-        let deserialized = serde_json::from_str::<Language>("\"ENGLISH\"").unwrap();
-        assert_eq!(deserialized, English);
-    }
+            #[cfg(feature = "basque")]
+            Language::Basque => "Euskara",
 
-    #[test]
-    fn test_from_str() {
-        let language = Language::from_str("english").unwrap();
-        assert_eq!(language, English);
+            #[cfg(feature = "belarusian")]
+            Language::Belarusian => "Беларуская",
+
+            #[cfg(feature = "bengali")]
+            Language::Bengali => "বাংলা",
+
+            #[cfg(feature = "bokmal")]
+            Language::Bokmal => "Norsk bokmål",
+
+            #[cfg(feature = "bosnian")]
+            Language::Bosnian => "Bosanski",
+
+            #[cfg(feature = "bulgarian")]
+            Language::Bulgarian => "Български",
+
+            #[cfg(feature = "catalan")]
+            Language::Catalan => "Català",
+
+            #[cfg(feature = "chinese")]
+            Language::Chinese => "中文",
+
+            #[cfg(feature = "croatian")]
+            Language::Croatian => "Hrvatski",
+
+            #[cfg(feature = "czech")]
+            Language::Czech => "Čeština",
+
+            #[cfg(feature = "danish")]
+            Language::Danish => "Dansk",
+
+            #[cfg(feature = "dutch")]
+            Language::Dutch => "Nederlands",
+
+            #[cfg(feature = "english")]
+            Language::English => "English",
+
+            #[cfg(feature = "esperanto")]
+            Language::Esperanto => "Esperanto",
+
+            #[cfg(feature = "estonian")]
+            Language::Estonian => "Eesti",
+
+            #[cfg(feature = "finnish")]
+            Language::Finnish => "Suomi",
+
+            #[cfg(feature = "french")]
+            Language::French => "Français",
+
+            #[cfg(feature = "ganda")]
+            Language::Ganda => "Luganda",
+
+            #[cfg(feature = "georgian")]
+            Language::Georgian => "ქართული",
+
+            #[cfg(feature = "german")]
+            Language::German => "Deutsch",
+
+            #[cfg(feature = "greek")]
+            Language::Greek => "Ελληνικά",
+
+            #[cfg(feature = "gujarati")]
+            Language::Gujarati => "ગુજરાતી",
+
+            #[cfg(feature = "hebrew")]
+            Language::Hebrew => "עברית",
+
+            #[cfg(feature = "hindi")]
+            Language::Hindi => "हिन्दी",
+
+            #[cfg(feature = "hungarian")]
+            Language::Hungarian => "Magyar",
+
+            #[cfg(feature = "icelandic")]
+            Language::Icelandic => "Íslenska",
+
+            #[cfg(feature = "indonesian")]
+            Language::Indonesian => "Bahasa Indonesia",
+
+            #[cfg(feature = "irish")]
+            Language::Irish => "Gaeilge",
+
+            #[cfg(feature = "italian")]
+            Language::Italian => "Italiano",
+
+            #[cfg(feature = "japanese")]
+            Language::Japanese => "日本語",
+
+            #[cfg(feature = "kazakh")]
+            Language::Kazakh => "Қазақ тілі",
+
+            #[cfg(feature = "korean")]
+            Language::Korean => "한국어",
+
+            #[cfg(feature = "latin")]
+            Language::Latin => "Latina",
+
+            #[cfg(feature = "latvian")]
+            Language::Latvian => "Latviešu",
+
+            #[cfg(feature = "lithuanian")]
+            Language::Lithuanian => "Lietuvių",
+
+            #[cfg(feature = "macedonian")]
+            Language::Macedonian => "Македонски",
+
+            #[cfg(feature = "malay")]
+            Language::Malay => "Bahasa Melayu",
+
+            #[cfg(feature = "maori")]
+            Language::Maori => "Te reo Māori",
+
+            #[cfg(feature = "marathi")]
+            Language::Marathi => "मराठी",
+
+            #[cfg(feature = "mongolian")]
+            Language::Mongolian => "Монгол",
+
+            #[cfg(feature = "nynorsk")]
+            Language::Nynorsk => "Norsk nynorsk",
+
+            #[cfg(feature = "persian")]
+            Language::Persian => "فارسی",
+
+            #[cfg(feature = "polish")]
+            Language::Polish => "Polski",
+
+            #[cfg(feature = "portuguese")]
+            Language::Portuguese => "Português",
+
+            #[cfg(feature = "punjabi")]
+            Language::Punjabi => "ਪੰਜਾਬੀ",
+
+            #[cfg(feature = "romanian")]
+            Language::Romanian => "Română",
+
+            #[cfg(feature = "russian")]
+            Language::Russian => "Русский",
+
+            #[cfg(feature = "serbian")]
+            Language::Serbian => "Српски",
+
+            #[cfg(feature = "shona")]
+            Language::Shona => "chiShona",
+
+            #[cfg(feature = "slovak")]
+            Language::Slovak => "Slovenčina",
+
+            #[cfg(feature = "slovene")]
+            Language::Slovene => "Slovenščina",
+
+            #[cfg(feature = "somali")]
+            Language::Somali => "Soomaali",
+
+            #[cfg(feature = "sotho")]
+            Language::Sotho => "Sesotho",
+
+            #[cfg(feature = "spanish")]
+            Language::Spanish => "Español",
+
+            #[cfg(feature = "swahili")]
+            Language::Swahili => "Kiswahili",
+
+            #[cfg(feature = "swedish")]
+            Language::Swedish => "Svenska",
+
+            #[cfg(feature = "tagalog")]
+            Language::Tagalog => "Tagalog",
+
+            #[cfg(feature = "tamil")]
+            Language::Tamil => "தமிழ்",
+
+            #[cfg(feature = "telugu")]
+            Language::Telugu => "తెలుగు",
+
+            #[cfg(feature = "thai")]
+            Language::Thai => "ไทย",
+
+            #[cfg(feature = "tsonga")]
+            Language::Tsonga => "Xitsonga",
+
+            #[cfg(feature = "tswana")]
+            Language::Tswana => "Setswana",
+
+            #[cfg(feature = "turkish")]
+            Language::Turkish => "Türkçe",
+
+            #[cfg(feature = "ukrainian")]
+            Language::Ukrainian => "Українська",
+
+            #[cfg(feature = "urdu")]
+            Language::Urdu => "اردو",
+
+            #[cfg(feature = "vietnamese")]
+            Language::Vietnamese => "Tiếng Việt",
+
+            #[cfg(feature = "welsh")]
+            Language::Welsh => "Cymraeg",
+
+            #[cfg(feature = "xhosa")]
+            Language::Xhosa => "isiXhosa",
+
+            #[cfg(feature = "yoruba")]
+            Language::Yoruba => "Yorùbá",
+
+            #[cfg(feature = "zulu")]
+            Language::Zulu => "isiZulu",
+        }
+    }
+
+    /// Returns the language family this language belongs to.
+    ///
+    /// ```
+    /// use lingua::Language::{Finnish, Hungarian};
+    /// use lingua::LanguageFamily;
+    ///
+    /// assert_eq!(Finnish.language_family(), LanguageFamily::Uralic);
+    /// assert_eq!(Finnish.language_family(), Hungarian.language_family());
+    /// ```
+    pub fn language_family(&self) -> LanguageFamily {
+        match self {
+            #[cfg(feature = "afrikaans")]
+            Language::Afrikaans => LanguageFamily::IndoEuropean,
+
+            #[cfg(feature = "albanian")]
+            Language::Albanian => LanguageFamily::IndoEuropean,
+
+            #[cfg(feature = "arabic")]
+            Language::Arabic => LanguageFamily::Afroasiatic,
+
+            #[cfg(feature = "armenian")]
+            Language::Armenian => LanguageFamily::IndoEuropean,
+
+            #[cfg(feature = "azerbaijani")]
+            Language::Azerbaijani => LanguageFamily::Turkic,
+
+            #[cfg(feature = "basque")]
+            Language::Basque => LanguageFamily::LanguageIsolate,
+
+            #[cfg(feature = "belarusian")]
+            Language::Belarusian => LanguageFamily::IndoEuropean,
+
+            #[cfg(feature = "bengali")]
+            Language::Bengali => LanguageFamily::IndoEuropean,
+
+            #[cfg(feature = "bokmal")]
+            Language::Bokmal => LanguageFamily::IndoEuropean,
+
+            #[cfg(feature = "bosnian")]
+            Language::Bosnian => LanguageFamily::IndoEuropean,
+
+            #[cfg(feature = "bulgarian")]
+            Language::Bulgarian => LanguageFamily::IndoEuropean,
+
+            #[cfg(feature = "catalan")]
+            Language::Catalan => LanguageFamily::IndoEuropean,
+
+            #[cfg(feature = "chinese")]
+            Language::Chinese => LanguageFamily::SinoTibetan,
+
+            #[cfg(feature = "croatian")]
+            Language::Croatian => LanguageFamily::IndoEuropean,
+
+            #[cfg(feature = "czech")]
+            Language::Czech => LanguageFamily::IndoEuropean,
+
+            #[cfg(feature = "danish")]
+            Language::Danish => LanguageFamily::IndoEuropean,
+
+            #[cfg(feature = "dutch")]
+            Language::Dutch => LanguageFamily::IndoEuropean,
+
+            #[cfg(feature = "english")]
+            Language::English => LanguageFamily::IndoEuropean,
+
+            #[cfg(feature = "esperanto")]
+            Language::Esperanto => LanguageFamily::Constructed,
+
+            #[cfg(feature = "estonian")]
+            Language::Estonian => LanguageFamily::Uralic,
+
+            #[cfg(feature = "finnish")]
+            Language::Finnish => LanguageFamily::Uralic,
+
+            #[cfg(feature = "french")]
+            Language::French => LanguageFamily::IndoEuropean,
+
+            #[cfg(feature = "ganda")]
+            Language::Ganda => LanguageFamily::NigerCongo,
+
+            #[cfg(feature = "georgian")]
+            Language::Georgian => LanguageFamily::Kartvelian,
+
+            #[cfg(feature = "german")]
+            Language::German => LanguageFamily::IndoEuropean,
+
+            #[cfg(feature = "greek")]
+            Language::Greek => LanguageFamily::IndoEuropean,
+
+            #[cfg(feature = "gujarati")]
+            Language::Gujarati => LanguageFamily::IndoEuropean,
+
+            #[cfg(feature = "hebrew")]
+            Language::Hebrew => LanguageFamily::Afroasiatic,
+
+            #[cfg(feature = "hindi")]
+            Language::Hindi => LanguageFamily::IndoEuropean,
+
+            #[cfg(feature = "hungarian")]
+            Language::Hungarian => LanguageFamily::Uralic,
+
+            #[cfg(feature = "icelandic")]
+            Language::Icelandic => LanguageFamily::IndoEuropean,
+
+            #[cfg(feature = "indonesian")]
+            Language::Indonesian => LanguageFamily::Austronesian,
+
+            #[cfg(feature = "irish")]
+            Language::Irish => LanguageFamily::IndoEuropean,
+
+            #[cfg(feature = "italian")]
+            Language::Italian => LanguageFamily::IndoEuropean,
+
+            #[cfg(feature = "japanese")]
+            Language::Japanese => LanguageFamily::Japonic,
+
+            #[cfg(feature = "kazakh")]
+            Language::Kazakh => LanguageFamily::Turkic,
+
+            #[cfg(feature = "korean")]
+            Language::Korean => LanguageFamily::Koreanic,
+
+            #[cfg(feature = "latin")]
+            Language::Latin => LanguageFamily::IndoEuropean,
+
+            #[cfg(feature = "latvian")]
+            Language::Latvian => LanguageFamily::IndoEuropean,
+
+            #[cfg(feature = "lithuanian")]
+            Language::Lithuanian => LanguageFamily::IndoEuropean,
+
+            #[cfg(feature = "macedonian")]
+            Language::Macedonian => LanguageFamily::IndoEuropean,
+
+            #[cfg(feature = "malay")]
+            Language::Malay => LanguageFamily::Austronesian,
+
+            #[cfg(feature = "maori")]
+            Language::Maori => LanguageFamily::Austronesian,
+
+            #[cfg(feature = "marathi")]
+            Language::Marathi => LanguageFamily::IndoEuropean,
+
+            #[cfg(feature = "mongolian")]
+            Language::Mongolian => LanguageFamily::Mongolic,
+
+            #[cfg(feature = "nynorsk")]
+            Language::Nynorsk => LanguageFamily::IndoEuropean,
+
+            #[cfg(feature = "persian")]
+            Language::Persian => LanguageFamily::IndoEuropean,
+
+            #[cfg(feature = "polish")]
+            Language::Polish => LanguageFamily::IndoEuropean,
+
+            #[cfg(feature = "portuguese")]
+            Language::Portuguese => LanguageFamily::IndoEuropean,
+
+            #[cfg(feature = "punjabi")]
+            Language::Punjabi => LanguageFamily::IndoEuropean,
+
+            #[cfg(feature = "romanian")]
+            Language::Romanian => LanguageFamily::IndoEuropean,
+
+            #[cfg(feature = "russian")]
+            Language::Russian => LanguageFamily::IndoEuropean,
+
+            #[cfg(feature = "serbian")]
+            Language::Serbian => LanguageFamily::IndoEuropean,
+
+            #[cfg(feature = "shona")]
+            Language::Shona => LanguageFamily::NigerCongo,
+
+            #[cfg(feature = "slovak")]
+            Language::Slovak => LanguageFamily::IndoEuropean,
+
+            #[cfg(feature = "slovene")]
+            Language::Slovene => LanguageFamily::IndoEuropean,
+
+            #[cfg(feature = "somali")]
+            Language::Somali => LanguageFamily::Afroasiatic,
+
+            #[cfg(feature = "sotho")]
+            Language::Sotho => LanguageFamily::NigerCongo,
+
+            #[cfg(feature = "spanish")]
+            Language::Spanish => LanguageFamily::IndoEuropean,
+
+            #[cfg(feature = "swahili")]
+            Language::Swahili => LanguageFamily::NigerCongo,
+
+            #[cfg(feature = "swedish")]
+            Language::Swedish => LanguageFamily::IndoEuropean,
+
+            #[cfg(feature = "tagalog")]
+            Language::Tagalog => LanguageFamily::Austronesian,
+
+            #[cfg(feature = "tamil")]
+            Language::Tamil => LanguageFamily::Dravidian,
+
+            #[cfg(feature = "telugu")]
+            Language::Telugu => LanguageFamily::Dravidian,
+
+            #[cfg(feature = "thai")]
+            Language::Thai => LanguageFamily::KraDai,
+
+            #[cfg(feature = "tsonga")]
+            Language::Tsonga => LanguageFamily::NigerCongo,
+
+            #[cfg(feature = "tswana")]
+            Language::Tswana => LanguageFamily::NigerCongo,
+
+            #[cfg(feature = "turkish")]
+            Language::Turkish => LanguageFamily::Turkic,
+
+            #[cfg(feature = "ukrainian")]
+            Language::Ukrainian => LanguageFamily::IndoEuropean,
+
+            #[cfg(feature = "urdu")]
+            Language::Urdu => LanguageFamily::IndoEuropean,
+
+            #[cfg(feature = "vietnamese")]
+            Language::Vietnamese => LanguageFamily::Austroasiatic,
+
+            #[cfg(feature = "welsh")]
+            Language::Welsh => LanguageFamily::IndoEuropean,
+
+            #[cfg(feature = "xhosa")]
+            Language::Xhosa => LanguageFamily::NigerCongo,
+
+            #[cfg(feature = "yoruba")]
+            Language::Yoruba => LanguageFamily::NigerCongo,
+
+            #[cfg(feature = "zulu")]
+            Language::Zulu => LanguageFamily::NigerCongo,
+        }
+    }
+
+    /// Returns the directions in which the alphabets of this language are written.
+    ///
+    /// ```
+    /// use lingua::Language::{English, Hebrew};
+    /// use lingua::WritingDirection;
+    ///
+    /// assert_eq!(English.writing_directions(), [WritingDirection::LeftToRight].into());
+    /// assert_eq!(Hebrew.writing_directions(), [WritingDirection::RightToLeft].into());
+    /// ```
+    pub fn writing_directions(&self) -> HashSet<WritingDirection> {
+        self.alphabets()
+            .iter()
+            .map(|alphabet| alphabet.writing_direction())
+            .collect()
+    }
+
+    /// Returns the accuracy that *Lingua* achieves in high accuracy mode for texts of
+    /// this language and the given length, as a value between 0.0 and 1.0.
+    ///
+    /// The values are taken from the accuracy reports published with this library which
+    /// have been measured with all supported languages being enabled. Applications can
+    /// use them to decide whether to trust a detection result or to ask the user instead.
+    ///
+    /// ```
+    /// use lingua::Language::German;
+    /// use lingua::TextLength;
+    ///
+    /// assert!(German.expected_accuracy(TextLength::Short) < German.expected_accuracy(TextLength::Long));
+    /// assert_eq!(German.expected_accuracy(TextLength::of("Ich bin hier")), 0.73);
+    /// ```
+    pub fn expected_accuracy(&self, text_length: TextLength) -> f64 {
+        expected_accuracy(self, text_length)
+            .unwrap_or_else(|| panic!("no accuracy report exists for language {self:?}"))
+    }
+
+    /// Returns the characters which, among all supported languages, are used by this
+    /// language only, or [None] if the language has no such characters.
+    pub fn unique_characters(&self) -> Option<&'static str> {
+        match self {
+            #[cfg(feature = "azerbaijani")]
+            Language::Azerbaijani => Some("Əə"),
+
+            #[cfg(feature = "catalan")]
+            Language::Catalan => Some("Ïï"),
+
+            #[cfg(feature = "czech")]
+            Language::Czech => Some("ĚěŘřŮů"),
+
+            #[cfg(feature = "esperanto")]
+            Language::Esperanto => Some("ĈĉĜĝĤĥĴĵŜŝŬŭ"),
+
+            #[cfg(feature = "german")]
+            Language::German => Some("ß"),
+
+            #[cfg(feature = "hungarian")]
+            Language::Hungarian => Some("ŐőŰű"),
+
+            #[cfg(feature = "kazakh")]
+            Language::Kazakh => Some("ӘәҒғҚқҢңҰұ"),
+
+            #[cfg(feature = "latvian")]
+            Language::Latvian => Some("ĢģĶķĻļŅņ"),
+
+            #[cfg(feature = "lithuanian")]
+            Language::Lithuanian => Some("ĖėĮįŲų"),
+
+            #[cfg(feature = "macedonian")]
+            Language::Macedonian => Some("ЃѓЅѕЌќЏџ"),
+
+            #[cfg(feature = "marathi")]
+            Language::Marathi => Some("ळ"),
+
+            #[cfg(feature = "mongolian")]
+            Language::Mongolian => Some("ӨөҮү"),
+
+            #[cfg(feature = "polish")]
+            Language::Polish => Some("ŁłŃńŚśŹź"),
+
+            #[cfg(feature = "romanian")]
+            Language::Romanian => Some("Țţ"),
+
+            #[cfg(feature = "serbian")]
+            Language::Serbian => Some("ЂђЋћ"),
+
+            #[cfg(feature = "slovak")]
+            Language::Slovak => Some("ĹĺĽľŔŕ"),
+
+            #[cfg(feature = "spanish")]
+            Language::Spanish => Some("¿¡"),
+
+            #[cfg(feature = "ukrainian")]
+            Language::Ukrainian => Some("ҐґЄєЇї"),
+
+            #[cfg(feature = "vietnamese")]
+            Language::Vietnamese => Some("ẰằẦầẲẳẨẩẴẵẪẫẮắẤấẠạẶặẬậỀềẺẻỂểẼẽỄễẾếỆệỈỉĨĩỊịƠơỒồỜờỎỏỔổỞởỖỗỠỡỐốỚớỘộỢợƯưỪừỦủỬửŨũỮữỨứỤụỰựỲỳỶỷỸỹỴỵ"),
+
+            #[cfg(feature = "yoruba")]
+            Language::Yoruba => Some("Ṣṣ"),
+
+            _ => None,
+        }
+    }
+}
+
+/// Accumulates counts or sums per language in an array indexed by the languages' discriminants,
+/// so that no hashing is needed in hot loops. The languages with a value other than zero are
+/// remembered, so that they can be enumerated and reset without scanning the whole array.
+pub(crate) struct LanguageCounts<T = u32> {
+    counts: [T; Language::COUNT],
+    languages: Vec<Language>,
+}
+
+impl<T: Copy + Default + PartialEq + AddAssign> LanguageCounts<T> {
+    pub(crate) fn new() -> Self {
+        Self {
+            counts: [T::default(); Language::COUNT],
+            languages: vec![],
+        }
+    }
+
+    /// Adds the given value to the value of the given language. Zero values are ignored,
+    /// so that only languages with a value other than zero are enumerated.
+    pub(crate) fn add(&mut self, language: Language, value: T) {
+        if value == T::default() {
+            return;
+        }
+        let count = &mut self.counts[language as usize];
+        if *count == T::default() {
+            self.languages.push(language);
+        }
+        *count += value;
+    }
+
+    pub(crate) fn get(&self, language: Language) -> T {
+        self.counts[language as usize]
+    }
+
+    /// Returns the value of the given language, or [None] if it has never been added.
+    pub(crate) fn find(&self, language: Language) -> Option<T> {
+        Some(self.get(language)).filter(|count| *count != T::default())
+    }
+
+    /// Returns the number of languages which have been counted at least once.
+    pub(crate) fn len(&self) -> usize {
+        self.languages.len()
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.languages.is_empty()
+    }
+
+    /// Returns the counted languages in the order of their first occurrence.
+    pub(crate) fn languages(&self) -> &[Language] {
+        &self.languages
+    }
+
+    /// Returns the counted languages with their counts in the order of their first occurrence.
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (Language, T)> + '_ {
+        self.languages
+            .iter()
+            .map(|language| (*language, self.get(*language)))
+    }
+
+    pub(crate) fn clear(&mut self) {
+        for language in self.languages.drain(..) {
+            self.counts[language as usize] = T::default();
+        }
+    }
+}
+
+impl LanguageCounts {
+    pub(crate) fn increment(&mut self, language: Language) {
+        self.add(language, 1);
+    }
+}
+
+impl<T: Copy + Default + PartialEq + AddAssign> FromIterator<(Language, T)> for LanguageCounts<T> {
+    fn from_iter<I: IntoIterator<Item = (Language, T)>>(iter: I) -> Self {
+        let mut counts = Self::new();
+        for (language, value) in iter {
+            counts.add(language, value);
+        }
+        counts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::language::Language::*;
+
+    use super::*;
+
+    #[test]
+    fn assert_languages_are_counted_correctly() {
+        let mut counts = LanguageCounts::new();
+        counts.increment(German);
+        counts.increment(English);
+        counts.increment(German);
+
+        assert_eq!(counts.len(), 2);
+        assert_eq!(counts.get(German), 2);
+        assert_eq!(counts.get(French), 0);
+        assert_eq!(
+            counts.iter().collect::<Vec<_>>(),
+            vec![(German, 2), (English, 1)]
+        );
+
+        counts.clear();
+        assert_eq!(counts.len(), 0);
+        assert_eq!(counts.get(German), 0);
+    }
+
+    #[test]
+    fn assert_language_sums_are_accumulated_correctly() {
+        let mut sums = [(German, -1.5), (English, 0.0), (German, -0.5)]
+            .into_iter()
+            .collect::<LanguageCounts<f64>>();
+
+        assert_eq!(sums.languages(), &[German]);
+        assert_eq!(sums.find(German), Some(-2.0));
+        assert_eq!(sums.find(English), None);
+
+        sums.add(French, -3.0);
+        assert_eq!(
+            sums.iter().collect::<Vec<_>>(),
+            vec![(German, -2.0), (French, -3.0)]
+        );
+    }
+
+    #[test]
+    fn assert_language_string_representation_is_correct() {
+        assert_eq!(English.to_string(), "English");
+    }
+
+    #[test]
+    fn test_language_serializer() {
+        let serialized = serde_json::to_string(&English).unwrap();
+        assert_eq!(serialized, "\"ENGLISH\"");
+    }
+
+    #[test]
+    fn test_language_deserializer() {
+        let deserialized = serde_json::from_str::<Language>("\"ENGLISH\"").unwrap();
+        assert_eq!(deserialized, English);
+    }
+
+    #[test]
+    fn test_from_str() {
+        let language = Language::from_str("english").unwrap();
+        assert_eq!(language, English);
+    }
+
+    #[test]
+    fn assert_language_can_be_parsed_from_iso_codes() {
+        assert_eq!(Language::from_str("en"), Ok(English));
+        assert_eq!(Language::from_str("NB"), Ok(Bokmal));
+        assert_eq!(Language::from_str("eng"), Ok(English));
+        assert_eq!(Language::from_str("Zho"), Ok(Chinese));
+        assert_eq!(
+            Language::from_str("xx"),
+            Err(strum::ParseError::VariantNotFound)
+        );
+        assert_eq!(
+            Language::from_str(""),
+            Err(strum::ParseError::VariantNotFound)
+        );
+    }
+
+    #[test]
+    fn assert_every_spoken_language_is_spoken_in_some_region() {
+        for language in Language::iter() {
+            assert_eq!(
+                language.spoken_in().is_empty(),
+                !Language::all_spoken_ones().contains(&language) || language == Esperanto,
+                "{language:?}"
+            );
+            assert!(language.spoken_in().windows(2).all(|it| it[0] < it[1]));
+        }
+        for region in Region::iter() {
+            assert!(!Language::all_spoken_in(region).is_empty(), "{region:?}");
+        }
+    }
+
+    #[test]
+    fn assert_language_metadata_is_correct() {
+        assert_eq!(Japanese.native_name(), "日本語");
+        assert_eq!(Georgian.language_family(), LanguageFamily::Kartvelian);
+        assert_eq!(Esperanto.language_family(), LanguageFamily::Constructed);
+        assert_eq!(
+            Urdu.writing_directions(),
+            hashset!(WritingDirection::RightToLeft)
+        );
+        assert_eq!(
+            Japanese.writing_directions(),
+            hashset!(WritingDirection::LeftToRight)
+        );
+    }
+
+    #[test]
+    fn assert_unique_characters_are_public() {
+        assert_eq!(German.unique_characters(), Some("ß"));
+        assert_eq!(English.unique_characters(), None);
+    }
+
+    #[test]
+    fn assert_languages_with_character_are_looked_up_correctly() {
+        assert_eq!(Language::languages_with_character('ß'), hashset!(German));
+        assert_eq!(
+            Language::languages_with_character('Ə'),
+            hashset!(Azerbaijani)
+        );
+        assert_eq!(
+            Language::languages_with_character('ã'),
+            hashset!(Portuguese, Vietnamese)
+        );
+        assert_eq!(
+            Language::languages_with_character('ą'),
+            hashset!(Lithuanian, Polish)
+        );
+        assert_eq!(Language::languages_with_character('a'), hashset!());
+        assert_eq!(Language::languages_with_character('7'), hashset!());
     }
 
     #[test]
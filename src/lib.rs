@@ -36,9 +36,9 @@
 //! Unfortunately, most of them have two major drawbacks:
 //!
 //! 1. Detection only works with quite lengthy text fragments. For very short text snippets
-//! such as Twitter messages, it does not provide adequate results.
+//!    such as Twitter messages, it does not provide adequate results.
 //! 2. The more languages take part in the decision process, the less accurate are the
-//! detection results.
+//!    detection results.
 //!
 //! *Lingua* aims at eliminating these problems. She nearly does not need any configuration and
 //! yields pretty accurate results on both long and short text, even on single words and phrases.
@@ -376,30 +376,102 @@ extern crate maplit;
 #[cfg(test)]
 use regex::Regex;
 
-pub use builder::LanguageDetectorBuilder;
+pub use accuracy::TextLength;
+pub use alignment::{AlignmentReport, SegmentPairLanguages};
+pub use alphabet::{Alphabet, WritingDirection};
+pub use anonymize::AnonymizationFilter;
+pub use binary::{decode_binary_model, encode_binary_model};
+pub use builder::{BuilderError, LanguageDetectorBuilder};
+pub use cascade::CascadeDetector;
+pub use cleanup::CleanupPolicy;
+pub use combiner::{DefaultScoreCombiner, NgramScores, ScoreCombiner};
+pub use consensus::ConsensusResult;
+pub use context::DetectionContext;
+pub use coverage::{LanguageCoverage, ModelSizes};
 pub use detector::LanguageDetector;
+#[cfg(feature = "hunspell")]
+pub use dictionary::{DictionaryVerifier, HunspellDictionary};
+#[cfg(feature = "failure-injection")]
+pub use failure::{FailureInjector, ModelFailure};
+pub use fallback::Fallback;
+pub use family::LanguageFamily;
 pub use isocode::{IsoCode639_1, IsoCode639_3};
 pub use language::Language;
-pub use result::DetectionResult;
+pub use limits::DetectionError;
+pub use live::LiveDetector;
+pub use memory::MemoryReport;
+pub use model::LanguageModel;
+pub use ngram::{lower_order_ngrams, ngrams, LowerOrderNgrams, Ngrams};
+pub use normalization::NormalizationForm;
+pub use pack::ModelPack;
+pub use postprocessor::ConfidencePostProcessor;
+pub use preprocessor::{MarkupStripper, TextPreprocessor};
+pub use profile::{ConfidenceProfile, ConfidenceWindow};
+pub use region::Region;
+pub use result::{DetectionResult, RankedConfidence};
+pub use rules::ScriptRuleDetector;
+pub use testdata::TestData;
+pub use truncate::truncate_for_detection;
+pub use usage::LanguageUsage;
 #[cfg(target_family = "wasm")]
 pub use wasm::{
     ConfidenceValue, DetectionResult as WasmDetectionResult,
     LanguageDetectorBuilder as WasmLanguageDetectorBuilder,
+    ScriptRuleDetector as WasmScriptRuleDetector,
 };
 pub use writer::{LanguageModelFilesWriter, TestDataFilesWriter};
 
+mod accuracy;
+mod alignment;
 mod alphabet;
+mod anonymize;
+mod binary;
 mod builder;
+mod capitalization;
+mod cascade;
+mod cleanup;
+mod combiner;
+mod confusable;
+mod consensus;
 mod constant;
+mod context;
+mod coverage;
+mod dedup;
 mod detector;
+#[cfg(feature = "hunspell")]
+mod dictionary;
+#[cfg(feature = "failure-injection")]
+mod failure;
+mod fallback;
+mod family;
+mod features;
+#[cfg(all(feature = "c-api", not(target_family = "wasm")))]
+mod ffi;
 mod fraction;
 mod isocode;
 mod json;
 mod language;
+mod limits;
+mod live;
+mod memory;
 mod model;
+mod names;
 mod ngram;
+mod ngramtable;
+mod normalization;
+mod ocr;
+mod pack;
+mod postprocessor;
+mod preprocessor;
+mod profile;
+mod region;
 mod result;
+mod rules;
 mod script;
+mod testdata;
+mod transcript;
+mod truncate;
+mod usage;
 mod writer;
 
 #[cfg(target_family = "wasm")]
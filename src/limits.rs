@@ -0,0 +1,222 @@
+/*
+ * Copyright © 2020-present Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::error::Error;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::time::{Duration, Instant};
+
+pub(crate) const MAXIMUM_INPUT_BYTES_MESSAGE: &str =
+    "Maximum number of input bytes must be greater than zero";
+pub(crate) const MAXIMUM_NGRAM_COUNT_MESSAGE: &str =
+    "Maximum number of ngrams must be greater than zero";
+pub(crate) const MAXIMUM_PROCESSING_TIME_MESSAGE: &str =
+    "Maximum processing time must be greater than zero";
+
+/// This enum describes the limits of a [LanguageDetector](crate::LanguageDetector)
+/// which can be exceeded by an input text, as returned by the fallible methods such as
+/// [LanguageDetector::try_detect_language_of](crate::LanguageDetector::try_detect_language_of).
+///
+/// The limits are configured with
+/// [LanguageDetectorBuilder::with_maximum_input_bytes](crate::LanguageDetectorBuilder::with_maximum_input_bytes),
+/// [LanguageDetectorBuilder::with_maximum_ngram_count](crate::LanguageDetectorBuilder::with_maximum_ngram_count) and
+/// [LanguageDetectorBuilder::with_maximum_processing_time](crate::LanguageDetectorBuilder::with_maximum_processing_time).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DetectionError {
+    /// The input text is larger than the maximum number of bytes.
+    InputTooLarge {
+        /// The size of the input text in bytes.
+        size: usize,
+        /// The maximum number of bytes.
+        limit: usize,
+    },
+    /// The input text consists of more ngrams than the maximum number of ngrams.
+    TooManyNgrams {
+        /// The number of ngrams of all lengths which would have to be looked up.
+        count: usize,
+        /// The maximum number of ngrams.
+        limit: usize,
+    },
+    /// The detection did not finish within the maximum processing time.
+    Timeout {
+        /// The maximum processing time.
+        limit: Duration,
+    },
+}
+
+impl Display for DetectionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            DetectionError::InputTooLarge { size, limit } => write!(
+                f,
+                "Input text of {size} bytes exceeds the maximum of {limit} bytes"
+            ),
+            DetectionError::TooManyNgrams { count, limit } => write!(
+                f,
+                "Input text of {count} ngrams exceeds the maximum of {limit} ngrams"
+            ),
+            DetectionError::Timeout { limit } => write!(
+                f,
+                "Detection exceeded the maximum processing time of {limit:?}"
+            ),
+        }
+    }
+}
+
+impl Error for DetectionError {}
+
+/// The hard caps on the work a detector spends on a single input text.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub(crate) struct DetectionLimits {
+    pub(crate) maximum_input_bytes: Option<usize>,
+    pub(crate) maximum_ngram_count: Option<usize>,
+    pub(crate) maximum_processing_time: Option<Duration>,
+}
+
+impl DetectionLimits {
+    pub(crate) fn check_input_size(&self, text: &str) -> Result<(), DetectionError> {
+        match self.maximum_input_bytes {
+            Some(limit) if text.len() > limit => Err(DetectionError::InputTooLarge {
+                size: text.len(),
+                limit,
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Checks the number of ngrams of the given lengths which the given words consist of.
+    /// Duplicate ngrams are counted as often as they occur, so that the count is known
+    /// before any ngram set is built.
+    pub(crate) fn check_ngram_count(
+        &self,
+        words: &[String],
+        ngram_lengths: &[usize],
+    ) -> Result<(), DetectionError> {
+        let limit = match self.maximum_ngram_count {
+            Some(limit) => limit,
+            None => return Ok(()),
+        };
+
+        let mut count = 0;
+
+        for word in words {
+            let character_count = word.chars().count();
+            for ngram_length in ngram_lengths {
+                count += (character_count + 1).saturating_sub(*ngram_length);
+            }
+        }
+
+        if count > limit {
+            Err(DetectionError::TooManyNgrams { count, limit })
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Starts measuring the processing time of an input text.
+    pub(crate) fn start(&self) -> Deadline {
+        Deadline(
+            self.maximum_processing_time
+                .map(|limit| (Instant::now() + limit, limit)),
+        )
+    }
+}
+
+/// The point in time at which the detection of an input text is aborted.
+/// The clock is only read if a maximum processing time is configured.
+pub(crate) struct Deadline(Option<(Instant, Duration)>);
+
+impl Deadline {
+    pub(crate) fn check(&self) -> Result<(), DetectionError> {
+        match self.0 {
+            Some((deadline, limit)) if Instant::now() > deadline => {
+                Err(DetectionError::Timeout { limit })
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn assert_input_size_is_checked() {
+        let limits = DetectionLimits {
+            maximum_input_bytes: Some(5),
+            ..Default::default()
+        };
+
+        assert_eq!(limits.check_input_size("hello"), Ok(()));
+        assert_eq!(
+            limits.check_input_size("hellö"),
+            Err(DetectionError::InputTooLarge { size: 6, limit: 5 })
+        );
+        assert_eq!(DetectionLimits::default().check_input_size("hellö"), Ok(()));
+    }
+
+    #[test]
+    fn assert_ngram_count_is_checked() {
+        let words = vec!["hello".to_string(), "ab".to_string()];
+        let limits = |limit| DetectionLimits {
+            maximum_ngram_count: Some(limit),
+            ..Default::default()
+        };
+
+        // unigrams: 5 + 2, trigrams: 3 + 0, fivegrams: 1 + 0
+        assert_eq!(limits(11).check_ngram_count(&words, &[1, 3, 5]), Ok(()));
+        assert_eq!(
+            limits(10).check_ngram_count(&words, &[1, 3, 5]),
+            Err(DetectionError::TooManyNgrams {
+                count: 11,
+                limit: 10
+            })
+        );
+    }
+
+    #[test]
+    fn assert_deadline_is_checked() {
+        let limits = DetectionLimits {
+            maximum_processing_time: Some(Duration::from_nanos(1)),
+            ..Default::default()
+        };
+        let deadline = limits.start();
+        std::thread::sleep(Duration::from_millis(1));
+
+        assert_eq!(
+            deadline.check(),
+            Err(DetectionError::Timeout {
+                limit: Duration::from_nanos(1)
+            })
+        );
+        assert_eq!(DetectionLimits::default().start().check(), Ok(()));
+    }
+
+    #[test]
+    fn assert_detection_errors_are_displayed_correctly() {
+        assert_eq!(
+            DetectionError::InputTooLarge { size: 6, limit: 5 }.to_string(),
+            "Input text of 6 bytes exceeds the maximum of 5 bytes"
+        );
+        assert_eq!(
+            DetectionError::Timeout {
+                limit: Duration::from_millis(50)
+            }
+            .to_string(),
+            "Detection exceeded the maximum processing time of 50ms"
+        );
+    }
+}
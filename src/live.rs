@@ -0,0 +1,197 @@
+/*
+ * Copyright © 2020-present Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::HashSet;
+
+use ahash::AHashMap;
+use compact_str::CompactString;
+
+use crate::detector::{compute_undetermined_confidence_values, LanguageDetector};
+use crate::language::Language;
+
+/// The languages a cache slot has been filled for, in sorted order, together with
+/// the logarithmized probability of each ngram seen so far for each of these languages.
+pub(crate) type NgramProbabilityCacheSlot = (Vec<Language>, AHashMap<CompactString, Vec<f64>>);
+
+/// Caches the ngram probabilities looked up in the language models,
+/// with one slot per ngram length.
+#[derive(Debug, Default)]
+pub(crate) struct NgramProbabilityCache {
+    slots: [NgramProbabilityCacheSlot; 5],
+}
+
+impl NgramProbabilityCache {
+    /// Returns the cache slots for the ngram lengths 1 to 5. Slots which have been filled
+    /// for a different set of languages are cleared first.
+    pub(crate) fn slots_for(
+        &mut self,
+        languages: &HashSet<Language>,
+    ) -> impl Iterator<Item = &mut NgramProbabilityCacheSlot> {
+        let mut sorted_languages = languages.iter().copied().collect::<Vec<_>>();
+        sorted_languages.sort();
+
+        for (slot_languages, log_probabilities) in self.slots.iter_mut() {
+            if *slot_languages != sorted_languages {
+                slot_languages.clone_from(&sorted_languages);
+                log_probabilities.clear();
+            }
+        }
+
+        self.slots.iter_mut()
+    }
+
+    fn clear(&mut self) {
+        for (languages, log_probabilities) in self.slots.iter_mut() {
+            languages.clear();
+            log_probabilities.clear();
+        }
+    }
+}
+
+/// A stateful wrapper around a [LanguageDetector] for live-typing scenarios,
+/// such as a text editor that detects the language on every keystroke.
+///
+/// Each time the text changes, [on_input_change](LiveDetector::on_input_change)
+/// is called with the complete current text. As long as the new text extends the
+/// previous one, the ngram probabilities which have already been looked up in the
+/// language models are reused, so only the newly typed ngrams cost a model lookup.
+/// Any other edit, such as deleting or replacing characters in the middle of the text,
+/// discards the cached state.
+///
+/// The results are identical to those of [LanguageDetector::detect_language_of]
+/// and [LanguageDetector::compute_language_confidence_values] for the same text.
+///
+/// ```
+/// use lingua::{LanguageDetectorBuilder, LiveDetector};
+/// use lingua::Language::{English, French, German};
+///
+/// let detector = LanguageDetectorBuilder::from_languages(&[English, French, German]).build();
+/// let mut live_detector = LiveDetector::new(&detector);
+///
+/// live_detector.on_input_change("languages");
+/// let detected_language = live_detector.on_input_change("languages are awesome");
+///
+/// assert_eq!(detected_language, Some(English));
+/// ```
+pub struct LiveDetector<'a> {
+    detector: &'a LanguageDetector,
+    previous_text: String,
+    cache: NgramProbabilityCache,
+    confidence_values: Vec<(Language, f64)>,
+}
+
+impl<'a> LiveDetector<'a> {
+    /// Creates a new live detector which delegates to the given [LanguageDetector].
+    pub fn new(detector: &'a LanguageDetector) -> Self {
+        Self {
+            detector,
+            previous_text: String::new(),
+            cache: NgramProbabilityCache::default(),
+            confidence_values: vec![],
+        }
+    }
+
+    /// Detects the language of the given text which is the complete text
+    /// entered so far, not only the most recent change.
+    ///
+    /// `None` is returned under the same conditions as in
+    /// [LanguageDetector::detect_language_of], taking the fallback chain
+    /// of the detector into account.
+    pub fn on_input_change(&mut self, full_text: &str) -> Option<Language> {
+        if !full_text.starts_with(&self.previous_text) {
+            self.cache.clear();
+        }
+
+        self.previous_text.clear();
+        self.previous_text.push_str(full_text);
+
+        self.confidence_values = self
+            .detector
+            .compute_language_confidence_values_with_cache(
+                full_text,
+                &self.detector.languages,
+                Some(&mut self.cache),
+            )
+            .unwrap_or_else(|_| compute_undetermined_confidence_values(&self.detector.languages));
+
+        self.detector
+            .select_language_or_fallback(&self.confidence_values)
+    }
+
+    /// Returns the confidence values computed by the most recent call of
+    /// [on_input_change](LiveDetector::on_input_change), sorted in descending order.
+    pub fn confidence_values(&self) -> &[(Language, f64)] {
+        &self.confidence_values
+    }
+
+    /// Discards the previous text and all cached state.
+    pub fn reset(&mut self) {
+        self.previous_text.clear();
+        self.cache.clear();
+        self.confidence_values.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::LanguageDetectorBuilder;
+    use crate::language::Language::*;
+
+    #[test]
+    fn assert_live_detection_equals_full_detection() {
+        let detector =
+            LanguageDetectorBuilder::from_languages(&[English, French, German, Spanish]).build();
+        let mut live_detector = LiveDetector::new(&detector);
+        let text = "Das ist ein schöner Tag";
+
+        for (i, _) in text.char_indices().skip(1) {
+            let prefix = &text[..i];
+
+            assert_eq!(
+                live_detector.on_input_change(prefix),
+                detector.detect_language_of(prefix),
+                "prefix: {prefix}"
+            );
+            assert_eq!(
+                live_detector.confidence_values(),
+                detector.compute_language_confidence_values(prefix),
+                "prefix: {prefix}"
+            );
+        }
+    }
+
+    #[test]
+    fn assert_cache_is_cleared_when_text_is_not_extended() {
+        let detector = LanguageDetectorBuilder::from_languages(&[English, German]).build();
+        let mut live_detector = LiveDetector::new(&detector);
+
+        live_detector.on_input_change("house");
+        assert!(!live_detector.cache.slots[2].1.is_empty());
+
+        live_detector.on_input_change("hous");
+        assert_eq!(live_detector.cache.slots[2].1.len(), 2);
+
+        live_detector.reset();
+        assert!(live_detector.previous_text.is_empty());
+        assert!(live_detector
+            .cache
+            .slots
+            .iter()
+            .all(|(_, it)| it.is_empty()));
+        assert!(live_detector.confidence_values().is_empty());
+    }
+}
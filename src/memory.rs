@@ -0,0 +1,116 @@
+/*
+ * Copyright © 2020-present Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::HashMap;
+
+use crate::alphabet::Alphabet;
+use crate::language::Language;
+
+/// This struct reports the approximate number of bytes that are currently held in memory
+/// by the language models and alphabet tables of a [LanguageDetector](crate::LanguageDetector).
+///
+/// Language models which have not been loaded yet do not occupy any memory
+/// and are therefore reported with zero bytes.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MemoryReport {
+    pub(crate) language_models: HashMap<Language, [usize; 5]>,
+    pub(crate) alphabets: HashMap<Alphabet, usize>,
+}
+
+impl MemoryReport {
+    /// Returns the number of bytes occupied by the model of the given language
+    /// and ngram length.
+    ///
+    /// ⚠ Panics if the ngram length is not in range 1..6.
+    pub fn language_model_bytes(&self, language: Language, ngram_length: usize) -> usize {
+        check_ngram_length(ngram_length);
+        self.language_models
+            .get(&language)
+            .map_or(0, |sizes| sizes[ngram_length - 1])
+    }
+
+    /// Returns the number of bytes occupied by all models of the given language.
+    pub fn language_bytes(&self, language: Language) -> usize {
+        self.language_models
+            .get(&language)
+            .map_or(0, |sizes| sizes.iter().sum())
+    }
+
+    /// Returns the number of bytes occupied by the models of the given ngram length
+    /// summed up over all languages.
+    ///
+    /// ⚠ Panics if the ngram length is not in range 1..6.
+    pub fn ngram_order_bytes(&self, ngram_length: usize) -> usize {
+        check_ngram_length(ngram_length);
+        self.language_models
+            .values()
+            .map(|sizes| sizes[ngram_length - 1])
+            .sum()
+    }
+
+    /// Returns the number of bytes occupied by the character table of the given alphabet.
+    pub fn alphabet_bytes(&self, alphabet: Alphabet) -> usize {
+        *self.alphabets.get(&alphabet).unwrap_or(&0)
+    }
+
+    /// Returns the number of bytes occupied by all language models and alphabet tables.
+    pub fn total_bytes(&self) -> usize {
+        let language_model_bytes = self
+            .language_models
+            .values()
+            .flat_map(|sizes| sizes.iter())
+            .sum::<usize>();
+        let alphabet_bytes = self.alphabets.values().sum::<usize>();
+        language_model_bytes + alphabet_bytes
+    }
+}
+
+fn check_ngram_length(ngram_length: usize) {
+    if !(1..6).contains(&ngram_length) {
+        panic!("ngram length {ngram_length} is not in range 1..6");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn assert_memory_report_sums_up_bytes_correctly() {
+        let report = MemoryReport {
+            language_models: hashmap!(
+                Language::English => [10, 20, 30, 40, 50],
+                Language::German => [1, 2, 3, 4, 5]
+            ),
+            alphabets: hashmap!(Alphabet::Latin => 100),
+        };
+
+        assert_eq!(report.language_model_bytes(Language::English, 3), 30);
+        assert_eq!(report.language_model_bytes(Language::French, 3), 0);
+        assert_eq!(report.language_bytes(Language::English), 150);
+        assert_eq!(report.language_bytes(Language::German), 15);
+        assert_eq!(report.ngram_order_bytes(2), 22);
+        assert_eq!(report.alphabet_bytes(Alphabet::Latin), 100);
+        assert_eq!(report.alphabet_bytes(Alphabet::Greek), 0);
+        assert_eq!(report.total_bytes(), 265);
+    }
+
+    #[test]
+    #[should_panic(expected = "ngram length 6 is not in range 1..6")]
+    fn assert_memory_report_rejects_invalid_ngram_length() {
+        MemoryReport::default().ngram_order_bytes(6);
+    }
+}
@@ -15,6 +15,8 @@
  */
 
 use std::collections::{BTreeMap, HashMap};
+use std::io;
+use std::io::ErrorKind;
 
 use ahash::AHashMap;
 use compact_str::CompactString;
@@ -26,16 +28,58 @@ use crate::fraction::Fraction;
 use crate::language::Language;
 use crate::ngram::{Ngram, NgramRef};
 
-#[derive(Debug, Eq, PartialEq, Serialize, Deserialize)]
-struct JsonLanguageModel {
-    language: Language,
-    ngrams: BTreeMap<Fraction, String>,
+/// This struct holds a language model of a single ngram length as stored in the language
+/// model files, i.e. the relative frequencies of the ngrams in the training data of a language.
+///
+/// It allows to convert language model files between the JSON format and the binary format,
+/// see [encode_binary_model](crate::encode_binary_model).
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct LanguageModel {
+    pub(crate) language: Language,
+    pub(crate) ngrams: BTreeMap<Fraction, String>,
+    #[serde(default, rename = "bootstrapped", skip_serializing_if = "is_false")]
+    pub(crate) is_bootstrapped: bool,
+}
+
+impl LanguageModel {
+    /// Parses a language model from the JSON format of the uncompressed language model files.
+    ///
+    /// Returns an error of kind [ErrorKind::InvalidData] if the JSON is not a valid language model.
+    pub fn from_json(json: &str) -> io::Result<Self> {
+        serde_json::from_str(json).map_err(|error| io::Error::new(ErrorKind::InvalidData, error))
+    }
+
+    /// Serializes this language model into the JSON format
+    /// of the uncompressed language model files.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+
+    /// Returns the language this model belongs to.
+    pub fn language(&self) -> Language {
+        self.language
+    }
+
+    /// Returns the number of ngrams in this model.
+    pub fn ngram_count(&self) -> usize {
+        self.ngrams
+            .values()
+            .map(|ngrams| ngrams.split(' ').count())
+            .sum()
+    }
+
+    /// Returns `true` if this model has been bootstrapped with
+    /// [LanguageModelFilesWriter::create_and_write_bootstrapped_language_model_files](crate::LanguageModelFilesWriter::create_and_write_bootstrapped_language_model_files).
+    pub fn is_bootstrapped(&self) -> bool {
+        self.is_bootstrapped
+    }
 }
 
 pub(crate) struct TrainingDataLanguageModel {
     language: Language,
     pub(crate) absolute_frequencies: Option<HashMap<Ngram, u32>>,
     relative_frequencies: Option<HashMap<Ngram, Fraction>>,
+    is_bootstrapped: bool,
 }
 
 impl TrainingDataLanguageModel {
@@ -59,11 +103,52 @@ impl TrainingDataLanguageModel {
             language: *language,
             absolute_frequencies: Some(absolute_frequencies),
             relative_frequencies: Some(relative_frequencies),
+            is_bootstrapped: false,
+        }
+    }
+
+    /// Creates a bootstrapped model by linearly interpolating the probabilities of the given
+    /// seed model with those of the model of a related language, the latter weighted with
+    /// `related_language_weight`. The model is marked as bootstrapped in its JSON file.
+    pub(crate) fn from_interpolation(
+        seed_model: &TrainingDataLanguageModel,
+        related_language_model: &AHashMap<CompactString, f64>,
+        related_language_weight: f64,
+    ) -> Self {
+        let mut probabilities = HashMap::<&str, f64>::new();
+
+        for (ngram, fraction) in seed_model.relative_frequencies.as_ref().unwrap() {
+            *probabilities.entry(&ngram.value).or_default() +=
+                (1.0 - related_language_weight) * fraction.to_f64();
+        }
+
+        for (ngram, probability) in related_language_model {
+            *probabilities.entry(ngram).or_default() += related_language_weight * probability;
+        }
+
+        let relative_frequencies = probabilities
+            .into_iter()
+            .map(|(ngram, probability)| (Ngram::new(ngram), Fraction::approximate(probability)))
+            .filter(|(_, fraction)| fraction.to_f64() > 0.0)
+            .collect();
+
+        TrainingDataLanguageModel {
+            language: seed_model.language,
+            absolute_frequencies: None,
+            relative_frequencies: Some(relative_frequencies),
+            is_bootstrapped: true,
         }
     }
 
     pub(crate) fn from_json(json: &str) -> AHashMap<CompactString, f64> {
-        let json_language_model = serde_json::from_str::<JsonLanguageModel>(json).unwrap();
+        Self::from_language_model(serde_json::from_str::<LanguageModel>(json).unwrap())
+    }
+
+    /// Returns the ngram probabilities of the given deserialized model, e.g. of a custom model
+    /// parsed with [LanguageModel::from_json], whose errors must not be unwrapped.
+    pub(crate) fn from_language_model(
+        json_language_model: LanguageModel,
+    ) -> AHashMap<CompactString, f64> {
         let mut json_relative_frequencies = AHashMap::new();
 
         for (fraction, ngrams) in json_language_model.ngrams {
@@ -91,9 +176,10 @@ impl TrainingDataLanguageModel {
             );
         }
 
-        let model = JsonLanguageModel {
+        let model = LanguageModel {
             language: self.language,
             ngrams: fractions_to_joined_ngrams,
+            is_bootstrapped: self.is_bootstrapped,
         };
 
         serde_json::to_string(&model).unwrap()
@@ -114,7 +200,7 @@ impl TrainingDataLanguageModel {
         for line in text.iter() {
             let chars = line.to_lowercase().chars().collect_vec();
 
-            for i in 0..=chars.len() - ngram_length {
+            for i in 0..(chars.len() + 1).saturating_sub(ngram_length) {
                 let slice = &chars[i..i + ngram_length].iter().collect::<String>();
 
                 if regex.is_match(slice) {
@@ -153,57 +239,66 @@ impl TrainingDataLanguageModel {
     }
 }
 
+/// Returns `true` if the given JSON language model has been bootstrapped
+/// from a seed lexicon and the model of a related language.
+fn is_false(value: &bool) -> bool {
+    !value
+}
+
 pub(crate) struct TestDataLanguageModel<'a> {
-    pub(crate) ngrams: Vec<Vec<NgramRef<'a>>>,
+    pub(crate) ngrams: Vec<NgramRef<'a>>,
 }
 
 impl<'a> TestDataLanguageModel<'a> {
     pub(crate) fn from(words: &'a [String], ngram_length: usize) -> Self {
-        if !(1..6).contains(&ngram_length) {
-            panic!("ngram length {ngram_length} is not in range 1..6");
+        Self::from_all(words, &[ngram_length]).pop().unwrap()
+    }
+
+    /// Extracts the ngrams of all given lengths in a single pass over the characters
+    /// of each word and returns one model per length in the same order.
+    pub(crate) fn from_all(words: &'a [String], ngram_lengths: &[usize]) -> Vec<Self> {
+        for ngram_length in ngram_lengths {
+            if !(1..6).contains(ngram_length) {
+                panic!("ngram length {ngram_length} is not in range 1..6");
+            }
         }
 
-        let mut ngrams = hashset!();
+        let mut ngrams = vec![vec![]; ngram_lengths.len()];
+        let mut char_boundaries = vec![];
 
+        // The ngrams borrow slices of the words, and their lower order ngrams are derived
+        // from them on demand. The character boundaries of a word are computed once
+        // and shared by the ngrams of all lengths starting at the same character.
         for word in words.iter() {
-            let chars_count = word.chars().count();
-
-            if chars_count >= ngram_length {
-                for i in 0..=chars_count - ngram_length {
-                    let slice = get_utf8_slice(word, i, i + ngram_length);
-                    ngrams.insert(NgramRef::new(slice));
+            char_boundaries.clear();
+            char_boundaries.extend(word.char_indices().map(|(index, _)| index));
+            char_boundaries.push(word.len());
+
+            for (i, start) in char_boundaries.iter().enumerate() {
+                for (ngrams, ngram_length) in ngrams.iter_mut().zip(ngram_lengths) {
+                    if let Some(end) = char_boundaries.get(i + ngram_length) {
+                        ngrams.push(NgramRef {
+                            value: &word[*start..*end],
+                            char_count: *ngram_length,
+                        });
+                    }
                 }
             }
         }
 
-        let mut lower_order_ngrams = Vec::with_capacity(ngrams.len());
-
-        for ngram in ngrams {
-            lower_order_ngrams.push(ngram.range_of_lower_order_ngrams().collect_vec());
-        }
-
-        Self {
-            ngrams: lower_order_ngrams,
-        }
+        ngrams
+            .into_iter()
+            .map(|mut ngrams| {
+                // Keep the ngrams sorted so that the log probabilities are always
+                // summed up in the same order.
+                ngrams.sort_unstable_by_key(|ngram| ngram.value);
+                ngrams.dedup();
+                Self { ngrams }
+            })
+            .collect()
     }
 }
 
-fn get_utf8_slice(string: &str, start: usize, end: usize) -> &str {
-    string
-        .char_indices()
-        .nth(start)
-        .map(|(start_pos, _)| {
-            string[start_pos..]
-                .char_indices()
-                .nth(end - start)
-                .map_or_else(
-                    || &string[start_pos..],
-                    |(end_pos, _)| &string[start_pos..start_pos + end_pos],
-                )
-        })
-        .unwrap()
-}
-
 #[cfg(test)]
 mod tests {
     use itertools::Itertools;
@@ -222,9 +317,10 @@ mod tests {
 
         #[test]
         fn test_json_model_serializer_and_deserializer() {
-            let model = JsonLanguageModel {
+            let model = LanguageModel {
                 language: Language::English,
                 ngrams: btreemap!(Fraction::new(3, 5) => "a b c d e".to_string()),
+                is_bootstrapped: false,
             };
 
             let serialized = serde_json::to_string(&model).unwrap();
@@ -233,9 +329,23 @@ mod tests {
                 r#"{"language":"ENGLISH","ngrams":{"3/5":"a b c d e"}}"#
             );
 
-            let deserialized = serde_json::from_str::<JsonLanguageModel>(&serialized).unwrap();
+            let deserialized = serde_json::from_str::<LanguageModel>(&serialized).unwrap();
             assert_eq!(deserialized, model);
         }
+
+        #[test]
+        fn test_bootstrapped_json_model_is_recognized() {
+            assert!(LanguageModel::from_json(
+                r#"{"language":"ENGLISH","ngrams":{"3/5":"a b"},"bootstrapped":true}"#
+            )
+            .unwrap()
+            .is_bootstrapped());
+            assert!(
+                !LanguageModel::from_json(r#"{"language":"ENGLISH","ngrams":{"3/5":"a b"}}"#)
+                    .unwrap()
+                    .is_bootstrapped()
+            );
+        }
     }
 
     mod training_data {
@@ -486,10 +596,45 @@ mod tests {
                 language: Language::English,
                 absolute_frequencies: None,
                 relative_frequencies: Some(expected_unigram_relative_frequencies()),
+                is_bootstrapped: false,
             };
             let deserialized = TrainingDataLanguageModel::from_json(&model.to_json());
             assert_eq!(deserialized, expected_unigram_json_relative_frequencies());
         }
+
+        #[test]
+        fn test_model_interpolation() {
+            let seed_model = TrainingDataLanguageModel {
+                language: Language::Georgian,
+                absolute_frequencies: None,
+                relative_frequencies: Some(map_keys_to_ngrams_and_values_to_fractions(
+                    hashmap!("a" => "1/2", "b" => "1/2"),
+                )),
+                is_bootstrapped: false,
+            };
+            let related_language_model = AHashMap::from([
+                (CompactString::new("a"), 0.5),
+                (CompactString::new("c"), 0.5),
+            ]);
+
+            let model = TrainingDataLanguageModel::from_interpolation(
+                &seed_model,
+                &related_language_model,
+                0.2,
+            );
+
+            assert_eq!(model.language, Language::Georgian);
+            assert!(model.is_bootstrapped);
+            assert_eq!(
+                model.relative_frequencies,
+                Some(map_keys_to_ngrams_and_values_to_fractions(
+                    hashmap!("a" => "1/2", "b" => "2/5", "c" => "1/10")
+                ))
+            );
+            assert!(LanguageModel::from_json(&model.to_json())
+                .unwrap()
+                .is_bootstrapped());
+        }
     }
 
     mod test_data {
@@ -497,7 +642,7 @@ mod tests {
 
         use super::*;
 
-        fn map_strs_to_ngrams(strs: Vec<Vec<&'static str>>) -> Vec<Vec<NgramRef>> {
+        fn map_strs_to_ngrams(strs: Vec<Vec<&'static str>>) -> Vec<Vec<NgramRef<'static>>> {
             strs.iter()
                 .map(|ngram_strs| ngram_strs.iter().map(|&it| NgramRef::new(it)).collect())
                 .collect()
@@ -734,11 +879,41 @@ mod tests {
         )]
         fn test_ngram_model_creation(ngram_length: usize, expected_ngrams: Vec<Vec<NgramRef>>) {
             let words = split_text_into_words(TEXT);
-            let mut model = TestDataLanguageModel::from(&words, ngram_length);
-            model
+            let model = TestDataLanguageModel::from(&words, ngram_length);
+            let ngrams = model
                 .ngrams
-                .sort_by(|first, second| first[0].value.cmp(&second[0].value));
-            assert_eq!(model.ngrams, expected_ngrams);
+                .iter()
+                .map(|ngram| ngram.range_of_lower_order_ngrams().collect_vec())
+                .collect_vec();
+            assert_eq!(ngrams, expected_ngrams);
+        }
+
+        #[test]
+        fn test_ngram_models_of_all_lengths_are_created_in_single_pass() {
+            let words = split_text_into_words(TEXT);
+            let models = TestDataLanguageModel::from_all(&words, &[1, 3, 5]);
+            let expected_ngrams = [
+                expected_unigrams(),
+                expected_trigrams(),
+                expected_fivegrams(),
+            ];
+
+            assert_eq!(models.len(), 3);
+
+            for (model, expected_ngrams) in models.iter().zip(expected_ngrams) {
+                let ngrams = model
+                    .ngrams
+                    .iter()
+                    .map(|ngram| ngram.range_of_lower_order_ngrams().collect_vec())
+                    .collect_vec();
+                assert_eq!(ngrams, expected_ngrams);
+            }
+        }
+
+        #[test]
+        #[should_panic(expected = "ngram length 6 is not in range 1..6")]
+        fn assert_ngram_models_cannot_be_created_for_invalid_length() {
+            TestDataLanguageModel::from_all(&[], &[3, 6]);
         }
     }
 }
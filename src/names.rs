@@ -0,0 +1,290 @@
+/*
+ * Copyright © 2020-present Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+
+use strum::IntoEnumIterator;
+
+use crate::alphabet::Alphabet;
+use crate::language::Language;
+
+/// The position within a name token at which an orthographic marker must occur.
+#[derive(Clone, Copy)]
+enum MarkerPosition {
+    /// The marker is a complete token, such as the particle `van`.
+    Token,
+    /// The token starts with the marker, but is longer than it.
+    Prefix,
+    /// The token ends with the marker, but is longer than it.
+    Suffix,
+    /// The marker occurs anywhere within the token.
+    Infix,
+}
+
+use MarkerPosition::*;
+
+/// Orthographic markers of personal names and the languages whose naming traditions
+/// they are typical for. Languages are given by name so that markers of languages
+/// which are not compiled in are skipped.
+const NAME_MARKERS: &[(&str, MarkerPosition, &[&str])] = &[
+    ("van", Token, &["Dutch", "Afrikaans"]),
+    ("von", Token, &["German"]),
+    ("mac", Prefix, &["Irish", "English"]),
+    ("mc", Prefix, &["Irish", "English"]),
+    ("o'", Prefix, &["Irish"]),
+    ("al-", Prefix, &["Arabic"]),
+    ("ibn", Token, &["Arabic"]),
+    ("bin", Token, &["Arabic", "Malay"]),
+    ("binti", Token, &["Malay"]),
+    ("sson", Suffix, &["Swedish", "Icelandic"]),
+    ("dóttir", Suffix, &["Icelandic"]),
+    ("sen", Suffix, &["Danish", "Bokmal", "Nynorsk"]),
+    ("nen", Suffix, &["Finnish"]),
+    ("mann", Suffix, &["German"]),
+    ("stein", Suffix, &["German"]),
+    ("berg", Suffix, &["German", "Swedish"]),
+    ("ini", Suffix, &["Italian"]),
+    ("elli", Suffix, &["Italian"]),
+    ("etti", Suffix, &["Italian"]),
+    ("ucci", Suffix, &["Italian"]),
+    ("eiro", Suffix, &["Portuguese"]),
+    ("inho", Suffix, &["Portuguese"]),
+    ("ez", Suffix, &["Spanish"]),
+    ("escu", Suffix, &["Romanian"]),
+    ("eanu", Suffix, &["Romanian"]),
+    ("ski", Suffix, &["Polish"]),
+    ("ska", Suffix, &["Polish"]),
+    ("wicz", Suffix, &["Polish"]),
+    ("czyk", Suffix, &["Polish"]),
+    ("ský", Suffix, &["Czech", "Slovak"]),
+    ("ská", Suffix, &["Czech", "Slovak"]),
+    ("ová", Suffix, &["Czech", "Slovak"]),
+    ("ić", Suffix, &["Bosnian", "Croatian", "Serbian"]),
+    ("ič", Suffix, &["Slovene"]),
+    ("auskas", Suffix, &["Lithuanian"]),
+    ("aitis", Suffix, &["Lithuanian"]),
+    ("ienė", Suffix, &["Lithuanian"]),
+    ("oğlu", Suffix, &["Turkish", "Azerbaijani"]),
+    ("yan", Suffix, &["Armenian"]),
+    ("ian", Suffix, &["Armenian"]),
+    ("shvili", Suffix, &["Georgian"]),
+    ("dze", Suffix, &["Georgian"]),
+    ("poulos", Suffix, &["Greek"]),
+    ("akis", Suffix, &["Greek"]),
+    ("enko", Suffix, &["Ukrainian"]),
+    ("ов", Suffix, &["Russian", "Bulgarian"]),
+    ("ова", Suffix, &["Russian", "Bulgarian"]),
+    ("ев", Suffix, &["Russian", "Bulgarian"]),
+    ("ева", Suffix, &["Russian", "Bulgarian"]),
+    ("енко", Suffix, &["Ukrainian"]),
+    ("ук", Suffix, &["Ukrainian"]),
+    ("юк", Suffix, &["Ukrainian"]),
+    ("ски", Suffix, &["Bulgarian", "Macedonian"]),
+    ("ска", Suffix, &["Bulgarian", "Macedonian"]),
+    ("вич", Suffix, &["Belarusian", "Serbian"]),
+    ("sch", Infix, &["German"]),
+    ("sz", Infix, &["Polish", "Hungarian"]),
+    ("cz", Infix, &["Polish"]),
+    ("rz", Infix, &["Polish"]),
+    ("ij", Infix, &["Dutch"]),
+    ("aa", Infix, &["Danish", "Bokmal", "Nynorsk", "Dutch"]),
+    ("gy", Infix, &["Hungarian"]),
+    ("zs", Infix, &["Hungarian"]),
+    ("cs", Infix, &["Hungarian"]),
+    ("nh", Infix, &["Portuguese"]),
+    ("lh", Infix, &["Portuguese"]),
+    ("uu", Infix, &["Finnish", "Estonian"]),
+];
+
+/// Tokens of company names which denote a legal form and do not say anything about
+/// the linguistic origin of the name. They are compared without dots.
+const LEGAL_FORMS: &[&str] = &[
+    "ab", "ag", "as", "bv", "co", "corp", "gmbh", "inc", "kg", "llc", "ltd", "nv", "oy", "plc",
+    "sa", "sarl", "spa", "srl", "ооо", "оао", "зао",
+];
+
+/// Computes a score for each of the given languages which reflects how likely a personal
+/// or company name originates from it.
+///
+/// Names are too short and too irregular for ngram models trained on prose. Instead, this
+/// character-origin model combines the evidence of the name's characters, of orthographic
+/// markers typical for the naming traditions of a language, such as the suffix `-sson`,
+/// and of the alphabets the name is written in. Languages which do not use all of these
+/// alphabets are ruled out. If there is no evidence other than the alphabets,
+/// all remaining languages receive the same score.
+pub(crate) fn compute_name_origin_scores(
+    name: &str,
+    languages: &HashSet<Language>,
+) -> HashMap<Language, f64> {
+    let tokens = split_name_into_tokens(name);
+    let alphabets = tokens
+        .iter()
+        .flat_map(|token| token.chars())
+        .filter_map(|ch| Alphabet::iter().find(|alphabet| alphabet.matches_char(ch)))
+        .collect::<HashSet<_>>();
+
+    if alphabets.is_empty() {
+        return HashMap::new();
+    }
+
+    let candidates = languages
+        .iter()
+        .filter(|language| language.alphabets().is_superset(&alphabets))
+        .copied()
+        .collect::<HashSet<_>>();
+
+    let mut scores = candidates
+        .iter()
+        .map(|language| (*language, 0.0))
+        .collect::<HashMap<_, _>>();
+
+    let mut add_evidence = |evidence_languages: HashSet<Language>| {
+        let matching_languages = evidence_languages
+            .intersection(&candidates)
+            .collect::<Vec<_>>();
+
+        for language in matching_languages.iter() {
+            *scores.get_mut(language).unwrap() += 1.0 / matching_languages.len() as f64;
+        }
+    };
+
+    let characters = tokens
+        .iter()
+        .flat_map(|token| token.chars())
+        .filter(|ch| ch.is_alphabetic())
+        .collect::<HashSet<_>>();
+
+    for ch in characters {
+        add_evidence(Language::languages_with_character(ch));
+    }
+
+    for (marker, position, marker_languages) in NAME_MARKERS {
+        if tokens
+            .iter()
+            .any(|token| matches_marker(token, marker, *position, tokens.len()))
+        {
+            add_evidence(
+                marker_languages
+                    .iter()
+                    .filter_map(|it| Language::from_str(it).ok())
+                    .collect(),
+            );
+        }
+    }
+
+    if scores.values().all(|score| *score == 0.0) {
+        for score in scores.values_mut() {
+            *score = 1.0;
+        }
+    }
+
+    scores
+}
+
+/// Splits the given name into lowercase tokens without surrounding punctuation.
+/// Initials and legal forms are removed unless the name consists of nothing else.
+fn split_name_into_tokens(name: &str) -> Vec<String> {
+    let tokens = name
+        .split_whitespace()
+        .map(|token| {
+            token
+                .trim_matches(|ch: char| !ch.is_alphabetic() && ch != '\'' && ch != '-')
+                .to_lowercase()
+        })
+        .filter(|token| token.chars().any(|ch| ch.is_alphabetic()))
+        .collect::<Vec<_>>();
+
+    let significant_tokens = tokens
+        .iter()
+        .filter(|token| {
+            let letters = token.replace('.', "");
+            letters.chars().count() > 1 && !LEGAL_FORMS.contains(&letters.as_str())
+        })
+        .cloned()
+        .collect::<Vec<_>>();
+
+    if significant_tokens.is_empty() {
+        tokens
+    } else {
+        significant_tokens
+    }
+}
+
+fn matches_marker(token: &str, marker: &str, position: MarkerPosition, token_count: usize) -> bool {
+    match position {
+        Token => token_count > 1 && token == marker,
+        Prefix => token.len() > marker.len() && token.starts_with(marker),
+        Suffix => token.len() > marker.len() && token.ends_with(marker),
+        Infix => token.contains(marker),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::language::Language::*;
+    use rstest::*;
+
+    fn most_likely_origin(name: &str, languages: &HashSet<Language>) -> Option<Language> {
+        let scores = compute_name_origin_scores(name, languages);
+        let maximum_score = scores.values().copied().fold(0.0, f64::max);
+        let best_languages = scores
+            .into_iter()
+            .filter(|(_, score)| *score == maximum_score)
+            .map(|(language, _)| language)
+            .collect::<Vec<_>>();
+
+        if best_languages.len() == 1 {
+            Some(best_languages[0])
+        } else {
+            None
+        }
+    }
+
+    #[rstest(
+        name,
+        expected_origin,
+        case("Søren Kierkegaard", Some(Danish)),
+        case("Ñandú S.A.", Some(Spanish)),
+        case("Anna Karlsson", Some(Swedish)),
+        case("J. R. Wójtowicz", Some(Polish)),
+        case("Sergei Ivanov", None),
+        case("Оксана Шевченко", Some(Ukrainian)),
+        case("Жанна", None),
+        case("ნინო", Some(Georgian))
+    )]
+    fn assert_name_origin_is_determined_correctly(name: &str, expected_origin: Option<Language>) {
+        let languages = hashset!(
+            Danish, English, Georgian, German, Polish, Russian, Spanish, Swedish, Ukrainian
+        );
+        assert_eq!(most_likely_origin(name, &languages), expected_origin);
+    }
+
+    #[test]
+    fn assert_languages_with_other_alphabets_are_ruled_out() {
+        let scores = compute_name_origin_scores("Жанна", &hashset!(English, Russian, Ukrainian));
+
+        assert_eq!(scores, hashmap!(Russian => 1.0, Ukrainian => 1.0));
+        assert!(compute_name_origin_scores("1234", &hashset!(English)).is_empty());
+    }
+
+    #[test]
+    fn assert_initials_and_legal_forms_are_removed() {
+        assert_eq!(split_name_into_tokens("J. R. Wójcik GmbH"), vec!["wójcik"]);
+        assert_eq!(split_name_into_tokens("S.A."), vec!["s.a"]);
+    }
+}
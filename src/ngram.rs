@@ -20,6 +20,8 @@ use std::fmt::Display;
 use serde::de::{Error, Visitor};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
+use crate::detector::split_text_into_words_by_default;
+
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub(crate) struct Ngram {
     pub(crate) value: String,
@@ -108,7 +110,7 @@ impl<'a> Iterator for NgramRefRange<'a> {
     type Item = NgramRef<'a>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let last_ch = self.start.value.chars().rev().next()?;
+        let last_ch = self.start.value.chars().next_back()?;
         let result = self.start;
         self.start.value = &self.start.value[..self.start.value.len() - last_ch.len_utf8()];
         self.start.char_count -= 1;
@@ -116,6 +118,118 @@ impl<'a> Iterator for NgramRefRange<'a> {
     }
 }
 
+/// This struct is an iterator over the ngrams of a given order that the
+/// [LanguageDetector](crate::LanguageDetector) extracts from an input text.
+///
+/// It is created by the function [ngrams].
+pub struct Ngrams {
+    words: Vec<String>,
+    ngram_length: usize,
+    word_index: usize,
+    char_index: usize,
+    // The byte offsets of the characters of the current word, followed by its length
+    char_boundaries: Vec<usize>,
+}
+
+impl Ngrams {
+    fn compute_char_boundaries(&mut self) {
+        self.char_boundaries.clear();
+        if let Some(word) = self.words.get(self.word_index) {
+            self.char_boundaries
+                .extend(word.char_indices().map(|(index, _)| index));
+            self.char_boundaries.push(word.len());
+        }
+    }
+}
+
+impl Iterator for Ngrams {
+    type Item = String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(word) = self.words.get(self.word_index) {
+            if self.char_index + self.ngram_length < self.char_boundaries.len() {
+                let start = self.char_boundaries[self.char_index];
+                let end = self.char_boundaries[self.char_index + self.ngram_length];
+                self.char_index += 1;
+                return Some(word[start..end].to_string());
+            }
+            self.word_index += 1;
+            self.char_index = 0;
+            self.compute_char_boundaries();
+        }
+        None
+    }
+}
+
+/// This struct is an iterator over an ngram and all of its lower-order ngrams.
+///
+/// It is created by the function [lower_order_ngrams].
+pub struct LowerOrderNgrams<'a> {
+    range: NgramRefRange<'a>,
+}
+
+impl<'a> Iterator for LowerOrderNgrams<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.range.next().map(|ngram| ngram.value)
+    }
+}
+
+/// Returns an iterator over all ngrams of length `ngram_length` that the
+/// [LanguageDetector](crate::LanguageDetector) extracts from the given text.
+///
+/// The text is split into lowercase words in exactly the same way as during
+/// language detection with the default configuration, so words mixing letters of
+/// several alphabets are split into one word per alphabet. The ngrams are then built
+/// from each word separately, so that no ngram spans the boundary between two words.
+/// They are returned in the order of their occurrence, duplicates included.
+///
+/// ```
+/// use lingua::ngrams;
+///
+/// let trigrams = ngrams("Hello, world!", 3).collect::<Vec<_>>();
+///
+/// assert_eq!(trigrams, vec!["hel", "ell", "llo", "wor", "orl", "rld"]);
+/// ```
+///
+/// ⚠ Panics if `ngram_length` is not in range 1..6.
+pub fn ngrams(text: &str, ngram_length: usize) -> Ngrams {
+    if !(1..6).contains(&ngram_length) {
+        panic!("ngram length {ngram_length} is not in range 1..6");
+    }
+    let mut ngrams = Ngrams {
+        words: split_text_into_words_by_default(text),
+        ngram_length,
+        word_index: 0,
+        char_index: 0,
+        char_boundaries: vec![],
+    };
+    ngrams.compute_char_boundaries();
+    ngrams
+}
+
+/// Returns an iterator over the given ngram and all of its lower-order ngrams,
+/// starting with the ngram itself and ending with its first character.
+///
+/// This is the order in which the [LanguageDetector](crate::LanguageDetector)
+/// backs off to lower-order ngrams if an ngram is not contained in a language model.
+///
+/// ```
+/// use lingua::lower_order_ngrams;
+///
+/// let ngrams = lower_order_ngrams("alter").collect::<Vec<_>>();
+///
+/// assert_eq!(ngrams, vec!["alter", "alte", "alt", "al", "a"]);
+/// ```
+///
+/// ⚠ Panics if `ngram` consists of more than five characters.
+pub fn lower_order_ngrams(ngram: &str) -> LowerOrderNgrams<'_> {
+    LowerOrderNgrams {
+        range: NgramRef::new(ngram).range_of_lower_order_ngrams(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -144,4 +258,42 @@ mod tests {
         assert_eq!(range.next(), Some(NgramRef::new("ä")));
         assert_eq!(range.next(), None);
     }
+
+    #[test]
+    fn test_ngrams() {
+        let unigrams = ngrams("Äb 12 cd", 1).collect::<Vec<_>>();
+        assert_eq!(unigrams, vec!["ä", "b", "c", "d"]);
+
+        let bigrams = ngrams("Äbc äbc", 2).collect::<Vec<_>>();
+        assert_eq!(bigrams, vec!["äb", "bc", "äb", "bc"]);
+
+        let fivegrams = ngrams("Äbcd efghij", 5).collect::<Vec<_>>();
+        assert_eq!(fivegrams, vec!["efghi", "fghij"]);
+
+        let trigrams = ngrams("Straße äöü", 3).collect::<Vec<_>>();
+        assert_eq!(trigrams, vec!["str", "tra", "raß", "aße", "äöü"]);
+
+        assert_eq!(ngrams("", 3).next(), None);
+    }
+
+    #[test]
+    fn assert_ngrams_of_mixed_alphabet_words_do_not_span_alphabets() {
+        let bigrams = ngrams("iPhoneпривет", 2).collect::<Vec<_>>();
+        assert_eq!(
+            bigrams,
+            vec!["ip", "ph", "ho", "on", "ne", "пр", "ри", "ив", "ве", "ет"]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "ngram length 6 is not in range 1..6")]
+    fn assert_ngrams_cannot_be_created_for_invalid_length() {
+        ngrams("abcdef", 6);
+    }
+
+    #[test]
+    fn test_lower_order_ngrams() {
+        let ngrams = lower_order_ngrams("äbcde").collect::<Vec<_>>();
+        assert_eq!(ngrams, vec!["äbcde", "äbcd", "äbc", "äb", "ä"]);
+    }
 }
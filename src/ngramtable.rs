@@ -0,0 +1,193 @@
+/*
+ * Copyright © 2020-present Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::mem::size_of;
+
+use ahash::{AHashMap, RandomState};
+use compact_str::CompactString;
+
+const EMPTY_SLOT: u32 = u32::MAX;
+const PROBABILITY_INDEX_BITS: u32 = 24;
+const PROBABILITY_INDEX_MASK: u32 = (1 << PROBABILITY_INDEX_BITS) - 1;
+
+/// This struct stores the ngram probabilities of a single language model in a compact,
+/// read-only hash table keyed by the bytes of the ngrams.
+///
+/// The ngrams are stored back to back in a single byte buffer, each one prefixed
+/// with its length. Many ngrams share the same probability, so the distinct
+/// probabilities are stored only once and referenced by their index. Each slot of
+/// the open-addressing table consists of the ngram's offset within the byte buffer
+/// and the probability index, whose upper bits hold a tag taken from the ngram's
+/// hash. Probing compares the tags first, so the byte buffer is only accessed
+/// for likely matches. Compared to an `AHashMap<CompactString, f64>`, a model
+/// occupies roughly half of the memory.
+pub(crate) struct NgramTable {
+    slots: Box<[(u32, u32)]>,
+    ngrams: Box<[u8]>,
+    probabilities: Box<[f64]>,
+    hasher: RandomState,
+}
+
+impl NgramTable {
+    /// Returns the probability of the given ngram or [None] if it is not part of the model.
+    #[inline]
+    pub(crate) fn get(&self, ngram: &str) -> Option<f64> {
+        if self.slots.is_empty() {
+            return None;
+        }
+
+        let ngram = ngram.as_bytes();
+        let hash = self.hasher.hash_one(ngram);
+        let tag = hash_tag(hash);
+        let mask = self.slots.len() - 1;
+        let mut slot = hash as usize & mask;
+
+        loop {
+            let (offset, tagged_index) = self.slots[slot];
+            if offset == EMPTY_SLOT {
+                return None;
+            }
+            if tagged_index >> PROBABILITY_INDEX_BITS == tag {
+                let start = offset as usize + 1;
+                let end = start + self.ngrams[offset as usize] as usize;
+                if &self.ngrams[start..end] == ngram {
+                    let index = tagged_index & PROBABILITY_INDEX_MASK;
+                    return Some(self.probabilities[index as usize]);
+                }
+            }
+            slot = (slot + 1) & mask;
+        }
+    }
+
+    pub(crate) fn contains_key(&self, ngram: &str) -> bool {
+        self.get(ngram).is_some()
+    }
+
+    /// Returns the number of bytes occupied by the table.
+    pub(crate) fn memory_usage(&self) -> usize {
+        self.slots.len() * size_of::<(u32, u32)>()
+            + self.ngrams.len()
+            + self.probabilities.len() * size_of::<f64>()
+    }
+}
+
+impl From<AHashMap<CompactString, f64>> for NgramTable {
+    fn from(model: AHashMap<CompactString, f64>) -> Self {
+        let mut probabilities = model.values().copied().collect::<Vec<_>>();
+        probabilities.sort_by(f64::total_cmp);
+        probabilities.dedup();
+
+        if probabilities.len() > PROBABILITY_INDEX_MASK as usize {
+            panic!("language model contains too many distinct probabilities");
+        }
+
+        // The capacity keeps the load factor at 0.75 at most, so probe sequences stay short.
+        let capacity = if model.is_empty() {
+            0
+        } else {
+            (model.len() * 4 / 3 + 1).next_power_of_two()
+        };
+        let hasher = RandomState::with_seeds(
+            0x243f6a8885a308d3,
+            0x13198a2e03707344,
+            0xa4093822299f31d0,
+            0x082efa98ec4e6c89,
+        );
+        let mut slots = vec![(EMPTY_SLOT, 0); capacity];
+        let mut ngrams = Vec::with_capacity(model.keys().map(|ngram| ngram.len() + 1).sum());
+
+        for (ngram, probability) in model.iter() {
+            let offset = u32::try_from(ngrams.len())
+                .expect("language model is too large to be stored in an ngram table");
+            ngrams.push(u8::try_from(ngram.len()).expect("ngram is too long"));
+            ngrams.extend_from_slice(ngram.as_bytes());
+
+            let index = probabilities
+                .binary_search_by(|it| it.total_cmp(probability))
+                .unwrap() as u32;
+            let hash = hasher.hash_one(ngram.as_bytes());
+            let mut slot = hash as usize & (capacity - 1);
+
+            while slots[slot].0 != EMPTY_SLOT {
+                slot = (slot + 1) & (capacity - 1);
+            }
+            slots[slot] = (offset, index | hash_tag(hash) << PROBABILITY_INDEX_BITS);
+        }
+
+        Self {
+            slots: slots.into_boxed_slice(),
+            ngrams: ngrams.into_boxed_slice(),
+            probabilities: probabilities.into_boxed_slice(),
+            hasher,
+        }
+    }
+}
+
+fn hash_tag(hash: u64) -> u32 {
+    (hash >> (64 - (32 - PROBABILITY_INDEX_BITS))) as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn assert_ngram_probabilities_are_looked_up_correctly() {
+        let model = AHashMap::from([
+            (CompactString::from("a"), 0.25),
+            (CompactString::from("ab"), 0.5),
+            (CompactString::from("abc"), 0.25),
+            (CompactString::from("ни"), 0.125),
+            (CompactString::from("日本"), 0.75),
+        ]);
+        let table = NgramTable::from(model.clone());
+
+        for (ngram, probability) in model.iter() {
+            assert_eq!(table.get(ngram), Some(*probability));
+        }
+        assert_eq!(table.get("b"), None);
+        assert_eq!(table.get("abcd"), None);
+        assert_eq!(table.get(""), None);
+        assert!(table.contains_key("ни"));
+        assert!(!table.contains_key("на"));
+        assert_eq!(table.probabilities.len(), 4);
+    }
+
+    #[test]
+    fn assert_empty_table_does_not_contain_ngrams() {
+        let table = NgramTable::from(AHashMap::new());
+
+        assert_eq!(table.get("a"), None);
+        assert_eq!(table.memory_usage(), 0);
+    }
+
+    #[test]
+    fn assert_table_is_smaller_than_hash_map() {
+        let model = (0..10_000)
+            .map(|i| {
+                (
+                    CompactString::from(format!("{i:05}")),
+                    (i % 100) as f64 / 100.0,
+                )
+            })
+            .collect::<AHashMap<_, _>>();
+        let hash_map_bytes = model.capacity() * (size_of::<CompactString>() + size_of::<f64>() + 1);
+        let table = NgramTable::from(model.clone());
+
+        assert!(table.memory_usage() * 2 < hash_map_bytes);
+        assert!(model.iter().all(|(ngram, p)| table.get(ngram) == Some(*p)));
+    }
+}
@@ -0,0 +1,69 @@
+/*
+ * Copyright © 2020-present Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use unicode_normalization::UnicodeNormalization;
+
+/// This enum specifies the Unicode normalization form the input text of a
+/// [LanguageDetector](crate::LanguageDetector) is brought into before its language is detected,
+/// see [LanguageDetectorBuilder::with_unicode_normalization](crate::LanguageDetectorBuilder::with_unicode_normalization).
+///
+/// The language models and the characters unique to certain languages consist of precomposed
+/// characters. Text typed on some platforms or copied from some sources contains
+/// decomposed characters instead, i.e. base letters followed by combining marks, which
+/// neither match the models nor the rules of the rule-based filter engine.
+///
+/// ```
+/// use lingua::NormalizationForm;
+///
+/// assert_eq!(NormalizationForm::Nfc.normalize("Vie\u{0323}\u{0302}t"), "Việt");
+/// assert_eq!(NormalizationForm::Nfkc.normalize("ﬁle"), "file");
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum NormalizationForm {
+    /// Canonical composition, which composes base letters and combining marks into
+    /// precomposed characters, such as Vietnamese letters or Hangul syllables.
+    Nfc,
+    /// Compatibility composition, which additionally replaces compatibility characters
+    /// by their canonical equivalents, such as ligatures, fullwidth letters or
+    /// circled letters by the respective letters.
+    Nfkc,
+}
+
+impl NormalizationForm {
+    /// Returns the given text brought into this normalization form.
+    pub fn normalize(&self, text: &str) -> String {
+        match self {
+            NormalizationForm::Nfc => text.nfc().collect(),
+            NormalizationForm::Nfkc => text.nfkc().collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn assert_text_is_normalized_correctly() {
+        let decomposed_hangul = "\u{1100}\u{1161}\u{11A8}";
+
+        assert_eq!(NormalizationForm::Nfc.normalize(decomposed_hangul), "각");
+        assert_eq!(NormalizationForm::Nfkc.normalize(decomposed_hangul), "각");
+        assert_eq!(NormalizationForm::Nfc.normalize("ｈｅｌｌｏ"), "ｈｅｌｌｏ");
+        assert_eq!(NormalizationForm::Nfkc.normalize("ｈｅｌｌｏ"), "hello");
+        assert_eq!(NormalizationForm::Nfc.normalize("ﬁle"), "ﬁle");
+    }
+}
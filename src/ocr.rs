@@ -0,0 +1,91 @@
+/*
+ * Copyright © 2020-present Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use ahash::AHashMap;
+use compact_str::CompactString;
+
+/// Maps the Cyrillic letters whose distinctions are typically lost when scanned all-caps
+/// text is recognized to the letters they are confused with. The diacritics of `Ё`, `Ї`
+/// and `Ў` are too small to survive, and `І` is frequently read as `И` in old typefaces.
+fn fold_cyrillic_letter(ch: char) -> char {
+    match ch {
+        'ё' => 'е',
+        'і' | 'ї' => 'и',
+        'ў' => 'у',
+        _ => ch,
+    }
+}
+
+/// Folds the Cyrillic letters of the given lowercase word which cannot be told apart
+/// reliably in OCR output of all-caps text, see [fold_cyrillic_letter].
+pub(crate) fn fold_cyrillic_letters(word: &str) -> String {
+    word.chars().map(fold_cyrillic_letter).collect()
+}
+
+/// Folds the ngrams of the given language model like [fold_cyrillic_letters] does.
+/// The probabilities of ngrams which become equal are summed up. This is exact for ngrams
+/// which only differ in their last letter and an approximation otherwise, so the sums
+/// are capped at 1.0.
+pub(crate) fn fold_language_model(
+    model: AHashMap<CompactString, f64>,
+) -> AHashMap<CompactString, f64> {
+    let mut folded_model = AHashMap::with_capacity(model.len());
+
+    for (ngram, probability) in model {
+        let folded_ngram = if ngram.chars().any(|ch| fold_cyrillic_letter(ch) != ch) {
+            CompactString::from(fold_cyrillic_letters(&ngram))
+        } else {
+            ngram
+        };
+        let folded_probability = folded_model.entry(folded_ngram).or_insert(0.0);
+        *folded_probability = f64::min(*folded_probability + probability, 1.0);
+    }
+
+    folded_model
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn assert_cyrillic_letters_are_folded() {
+        assert_eq!(fold_cyrillic_letters("її"), "ии");
+        assert_eq!(fold_cyrillic_letters("ёлка"), "елка");
+        assert_eq!(
+            fold_cyrillic_letters("беларускі мова ўсё"),
+            "беларуски мова усе"
+        );
+        assert_eq!(fold_cyrillic_letters("hello"), "hello");
+    }
+
+    #[test]
+    fn assert_language_model_is_folded() {
+        let model = AHashMap::from([
+            (CompactString::from("ні"), 0.25),
+            (CompactString::from("ни"), 0.5),
+            (CompactString::from("ніч"), 0.75),
+            (CompactString::from("нич"), 0.5),
+            (CompactString::from("на"), 0.125),
+        ]);
+        let folded_model = fold_language_model(model);
+
+        assert_eq!(folded_model.len(), 3);
+        assert_eq!(folded_model.get("ни"), Some(&0.75));
+        assert_eq!(folded_model.get("нич"), Some(&1.0));
+        assert_eq!(folded_model.get("на"), Some(&0.125));
+    }
+}
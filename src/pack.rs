@@ -0,0 +1,582 @@
+/*
+ * Copyright © 2020-present Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+
+use ahash::AHashMap;
+use compact_str::CompactString;
+use once_cell::sync::Lazy;
+use strum::IntoEnumIterator;
+use strum_macros::EnumIter;
+
+use crate::binary::{is_bootstrapped_binary, load_binary_model};
+use crate::detector::unload_model_pack_language_models;
+use crate::json::{decompress, decompress_json, load_language_model};
+use crate::language::Language;
+use crate::model::{LanguageModel, TrainingDataLanguageModel};
+use crate::ngram::Ngram;
+
+/// The language models registered for a model pack, keyed by pack and language.
+type ModelPackDirectoryMap = RwLock<HashMap<(ModelPack, Language), CustomModels>>;
+
+/// The language models of a single language which are used instead of the bundled ones,
+/// either registered for a model pack or passed to a
+/// [LanguageDetectorBuilder](crate::LanguageDetectorBuilder).
+#[derive(Clone)]
+pub(crate) struct CustomModels {
+    source: ModelSource,
+    is_bootstrapped: bool,
+}
+
+/// The location the registered models of a language are read from.
+#[derive(Clone)]
+enum ModelSource {
+    Directory(PathBuf),
+    /// The Brotli-compressed binary models keyed by ngram length.
+    Memory(HashMap<usize, Arc<Vec<u8>>>),
+}
+
+const BINARY_FILE_EXTENSION: &str = "bin.br";
+const JSON_FILE_EXTENSION: &str = "json.br";
+
+static MODEL_PACK_DIRECTORIES: Lazy<ModelPackDirectoryMap> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+/// This enum specifies the sets of language models a
+/// [LanguageDetector](crate::LanguageDetector) can be built with.
+///
+/// The bundled language models have been trained on news articles. They measurably
+/// underperform on informal or very short texts such as social media posts or product
+/// titles. Models trained on corpora of such a domain can be created with
+/// [LanguageModelFilesWriter](crate::LanguageModelFilesWriter) and registered for
+/// [ModelPack::Custom] with [ModelPack::register_models]. The pack does not need to
+/// provide models for every language: languages without registered models fall back
+/// to the bundled models, so models can be mixed per language.
+///
+/// A model pack is selected with
+/// [LanguageDetectorBuilder::with_model_pack](crate::LanguageDetectorBuilder::with_model_pack).
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq, EnumIter)]
+pub enum ModelPack {
+    /// The language models bundled with this library, trained on news articles.
+    #[default]
+    News,
+    /// Language models registered for individual languages with [ModelPack::register_models]
+    /// or [ModelPack::register_model_bytes], such as models of a specific domain.
+    Custom,
+}
+
+impl ModelPack {
+    /// Registers the language model files in the given directory as the models
+    /// of the given language within this pack.
+    ///
+    /// The directory must have the layout produced by
+    /// [LanguageModelFilesWriter::create_and_write_language_model_files](crate::LanguageModelFilesWriter::create_and_write_language_model_files),
+    /// i.e. it must contain the file `trigrams.json.br` or `trigrams.bin.br` and may contain
+    /// the files for the other ngram lengths, see [encode_binary_model](crate::encode_binary_model)
+    /// for the binary format. This is the layout of the directories `models` of the
+    /// bundled language model crates as well. A previous registration for the same language
+    /// is replaced and models of the language already loaded for this pack are discarded,
+    /// so that all detectors using this pack load the newly registered models on next use.
+    ///
+    /// Returns an error of kind [ErrorKind::InvalidInput] for [ModelPack::News],
+    /// whose models cannot be replaced, an error of kind [ErrorKind::NotFound]
+    /// if the directory does not contain a trigram model, and an error of kind
+    /// [ErrorKind::InvalidData] if the trigram model is not a valid model. Models of other
+    /// ngram lengths which are not valid fail with [ErrorKind::InvalidData] when they are loaded.
+    pub fn register_models<P: AsRef<Path>>(
+        &self,
+        language: Language,
+        directory: P,
+    ) -> io::Result<()> {
+        if *self == ModelPack::News {
+            return Err(io::Error::new(
+                ErrorKind::InvalidInput,
+                "The bundled news models cannot be replaced",
+            ));
+        }
+
+        let models = CustomModels::from_directory(directory.as_ref())?;
+
+        MODEL_PACK_DIRECTORIES
+            .write()
+            .unwrap()
+            .insert((*self, language), models);
+
+        unload_model_pack_language_models(*self, language);
+
+        Ok(())
+    }
+
+    /// Registers the model of the given language and ngram length within this pack from
+    /// the given bytes, which must be the contents of a Brotli-compressed binary model file
+    /// such as `trigrams.bin.br`, see [encode_binary_model](crate::encode_binary_model).
+    ///
+    /// This allows to use models which are not available as files, e.g. models fetched
+    /// over the network by a WebAssembly module. The trigram model must be registered
+    /// first. Registering it replaces a previous registration for the same language,
+    /// the models of the other ngram lengths are added to it. Models of the language
+    /// already loaded for this pack are discarded, as with [ModelPack::register_models].
+    ///
+    /// Returns an error of kind [ErrorKind::InvalidInput] for [ModelPack::News], whose models
+    /// cannot be replaced, or for an ngram length other than 1 to 5, an error of kind
+    /// [ErrorKind::NotFound] if no trigram model has been registered from bytes before,
+    /// and an error of kind [ErrorKind::InvalidData] if the bytes are not a valid model.
+    pub fn register_model_bytes(
+        &self,
+        language: Language,
+        ngram_length: usize,
+        bytes: Vec<u8>,
+    ) -> io::Result<()> {
+        if *self == ModelPack::News {
+            return Err(io::Error::new(
+                ErrorKind::InvalidInput,
+                "The bundled news models cannot be replaced",
+            ));
+        }
+        let mut directories = MODEL_PACK_DIRECTORIES.write().unwrap();
+        let key = (*self, language);
+        let models =
+            CustomModels::with_model_bytes(directories.get(&key), language, ngram_length, bytes)?;
+        directories.insert(key, models);
+
+        drop(directories);
+        unload_model_pack_language_models(*self, language);
+
+        Ok(())
+    }
+
+    /// Returns the languages for which this pack provides its own models.
+    /// All other languages fall back to the bundled models.
+    pub fn languages(&self) -> HashSet<Language> {
+        if *self == ModelPack::News {
+            return Language::iter().collect();
+        }
+
+        MODEL_PACK_DIRECTORIES
+            .read()
+            .unwrap()
+            .keys()
+            .filter(|(pack, _)| pack == self)
+            .map(|(_, language)| *language)
+            .collect()
+    }
+
+    /// Returns the languages for which this pack provides bootstrapped models created with
+    /// [LanguageModelFilesWriter::create_and_write_bootstrapped_language_model_files](crate::LanguageModelFilesWriter::create_and_write_bootstrapped_language_model_files).
+    /// Such models have not been trained on a proper corpus, so detection results
+    /// for these languages should be regarded as low-confidence.
+    pub fn bootstrapped_languages(&self) -> HashSet<Language> {
+        MODEL_PACK_DIRECTORIES
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|((pack, _), models)| pack == self && models.is_bootstrapped)
+            .map(|((_, language), _)| *language)
+            .collect()
+    }
+}
+
+impl CustomModels {
+    /// Reads the language model files in the given directory, see
+    /// [ModelPack::register_models] for the expected layout. Only the trigram model is read
+    /// at this point, the models are loaded when they are used for the first time.
+    ///
+    /// Returns an error of kind [ErrorKind::NotFound] if the directory
+    /// does not contain a trigram model and an error of kind [ErrorKind::InvalidData]
+    /// if the trigram model is not a valid model.
+    pub(crate) fn from_directory(directory: &Path) -> io::Result<Self> {
+        match read_trigram_model_bootstrapping(directory)? {
+            Some(is_bootstrapped) => Ok(Self {
+                source: ModelSource::Directory(directory.to_path_buf()),
+                is_bootstrapped,
+            }),
+            None => Err(io::Error::new(
+                ErrorKind::NotFound,
+                format!("No trigram model found in {}", directory.display()),
+            )),
+        }
+    }
+
+    /// Returns the given models of the given language extended by the model of the given
+    /// ngram length in the given bytes, see [ModelPack::register_model_bytes] for the
+    /// expected format. A trigram model replaces the given models.
+    ///
+    /// Returns an error of kind [ErrorKind::InvalidInput] for an ngram length other than 1 to 5,
+    /// an error of kind [ErrorKind::NotFound] if the given models do not stem from bytes,
+    /// and an error of kind [ErrorKind::InvalidData] if the bytes are not a valid model.
+    pub(crate) fn with_model_bytes(
+        models: Option<&Self>,
+        language: Language,
+        ngram_length: usize,
+        bytes: Vec<u8>,
+    ) -> io::Result<Self> {
+        if !(1..=5).contains(&ngram_length) {
+            return Err(io::Error::new(
+                ErrorKind::InvalidInput,
+                format!("Ngram length {ngram_length} is not in range 1..=5"),
+            ));
+        }
+
+        let model = decompress(&bytes)?;
+        load_binary_model(&model)?;
+
+        if ngram_length == 3 {
+            return Ok(Self {
+                source: ModelSource::Memory(hashmap!(3 => Arc::new(bytes))),
+                is_bootstrapped: is_bootstrapped_binary(&model),
+            });
+        }
+
+        match models {
+            Some(Self {
+                source: ModelSource::Memory(models),
+                is_bootstrapped,
+            }) => {
+                let mut models = models.clone();
+                models.insert(ngram_length, Arc::new(bytes));
+                Ok(Self {
+                    source: ModelSource::Memory(models),
+                    is_bootstrapped: *is_bootstrapped,
+                })
+            }
+            _ => Err(io::Error::new(
+                ErrorKind::NotFound,
+                format!("No trigram model registered from bytes for {language}"),
+            )),
+        }
+    }
+
+    /// Loads the ngram probabilities of the given language and ngram length.
+    /// Binary model files take precedence over JSON ones.
+    pub(crate) fn load(
+        &self,
+        language: Language,
+        ngram_length: usize,
+    ) -> io::Result<AHashMap<CompactString, f64>> {
+        let directory = match &self.source {
+            ModelSource::Directory(directory) => directory,
+            ModelSource::Memory(models) => {
+                return match models.get(&ngram_length) {
+                    Some(bytes) => load_binary_model(&decompress(bytes)?),
+                    None => Err(io::Error::new(
+                        ErrorKind::NotFound,
+                        format!(
+                            "No {} model registered for {language}",
+                            Ngram::find_ngram_name_by_length(ngram_length)
+                        ),
+                    )),
+                }
+            }
+        };
+
+        let binary_file_path = directory.join(model_file_name(ngram_length, BINARY_FILE_EXTENSION));
+
+        if binary_file_path.is_file() {
+            load_binary_model(&decompress(&fs::read(binary_file_path)?)?)
+        } else {
+            let json_file_path = directory.join(model_file_name(ngram_length, JSON_FILE_EXTENSION));
+            let json = decompress_json(&fs::read(json_file_path)?)?;
+            LanguageModel::from_json(&json).map(TrainingDataLanguageModel::from_language_model)
+        }
+    }
+}
+
+/// Loads the ngram probabilities of the given language and ngram length from the given pack,
+/// falling back to the bundled model if the pack does not provide models for the language.
+pub(crate) fn load_model_pack_language_model(
+    pack: ModelPack,
+    language: Language,
+    ngram_length: usize,
+) -> io::Result<AHashMap<CompactString, f64>> {
+    // The models are cloned, so that the lock is not held while loading the model.
+    let models = MODEL_PACK_DIRECTORIES
+        .read()
+        .unwrap()
+        .get(&(pack, language))
+        .cloned();
+
+    match models {
+        Some(models) => models.load(language, ngram_length),
+        None => load_language_model(language, ngram_length),
+    }
+}
+
+/// Returns whether the trigram model in the given directory is bootstrapped,
+/// or [None] if the directory does not contain a trigram model.
+fn read_trigram_model_bootstrapping(directory: &Path) -> io::Result<Option<bool>> {
+    let binary_file_path = directory.join(model_file_name(3, BINARY_FILE_EXTENSION));
+    let json_file_path = directory.join(model_file_name(3, JSON_FILE_EXTENSION));
+
+    if binary_file_path.is_file() {
+        let bytes = decompress(&fs::read(binary_file_path)?)?;
+        Ok(Some(is_bootstrapped_binary(&bytes)))
+    } else if json_file_path.is_file() {
+        let json = decompress_json(&fs::read(json_file_path)?)?;
+        Ok(Some(LanguageModel::from_json(&json)?.is_bootstrapped()))
+    } else {
+        Ok(None)
+    }
+}
+
+fn model_file_name(ngram_length: usize, file_extension: &str) -> String {
+    format!(
+        "{}s.{file_extension}",
+        Ngram::find_ngram_name_by_length(ngram_length)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::binary::encode_binary_model;
+    use crate::model::LanguageModel;
+    use crate::writer::LanguageModelFilesWriter;
+    use brotli::CompressorWriter;
+    use std::fs::File;
+    use std::io::Write;
+    use tempfile::{tempdir, NamedTempFile};
+
+    #[test]
+    fn assert_models_can_be_registered_for_pack() {
+        let mut training_file = NamedTempFile::new().unwrap();
+        training_file
+            .write_all("lol brb omg idk tbh".as_bytes())
+            .unwrap();
+        let directory = tempdir().unwrap();
+
+        LanguageModelFilesWriter::create_and_write_language_model_files(
+            training_file.path(),
+            directory.path(),
+            &Language::Somali,
+            "\\p{L}",
+        )
+        .unwrap();
+
+        assert!(!ModelPack::Custom.languages().contains(&Language::Somali));
+
+        ModelPack::Custom
+            .register_models(Language::Somali, directory.path())
+            .unwrap();
+
+        assert!(ModelPack::Custom.languages().contains(&Language::Somali));
+        assert!(!ModelPack::Custom
+            .bootstrapped_languages()
+            .contains(&Language::Somali));
+        assert!(
+            load_model_pack_language_model(ModelPack::Custom, Language::Somali, 3)
+                .unwrap()
+                .contains_key("brb")
+        );
+        assert_eq!(
+            load_model_pack_language_model(ModelPack::News, Language::Somali, 3).unwrap(),
+            load_language_model(Language::Somali, 3).unwrap()
+        );
+    }
+
+    #[test]
+    fn assert_binary_models_can_be_registered_for_pack() {
+        let directory = tempdir().unwrap();
+        let json = r#"{"language":"TSONGA","ngrams":{"1/3":"xik xit","2/3":"nhl"}}"#;
+        let mut file = CompressorWriter::new(
+            File::create(directory.path().join("trigrams.bin.br")).unwrap(),
+            4096,
+            11,
+            22,
+        );
+        file.write_all(&encode_binary_model(
+            &LanguageModel::from_json(json).unwrap(),
+        ))
+        .unwrap();
+        drop(file);
+
+        ModelPack::Custom
+            .register_models(Language::Tsonga, directory.path())
+            .unwrap();
+
+        let model = load_model_pack_language_model(ModelPack::Custom, Language::Tsonga, 3).unwrap();
+
+        assert_eq!(model.len(), 3);
+        assert_eq!(model.get("xik"), Some(&(1.0 / 3.0)));
+        assert_eq!(model.get("nhl"), Some(&(2.0 / 3.0)));
+        assert!(!ModelPack::Custom
+            .bootstrapped_languages()
+            .contains(&Language::Tsonga));
+    }
+
+    #[test]
+    fn assert_corrupt_json_models_are_rejected() {
+        let directory = tempdir().unwrap();
+        let write_json = |file_name: &str, json: &str| {
+            let mut file = CompressorWriter::new(
+                File::create(directory.path().join(file_name)).unwrap(),
+                4096,
+                11,
+                22,
+            );
+            file.write_all(json.as_bytes()).unwrap();
+        };
+
+        write_json("trigrams.json.br", r#"{"language":"TSWANA","ngrams":"#);
+
+        assert_eq!(
+            ModelPack::Custom
+                .register_models(Language::Tswana, directory.path())
+                .map_err(|error| error.kind()),
+            Err(ErrorKind::InvalidData)
+        );
+        assert!(!ModelPack::Custom.languages().contains(&Language::Tswana));
+
+        write_json(
+            "trigrams.json.br",
+            r#"{"language":"TSWANA","ngrams":{"1/2":"tse mme"}}"#,
+        );
+        write_json("unigrams.json.br", "not a language model");
+
+        ModelPack::Custom
+            .register_models(Language::Tswana, directory.path())
+            .unwrap();
+
+        assert!(load_model_pack_language_model(ModelPack::Custom, Language::Tswana, 3).is_ok());
+        assert_eq!(
+            load_model_pack_language_model(ModelPack::Custom, Language::Tswana, 1)
+                .map_err(|error| error.kind()),
+            Err(ErrorKind::InvalidData)
+        );
+    }
+
+    #[test]
+    fn assert_model_bytes_can_be_registered_for_pack() {
+        let compress = |json: &str| {
+            let mut bytes = vec![];
+            let mut writer = CompressorWriter::new(&mut bytes, 4096, 11, 22);
+            writer
+                .write_all(&encode_binary_model(
+                    &LanguageModel::from_json(json).unwrap(),
+                ))
+                .unwrap();
+            drop(writer);
+            bytes
+        };
+        let trigrams = compress(r#"{"language":"XHOSA","ngrams":{"1/2":"ukw ngo"}}"#);
+        let unigrams = compress(r#"{"language":"XHOSA","ngrams":{"1/4":"u k w n"}}"#);
+
+        assert_eq!(
+            ModelPack::Custom
+                .register_model_bytes(Language::Xhosa, 1, unigrams.clone())
+                .map_err(|error| error.kind()),
+            Err(ErrorKind::NotFound)
+        );
+
+        ModelPack::Custom
+            .register_model_bytes(Language::Xhosa, 3, trigrams)
+            .unwrap();
+        ModelPack::Custom
+            .register_model_bytes(Language::Xhosa, 1, unigrams)
+            .unwrap();
+
+        assert!(ModelPack::Custom.languages().contains(&Language::Xhosa));
+        assert_eq!(
+            load_model_pack_language_model(ModelPack::Custom, Language::Xhosa, 3)
+                .unwrap()
+                .get("ngo"),
+            Some(&0.5)
+        );
+        assert_eq!(
+            load_model_pack_language_model(ModelPack::Custom, Language::Xhosa, 1)
+                .unwrap()
+                .len(),
+            4
+        );
+        assert_eq!(
+            load_model_pack_language_model(ModelPack::Custom, Language::Xhosa, 2)
+                .map_err(|error| error.kind()),
+            Err(ErrorKind::NotFound)
+        );
+    }
+
+    #[test]
+    fn assert_invalid_model_bytes_are_rejected() {
+        assert_eq!(
+            ModelPack::News
+                .register_model_bytes(Language::Ganda, 3, vec![])
+                .map_err(|error| error.kind()),
+            Err(ErrorKind::InvalidInput)
+        );
+        assert_eq!(
+            ModelPack::Custom
+                .register_model_bytes(Language::Ganda, 6, vec![])
+                .map_err(|error| error.kind()),
+            Err(ErrorKind::InvalidInput)
+        );
+        assert!(ModelPack::Custom
+            .register_model_bytes(Language::Ganda, 3, b"invalid".to_vec())
+            .is_err());
+        assert!(!ModelPack::Custom.languages().contains(&Language::Ganda));
+    }
+
+    #[test]
+    fn assert_invalid_registrations_are_rejected() {
+        let directory = tempdir().unwrap();
+
+        assert_eq!(
+            ModelPack::News
+                .register_models(Language::English, directory.path())
+                .map_err(|error| error.kind()),
+            Err(ErrorKind::InvalidInput)
+        );
+        assert_eq!(
+            ModelPack::Custom
+                .register_models(Language::English, directory.path())
+                .map_err(|error| error.kind()),
+            Err(ErrorKind::NotFound)
+        );
+        assert_eq!(ModelPack::News.languages().len(), Language::iter().count());
+    }
+
+    #[test]
+    fn assert_bootstrapped_models_are_recognized_when_registered() {
+        let mut seed_lexicon_file = NamedTempFile::new().unwrap();
+        seed_lexicon_file
+            .write_all("goeie\nmôre\ndankie\nasseblief".as_bytes())
+            .unwrap();
+        let directory = tempdir().unwrap();
+
+        LanguageModelFilesWriter::create_and_write_bootstrapped_language_model_files(
+            seed_lexicon_file.path(),
+            directory.path(),
+            &Language::Afrikaans,
+            &Language::Dutch,
+            0.5,
+            "\\p{L}",
+        )
+        .unwrap();
+
+        ModelPack::Custom
+            .register_models(Language::Afrikaans, directory.path())
+            .unwrap();
+
+        assert!(ModelPack::Custom.languages().contains(&Language::Afrikaans));
+        assert!(ModelPack::Custom
+            .bootstrapped_languages()
+            .contains(&Language::Afrikaans));
+        assert!(ModelPack::News.bootstrapped_languages().is_empty());
+    }
+}
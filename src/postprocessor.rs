@@ -0,0 +1,61 @@
+/*
+ * Copyright © 2020-present Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::language::Language;
+
+/// A hook which inspects and rewrites the confidence values computed by a
+/// [LanguageDetector](crate::LanguageDetector) before they are returned.
+///
+/// Post-processors keep policies such as merging closely related languages, dropping
+/// languages below a floor or applying business rules co-located with the detector
+/// configuration. They are registered with
+/// [LanguageDetectorBuilder::with_post_processor](crate::LanguageDetectorBuilder::with_post_processor)
+/// and applied in the order of their registration. Every closure taking a mutable
+/// reference to the confidence values implements this trait.
+///
+/// The confidence values are passed sorted in descending order. Post-processors may change
+/// the values and remove entries, the detector sorts the values again afterwards.
+/// The detected language is determined from the post-processed values.
+///
+/// ```
+/// use lingua::{ConfidencePostProcessor, Language};
+///
+/// /// Sets all confidence values below a floor to zero.
+/// struct ConfidenceFloor(f64);
+///
+/// impl ConfidencePostProcessor for ConfidenceFloor {
+///     fn process(&self, confidence_values: &mut Vec<(Language, f64)>) {
+///         for (_, confidence) in confidence_values.iter_mut() {
+///             if *confidence < self.0 {
+///                 *confidence = 0.0;
+///             }
+///         }
+///     }
+/// }
+/// ```
+pub trait ConfidencePostProcessor: Send + Sync {
+    /// Inspects and rewrites the given confidence values.
+    fn process(&self, confidence_values: &mut Vec<(Language, f64)>);
+}
+
+impl<F> ConfidencePostProcessor for F
+where
+    F: Fn(&mut Vec<(Language, f64)>) + Send + Sync,
+{
+    fn process(&self, confidence_values: &mut Vec<(Language, f64)>) {
+        self(confidence_values)
+    }
+}
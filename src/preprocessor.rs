@@ -0,0 +1,250 @@
+/*
+ * Copyright © 2020-present Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use itertools::Itertools;
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+
+use crate::normalization::NormalizationForm;
+
+/// A hook which rewrites the input text of a [LanguageDetector](crate::LanguageDetector)
+/// before its language is detected, or replaces how the text is split into words.
+///
+/// By default, the detector removes invisible and bidirectional formatting characters
+/// and splits the text into lowercase words consisting of letters only, see
+/// [CleanupPolicy](crate::CleanupPolicy). For some domains, this destroys signal,
+/// e.g. markup is split into words which do not belong to any language. Preprocessors are
+/// registered with
+/// [LanguageDetectorBuilder::with_preprocessor](crate::LanguageDetectorBuilder::with_preprocessor)
+/// and applied in the order of their registration. Every closure taking the text
+/// and returning the rewritten text implements this trait.
+///
+/// A preprocessor which overrides [TextPreprocessor::split_into_words] replaces the built-in
+/// splitting. The words of the first preprocessor which splits the text are used as they are,
+/// so they should be lowercase like the ngrams of the language models.
+///
+/// ```
+/// use lingua::TextPreprocessor;
+///
+/// /// Keeps words with apostrophes such as "don't" together.
+/// struct ApostropheWords;
+///
+/// impl TextPreprocessor for ApostropheWords {
+///     fn preprocess(&self, text: &str) -> String {
+///         text.to_string()
+///     }
+///
+///     fn split_into_words(&self, text: &str) -> Option<Vec<String>> {
+///         let words = text
+///             .split(|ch: char| !ch.is_alphabetic() && ch != '\'')
+///             .map(|word| word.trim_matches('\'').to_lowercase())
+///             .filter(|word| !word.is_empty())
+///             .collect();
+///         Some(words)
+///     }
+/// }
+///
+/// assert_eq!(
+///     ApostropheWords.split_into_words("Don't panic!"),
+///     Some(vec!["don't".to_string(), "panic".to_string()])
+/// );
+/// ```
+pub trait TextPreprocessor: Send + Sync {
+    /// Rewrites the given text before it is split into words.
+    fn preprocess(&self, text: &str) -> String;
+
+    /// Splits the given preprocessed text into words, or returns [None] if the text
+    /// should be split by the next preprocessor or the built-in splitting.
+    fn split_into_words(&self, _text: &str) -> Option<Vec<String>> {
+        None
+    }
+}
+
+impl<F> TextPreprocessor for F
+where
+    F: Fn(&str) -> String + Send + Sync,
+{
+    fn preprocess(&self, text: &str) -> String {
+        self(text)
+    }
+}
+
+/// Matches elements whose content is code rather than text, HTML comments and fenced
+/// or inline code of Markdown.
+static CODE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?is)<script\b.*?</script\s*>|<style\b.*?</style\s*>|<!--.*?-->|```.*?```|~~~.*?~~~|`[^`\n]*`",
+    )
+    .unwrap()
+});
+
+static HTML_TAGS: Lazy<Regex> = Lazy::new(|| Regex::new(r"</?[a-zA-Z][^<>]*>").unwrap());
+
+static HTML_ENTITIES: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"&(?:#([0-9]{1,7})|#[xX]([0-9a-fA-F]{1,6})|([a-zA-Z]+));").unwrap());
+
+/// Matches Markdown images and links, capturing their text, and reference definitions.
+static MARKDOWN_LINKS: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?m)!?\[([^\]]*)\](?:\([^)]*\)|\[[^\]]*\])|^\s*\[[^\]]+\]:\s*\S+.*$").unwrap()
+});
+
+static URLS: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)\b(?:https?://|ftp://|www\.|mailto:)\S+").unwrap());
+
+/// A built-in [TextPreprocessor] which strips HTML and Markdown markup from the input text.
+///
+/// Scraped web content contains tag names, attributes, entities, URLs and code, which
+/// consist of Latin letters regardless of the language of the page. They dilute the ngram
+/// statistics and make the rule-based filter engine favor languages written in Latin script.
+/// This preprocessor removes
+///
+/// - HTML tags with their attributes and HTML comments,
+/// - the content of `script` and `style` elements,
+/// - fenced and inline code of Markdown,
+/// - URLs and the targets of Markdown links and images, keeping their text,
+///
+/// and decodes HTML entities. The remaining Markdown syntax such as emphasis or headings
+/// consists of punctuation, which is removed when the text is split into words anyway.
+///
+/// ```
+/// use lingua::{MarkupStripper, TextPreprocessor};
+///
+/// assert_eq!(
+///     MarkupStripper.preprocess("<p class=\"intro\">Caf&eacute; [au lait](https://example.com)</p>"),
+///     " Café au lait "
+/// );
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MarkupStripper;
+
+impl TextPreprocessor for MarkupStripper {
+    fn preprocess(&self, text: &str) -> String {
+        let text = CODE.replace_all(text, " ");
+        let text = HTML_TAGS.replace_all(&text, " ");
+        let text = MARKDOWN_LINKS.replace_all(&text, |captures: &Captures| {
+            captures
+                .get(1)
+                .map_or(String::new(), |text| text.as_str().to_string())
+        });
+        let text = URLS.replace_all(&text, " ");
+        HTML_ENTITIES
+            .replace_all(&text, |captures: &Captures| {
+                let decoded_char = if let Some(decimal) = captures.get(1) {
+                    decimal.as_str().parse().ok().and_then(char::from_u32)
+                } else if let Some(hexadecimal) = captures.get(2) {
+                    u32::from_str_radix(hexadecimal.as_str(), 16)
+                        .ok()
+                        .and_then(char::from_u32)
+                } else {
+                    decode_named_entity(&captures[3])
+                };
+                decoded_char.unwrap_or(' ').to_string()
+            })
+            .into_owned()
+    }
+}
+
+/// Decodes the named HTML entities which are common in text content: the ones of the
+/// characters with a special meaning in HTML, spaces, dashes, quotation marks and
+/// the accented letters of Latin-1.
+fn decode_named_entity(name: &str) -> Option<char> {
+    let ch = match name {
+        "amp" => '&',
+        "lt" => '<',
+        "gt" => '>',
+        "quot" => '"',
+        "apos" => '\'',
+        "nbsp" | "ensp" | "emsp" | "thinsp" => ' ',
+        "ndash" => '–',
+        "mdash" => '—',
+        "lsquo" => '‘',
+        "rsquo" => '’',
+        "ldquo" => '“',
+        "rdquo" => '”',
+        "laquo" => '«',
+        "raquo" => '»',
+        "szlig" => 'ß',
+        _ => return decode_latin1_letter_entity(name),
+    };
+    Some(ch)
+}
+
+/// Decodes entities such as `&eacute;` or `&Ouml;`, composed of a base letter and the name
+/// of a diacritic, which are defined for the accented letters of Latin-1.
+fn decode_latin1_letter_entity(name: &str) -> Option<char> {
+    let mut chars = name.chars();
+    let base_letter = chars.next()?;
+    let combining_mark = match chars.as_str() {
+        "grave" => '\u{0300}',
+        "acute" => '\u{0301}',
+        "circ" => '\u{0302}',
+        "tilde" => '\u{0303}',
+        "uml" => '\u{0308}',
+        "ring" => '\u{030A}',
+        "cedil" => '\u{0327}',
+        _ => return None,
+    };
+    if !base_letter.is_ascii_alphabetic() {
+        return None;
+    }
+    NormalizationForm::Nfc
+        .normalize(&format!("{base_letter}{combining_mark}"))
+        .chars()
+        .exactly_one()
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strip(text: &str) -> String {
+        MarkupStripper.preprocess(text).split_whitespace().join(" ")
+    }
+
+    #[test]
+    fn assert_html_is_stripped() {
+        assert_eq!(
+            strip("<html><head><style>body { color: red; }</style></head><body>"),
+            ""
+        );
+        assert_eq!(
+            strip("<div id=\"main\"><!-- nav --><h1>Привет</h1><script>var x;</script></div>"),
+            "Привет"
+        );
+        assert_eq!(
+            strip("Fran&ccedil;ais &amp; Deutsch&nbsp;&#252;ber &#x263A; &foo;"),
+            "Français & Deutsch über ☺"
+        );
+        assert_eq!(strip("1 < 2 und 3>2"), "1 < 2 und 3>2");
+    }
+
+    #[test]
+    fn assert_markdown_is_stripped() {
+        assert_eq!(
+            strip("## Überschrift\n\n```rust\nfn main() {}\n```\nText mit `code`"),
+            "## Überschrift Text mit"
+        );
+        assert_eq!(
+            strip("Siehe [die Doku](https://docs.rs/lingua) und ![Bild](bild.png)"),
+            "Siehe die Doku und Bild"
+        );
+        assert_eq!(
+            strip("Mehr unter www.example.com\n[1]: https://example.com"),
+            "Mehr unter"
+        );
+    }
+}
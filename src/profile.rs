@@ -0,0 +1,167 @@
+/*
+ * Copyright © 2020-present Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::ops::Range;
+
+use crate::constant::TOKENS_WITHOUT_WHITESPACE;
+use crate::language::Language;
+
+pub(crate) const WINDOW_SIZE_MESSAGE: &str = "Window size must be greater than zero";
+pub(crate) const WINDOW_STEP_MESSAGE: &str = "Window step must be greater than zero";
+
+/// This struct describes how the confidence in the language of a document develops
+/// over the course of the document, as returned by
+/// [LanguageDetector::compute_confidence_profile](crate::LanguageDetector::compute_confidence_profile).
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConfidenceProfile {
+    pub(crate) language: Option<Language>,
+    pub(crate) windows: Vec<ConfidenceWindow>,
+}
+
+/// This struct describes a window of consecutive words within a document,
+/// together with the confidence in the language of the entire document.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ConfidenceWindow {
+    pub(crate) start_index: usize,
+    pub(crate) end_index: usize,
+    pub(crate) word_count: usize,
+    pub(crate) confidence: f64,
+    pub(crate) most_likely_language: Option<Language>,
+}
+
+impl ConfidenceProfile {
+    /// Returns the most likely language of the entire document,
+    /// or [None] if the document does not contain any words.
+    pub fn language(&self) -> Option<Language> {
+        self.language
+    }
+    /// Returns the windows in the order of their occurrence in the document.
+    pub fn windows(&self) -> &[ConfidenceWindow] {
+        &self.windows
+    }
+    /// Returns the window with the lowest confidence in the language of the document,
+    /// i.e. the section where the language signal is weakest. If several windows
+    /// share the lowest confidence, the first one is returned.
+    pub fn weakest_window(&self) -> Option<&ConfidenceWindow> {
+        self.windows.iter().reduce(|weakest, window| {
+            if window.confidence < weakest.confidence {
+                window
+            } else {
+                weakest
+            }
+        })
+    }
+}
+
+impl ConfidenceWindow {
+    /// Returns the byte index of the start of the window within the document.
+    pub fn start_index(&self) -> usize {
+        self.start_index
+    }
+    /// Returns the byte index of the end of the window within the document.
+    pub fn end_index(&self) -> usize {
+        self.end_index
+    }
+    /// Returns the number of words in the window.
+    pub fn word_count(&self) -> usize {
+        self.word_count
+    }
+    /// Returns the confidence value of the language of the entire document
+    /// for the text of this window, a value between 0.0 and 1.0.
+    pub fn confidence(&self) -> f64 {
+        self.confidence
+    }
+    /// Returns the most likely language of the text of this window, which differs
+    /// from the language of the entire document where the language switches.
+    /// Returns [None] if all languages are equally likely.
+    pub fn most_likely_language(&self) -> Option<Language> {
+        self.most_likely_language
+    }
+}
+
+/// Splits the given text into windows of `window_size` consecutive words, the next window
+/// starting `step` words after the previous one. The last window may contain fewer words.
+/// Returns the byte ranges of the windows within the text and their word counts.
+pub(crate) fn split_text_into_windows(
+    text: &str,
+    window_size: usize,
+    step: usize,
+) -> Vec<(Range<usize>, usize)> {
+    let words = TOKENS_WITHOUT_WHITESPACE
+        .find_iter(text)
+        .map(|mat| mat.range())
+        .collect::<Vec<_>>();
+
+    let mut windows = vec![];
+
+    for start in (0..words.len()).step_by(step) {
+        let end = (start + window_size).min(words.len());
+        windows.push((words[start].start..words[end - 1].end, end - start));
+        if end == words.len() {
+            break;
+        }
+    }
+
+    windows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn window_texts(text: &str, window_size: usize, step: usize) -> Vec<&str> {
+        split_text_into_windows(text, window_size, step)
+            .into_iter()
+            .map(|(range, _)| &text[range])
+            .collect()
+    }
+
+    #[test]
+    fn assert_text_is_split_into_windows_correctly() {
+        let text = "One two, three four. Five";
+
+        assert_eq!(
+            window_texts(text, 2, 2),
+            vec!["One two", "three four", "Five"]
+        );
+        assert_eq!(
+            window_texts(text, 3, 1),
+            vec!["One two, three", "two, three four", "three four. Five"]
+        );
+        assert_eq!(window_texts(text, 10, 10), vec![text]);
+        assert!(window_texts(" 123 ", 2, 2).is_empty());
+    }
+
+    #[test]
+    fn assert_weakest_window_is_found() {
+        let window = |confidence| ConfidenceWindow {
+            start_index: 0,
+            end_index: 0,
+            word_count: 1,
+            confidence,
+            most_likely_language: None,
+        };
+        let profile = ConfidenceProfile {
+            language: None,
+            windows: vec![window(0.9), window(0.2), window(0.5), window(0.2)],
+        };
+
+        assert!(std::ptr::eq(
+            profile.weakest_window().unwrap(),
+            &profile.windows()[1]
+        ));
+    }
+}
@@ -0,0 +1,61 @@
+/*
+ * Copyright © 2020-present Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use serde::{Deserialize, Serialize};
+use strum_macros::EnumIter;
+
+/// This enum specifies the world regions in which the languages supported by *Lingua*
+/// are spoken, as returned by [Language::spoken_in](crate::Language::spoken_in).
+///
+/// The regions roughly follow the geoscheme of the United Nations, with the Caucasus
+/// and Central Asia being separated from the Middle East.
+#[derive(
+    Clone, Copy, Debug, Serialize, Deserialize, EnumIter, Eq, PartialEq, Hash, Ord, PartialOrd,
+)]
+pub enum Region {
+    /// Armenia, Azerbaijan and Georgia.
+    Caucasus,
+    /// Kazakhstan, Kyrgyzstan, Tajikistan, Turkmenistan and Uzbekistan.
+    CentralAsia,
+    /// China, Japan, Korea, Mongolia and Taiwan.
+    EastAsia,
+    /// Belarus, Bulgaria, Czechia, Hungary, Moldova, Poland, Romania, Russia,
+    /// Slovakia and Ukraine.
+    EasternEurope,
+    /// Mexico, Central America, South America and the Caribbean.
+    LatinAmerica,
+    /// The Arabian Peninsula, Iran, Iraq, Israel, the Levant and Turkey.
+    MiddleEast,
+    /// Canada and the United States.
+    NorthAmerica,
+    /// Algeria, Egypt, Libya, Morocco, Sudan and Tunisia.
+    NorthAfrica,
+    /// The British Isles, the Nordic countries and the Baltic states.
+    NorthernEurope,
+    /// Australia, New Zealand and the Pacific islands.
+    Oceania,
+    /// Afghanistan, Bangladesh, India, Nepal, Pakistan and Sri Lanka.
+    SouthAsia,
+    /// Indonesia, Malaysia, the Philippines, Singapore, Thailand, Vietnam
+    /// and their neighbours.
+    SoutheastAsia,
+    /// The Iberian Peninsula, Italy, Greece and the Western Balkans.
+    SouthernEurope,
+    /// Africa south of the Sahara.
+    SubSaharanAfrica,
+    /// Austria, the Benelux countries, France, Germany and Switzerland.
+    WesternEurope,
+}
@@ -40,3 +40,33 @@ impl DetectionResult {
         self.language
     }
 }
+
+/// This struct describes the confidence value of a single language, as returned by
+/// [LanguageDetector::compute_confidence_ranking](crate::LanguageDetector::compute_confidence_ranking).
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct RankedConfidence {
+    pub(crate) language: Language,
+    pub(crate) value: f64,
+    pub(crate) rank: usize,
+}
+
+impl RankedConfidence {
+    /// Returns the language this confidence value belongs to.
+    pub fn language(&self) -> Language {
+        self.language
+    }
+    /// Returns the confidence value relative to the other languages,
+    /// a value between 0.0 and 1.0.
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+    /// Returns the position of the language in the ranking, starting at 1
+    /// for the most likely language.
+    pub fn rank(&self) -> usize {
+        self.rank
+    }
+    /// Returns `true` if the confidence value reaches the given threshold.
+    pub fn is_reliable(&self, threshold: f64) -> bool {
+        self.value >= threshold
+    }
+}
@@ -0,0 +1,109 @@
+/*
+ * Copyright © 2020-present Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::HashSet;
+
+use crate::detector::LanguageDetector;
+use crate::language::Language;
+
+pub(crate) const MISSING_LANGUAGE_MESSAGE: &str =
+    "ScriptRuleDetector needs at least 1 language to choose from";
+
+/// This struct detects the language of given input text by its script and by characters
+/// unique to a language only, without consulting any ngram models.
+///
+/// It answers questions such as "Is this text written in Greek, Hebrew, Thai or Chinese?"
+/// for which the rule-based filter engine of [LanguageDetector] is sufficient. As no language
+/// models are ever loaded, it needs hardly any memory and is ready instantly, so it suits
+/// WebAssembly widgets and embedded user interfaces. Languages which share their script
+/// with other languages, such as English and German, can only be detected if the text
+/// contains characters unique to them. Otherwise, [None] is returned.
+///
+/// ```
+/// use lingua::Language::{Chinese, English, Greek, Hebrew, Thai};
+/// use lingua::ScriptRuleDetector;
+///
+/// let detector = ScriptRuleDetector::from_languages(&[Chinese, English, Greek, Hebrew, Thai]);
+///
+/// assert_eq!(detector.detect_language_of("Καλημέρα κόσμε"), Some(Greek));
+/// assert_eq!(detector.detect_language_of("שלום עולם"), Some(Hebrew));
+/// assert_eq!(detector.detect_language_of("languages are awesome"), None);
+/// ```
+pub struct ScriptRuleDetector {
+    detector: LanguageDetector,
+}
+
+impl ScriptRuleDetector {
+    /// Creates and returns an instance of `ScriptRuleDetector` with all built-in languages.
+    pub fn from_all_languages() -> Self {
+        Self::from(Language::all())
+    }
+
+    /// Creates and returns an instance of `ScriptRuleDetector` with the specified `languages`.
+    ///
+    /// ⚠ Panics if no languages are specified.
+    pub fn from_languages(languages: &[Language]) -> Self {
+        if languages.is_empty() {
+            panic!("{}", MISSING_LANGUAGE_MESSAGE);
+        }
+        Self::from(languages.iter().cloned().collect())
+    }
+
+    /// Detects the language of given input text by its script and unique characters.
+    /// If the language cannot be detected this way, [None] is returned.
+    pub fn detect_language_of<T: Into<String>>(&self, text: T) -> Option<Language> {
+        self.detector.detect_language_by_rules_of(&text.into())
+    }
+
+    /// Returns the languages this detector chooses from.
+    pub fn supported_languages(&self) -> HashSet<Language> {
+        self.detector.supported_languages()
+    }
+
+    fn from(languages: HashSet<Language>) -> Self {
+        Self {
+            detector: LanguageDetector::from(languages, 0.0, false, false),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::language::Language::{Chinese, English, German, Japanese, Thai};
+
+    #[test]
+    fn assert_language_is_detected_by_rules_only() {
+        let detector =
+            ScriptRuleDetector::from_languages(&[Chinese, English, German, Japanese, Thai]);
+
+        assert_eq!(detector.detect_language_of("สวัสดีครับ"), Some(Thai));
+        assert_eq!(detector.detect_language_of("這是一個句子"), Some(Chinese));
+        assert_eq!(
+            detector.detect_language_of("これは文章です"),
+            Some(Japanese)
+        );
+        assert_eq!(detector.detect_language_of("Straße"), Some(German));
+        assert_eq!(detector.detect_language_of("house"), None);
+        assert_eq!(detector.detect_language_of(""), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "ScriptRuleDetector needs at least 1 language to choose from")]
+    fn assert_detector_cannot_be_created_without_languages() {
+        ScriptRuleDetector::from_languages(&[]);
+    }
+}
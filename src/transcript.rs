@@ -0,0 +1,97 @@
+/*
+ * Copyright © 2020-present Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+/// Annotations in square, round or angle brackets, such as `[00:01:02]`,
+/// `(laughs)` or `<inaudible>`.
+static BRACKETED_ANNOTATIONS: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\[[^\]]*\]|\([^)]*\)|<[^>]*>").unwrap());
+
+/// Timestamps which are not enclosed in brackets, such as `00:01:02` or `1:02.5`.
+static TIMESTAMPS: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\b\d{1,2}(?::\d{2}){1,2}(?:[.,]\d+)?\b").unwrap());
+
+/// Speaker labels at the start of a line, such as `SPEAKER 1:`, `Interviewer:` or `>> ANNA:`,
+/// consisting of at most three words followed by a colon.
+static SPEAKER_LABELS: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?m)^[\s>\-–—]*(?:[\p{L}\p{N}_.'-]+\s*){1,3}:(?:\s|$)").unwrap());
+
+/// Hesitation markers produced by speech recognition systems in various languages.
+const FILLER_WORDS: [&str; 19] = [
+    "ah", "äh", "ähm", "eh", "ehh", "ehm", "erm", "euh", "hm", "hmm", "mhm", "mm", "mmm", "uh",
+    "uhh", "uhm", "um", "umm", "öh",
+];
+
+/// Removes the artifacts of speech transcripts from the given text, i.e. bracketed annotations
+/// such as timestamps or sound descriptions, unbracketed timestamps and speaker labels
+/// at the start of lines.
+pub(crate) fn strip_transcript_annotations(text: &str) -> String {
+    let text = BRACKETED_ANNOTATIONS.replace_all(text, " ");
+    let text = TIMESTAMPS.replace_all(&text, " ");
+    SPEAKER_LABELS.replace_all(&text, "").into_owned()
+}
+
+/// Removes filler words such as `uh` or `ähm` from the given lowercase words, as they
+/// carry little information about the language of a transcript but distort the
+/// probabilities of very short utterances. If the words consist of fillers only,
+/// they are returned unchanged.
+pub(crate) fn remove_filler_words(words: Vec<String>) -> Vec<String> {
+    if words
+        .iter()
+        .all(|word| FILLER_WORDS.contains(&word.as_str()))
+    {
+        return words;
+    }
+
+    words
+        .into_iter()
+        .filter(|word| !FILLER_WORDS.contains(&word.as_str()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[rstest(
+        text,
+        expected_text,
+        case("[00:01:02] SPEAKER 1: uh, bonjour", "uh, bonjour"),
+        case("Interviewer: How are you? (laughs)", "How are you?  "),
+        case(">> ANNA: Hallo <inaudible> zusammen", "Hallo   zusammen"),
+        case("00:12 Dr. Smith: ok", "ok"),
+        case("The meeting starts at 10:30.", "The meeting starts at  ."),
+        case("Hello, my name is: Tom", "Hello, my name is: Tom")
+    )]
+    fn assert_transcript_annotations_are_stripped(text: &str, expected_text: &str) {
+        assert_eq!(
+            strip_transcript_annotations(text).trim(),
+            expected_text.trim()
+        );
+    }
+
+    #[test]
+    fn assert_filler_words_are_removed() {
+        let words = vec!["uh".to_string(), "bonjour".to_string(), "euh".to_string()];
+        assert_eq!(remove_filler_words(words), vec!["bonjour".to_string()]);
+
+        let fillers = vec!["uh".to_string(), "hmm".to_string()];
+        assert_eq!(remove_filler_words(fillers.clone()), fillers);
+    }
+}
@@ -0,0 +1,175 @@
+/*
+ * Copyright © 2020-present Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use itertools::Itertools;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use strum::IntoEnumIterator;
+
+use crate::alphabet::Alphabet;
+
+/// Characters which must not be separated from the preceding character: combining marks
+/// as well as the zero-width non-joiner and joiner.
+static GRAPHEME_EXTENDERS: Lazy<Regex> =
+    Lazy::new(|| Regex::new("^[\\p{M}\u{200C}\u{200D}]$").unwrap());
+
+/// Characters which end a sentence if they are followed by whitespace.
+const SENTENCE_TERMINATORS: [char; 5] = ['.', '!', '?', '…', '؟'];
+
+/// Characters which end a sentence regardless of the following character.
+const FULLWIDTH_SENTENCE_TERMINATORS: [char; 3] = ['。', '！', '？'];
+
+/// Alphabets whose characters are treated as separate words, as these scripts
+/// do not separate words by whitespace.
+const LOGOGRAPHIC_ALPHABETS: [Alphabet; 4] = [
+    Alphabet::Han,
+    Alphabet::Hangul,
+    Alphabet::Hiragana,
+    Alphabet::Katakana,
+];
+
+/// Truncates the given text to at most `max_chars` characters such that no word,
+/// sentence or grapheme is cut in half.
+///
+/// Callers enforcing a maximum input size should prefer this function to slicing the text,
+/// as a half-finished word may lose a character which is decisive for its language or
+/// contribute ngrams no language knows. The text is cut at the last sentence boundary
+/// if that retains at least half of `max_chars` characters, otherwise at the last word
+/// boundary or change of script. Scripts such as Han or Hiragana which do not separate
+/// words by whitespace can be cut between any two characters. If no such boundary exists,
+/// the text is cut at the last position which does not separate a combining mark from
+/// its base character. Trailing whitespace is removed from the truncated text.
+///
+/// ```
+/// use lingua::truncate_for_detection;
+///
+/// let text = "Das ist gut. Wolkenkratzer sind hoch.";
+///
+/// assert_eq!(truncate_for_detection(text, 20), "Das ist gut.");
+/// assert_eq!(truncate_for_detection(text, 30), "Das ist gut. Wolkenkratzer");
+/// assert_eq!(truncate_for_detection(text, 100), text);
+/// ```
+pub fn truncate_for_detection(text: &str, max_chars: usize) -> &str {
+    let chars = text.char_indices().take(max_chars + 1).collect_vec();
+
+    if chars.len() <= max_chars {
+        return text;
+    }
+
+    // A cut at position i keeps the first i characters.
+    let is_sentence_boundary = |i: usize| {
+        let (previous, next) = (chars[i - 1].1, chars[i].1);
+        FULLWIDTH_SENTENCE_TERMINATORS.contains(&previous)
+            || (SENTENCE_TERMINATORS.contains(&previous) && next.is_whitespace())
+    };
+    let is_grapheme_boundary = |i: usize| !is_inside_grapheme(chars[i - 1].1, chars[i].1);
+    let is_word_boundary = |i: usize| {
+        // Combining marks belong to the word of their base character.
+        let base = chars[..i]
+            .iter()
+            .rev()
+            .map(|(_, ch)| *ch)
+            .find(|ch| !is_grapheme_extender(*ch))
+            .unwrap_or(chars[i - 1].1);
+        is_grapheme_boundary(i) && !is_inside_word(base, chars[i].1)
+    };
+
+    let minimum_char_count = (max_chars / 2).max(1);
+
+    let cut = (minimum_char_count..=max_chars)
+        .rev()
+        .find(|i| is_sentence_boundary(*i))
+        .or_else(|| (1..=max_chars).rev().find(|i| is_word_boundary(*i)))
+        .or_else(|| (1..=max_chars).rev().find(|i| is_grapheme_boundary(*i)))
+        .unwrap_or(0);
+
+    text[..chars[cut].0].trim_end()
+}
+
+fn is_grapheme_extender(ch: char) -> bool {
+    let mut buffer = [0; 4];
+    GRAPHEME_EXTENDERS.is_match(ch.encode_utf8(&mut buffer))
+}
+
+fn is_inside_grapheme(previous: char, next: char) -> bool {
+    is_grapheme_extender(next) || previous == '\u{200D}'
+}
+
+fn is_inside_word(previous: char, next: char) -> bool {
+    if !previous.is_alphabetic() || !next.is_alphabetic() {
+        return false;
+    }
+
+    let previous_alphabet = find_alphabet(previous);
+
+    if previous_alphabet.is_some_and(|alphabet| LOGOGRAPHIC_ALPHABETS.contains(&alphabet)) {
+        return false;
+    }
+
+    previous_alphabet == find_alphabet(next)
+}
+
+fn find_alphabet(ch: char) -> Option<Alphabet> {
+    Alphabet::iter().find(|alphabet| alphabet.matches_char(ch))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn assert_text_is_truncated_at_sentence_boundary() {
+        let text = "Hi! This is a rather long sentence.";
+
+        assert_eq!(truncate_for_detection(text, 6), "Hi!");
+        // The sentence boundary would retain less than half of the characters.
+        assert_eq!(truncate_for_detection(text, 20), "Hi! This is a rather");
+        assert_eq!(
+            truncate_for_detection("我很好。你呢？谢谢", 8),
+            "我很好。你呢？"
+        );
+    }
+
+    #[test]
+    fn assert_text_is_truncated_at_word_boundary() {
+        assert_eq!(
+            truncate_for_detection("Wolkenkratzer sind hoch", 16),
+            "Wolkenkratzer"
+        );
+        assert_eq!(truncate_for_detection("Straße, Fußball", 10), "Straße,");
+        assert_eq!(truncate_for_detection("東京タワー", 3), "東京タ");
+    }
+
+    #[test]
+    fn assert_text_is_truncated_at_change_of_script() {
+        assert_eq!(truncate_for_detection("abcабв", 4), "abc");
+    }
+
+    #[test]
+    fn assert_graphemes_are_not_split() {
+        let text = "Cafe\u{301}s creme";
+
+        assert_eq!(truncate_for_detection(text, 5), "Cafe\u{301}");
+        assert_eq!(truncate_for_detection(text, 8), "Cafe\u{301}s");
+        assert_eq!(truncate_for_detection("e\u{301}\u{301}", 2), "");
+    }
+
+    #[test]
+    fn assert_short_text_is_not_truncated() {
+        assert_eq!(truncate_for_detection("hello", 5), "hello");
+        assert_eq!(truncate_for_detection("hello", 0), "");
+    }
+}
@@ -0,0 +1,405 @@
+/*
+ * Copyright © 2020-present Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::language::Language;
+
+pub(crate) const MAXIMUM_IDLE_TIME_MESSAGE: &str = "Maximum idle time must be greater than zero";
+pub(crate) const MAXIMUM_RESIDENT_LANGUAGES_MESSAGE: &str =
+    "Maximum number of resident languages must be greater than zero";
+
+/// The maximum interval in between two checks for idle languages during automatic eviction.
+const EVICTION_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// This struct reports how often a language has been detected by a
+/// [LanguageDetector](crate::LanguageDetector) and when its language models
+/// have been used for the last time, as returned by
+/// [LanguageDetector::language_usage](crate::LanguageDetector::language_usage).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LanguageUsage {
+    pub(crate) language: Language,
+    pub(crate) detection_count: u64,
+    pub(crate) idle_time: Duration,
+}
+
+impl LanguageUsage {
+    /// Returns the language this usage is reported for.
+    pub fn language(&self) -> Language {
+        self.language
+    }
+    /// Returns how often this language has been the most likely language of a text.
+    pub fn detection_count(&self) -> u64 {
+        self.detection_count
+    }
+    /// Returns the time which has passed since the language models of this language
+    /// have been evaluated for the last time, or since the detector has been built
+    /// if they have never been evaluated.
+    pub fn idle_time(&self) -> Duration {
+        self.idle_time
+    }
+}
+
+struct UsageRecord {
+    detection_count: u64,
+    last_used: Instant,
+    is_resident: bool,
+    /// The number of detections which are evaluating the language models right now.
+    evaluation_count: usize,
+}
+
+/// Keeps the language models of some languages from being evicted as long as they are
+/// being evaluated, see [LanguageUsageTracker::start_evaluation].
+pub(crate) struct EvaluationGuard<'a> {
+    tracker: &'a LanguageUsageTracker,
+    languages: HashSet<Language>,
+}
+
+impl Drop for EvaluationGuard<'_> {
+    fn drop(&mut self) {
+        let mut records = self.tracker.records.lock().unwrap();
+        for language in self.languages.iter() {
+            if let Some(record) = records.get_mut(language) {
+                record.evaluation_count -= 1;
+            }
+        }
+    }
+}
+
+/// Tracks the detections of each language and the last use of its language models,
+/// in order to evict the models of languages which do not occur in the traffic
+/// or which have been used least recently.
+pub(crate) struct LanguageUsageTracker {
+    records: Mutex<HashMap<Language, UsageRecord>>,
+    maximum_idle_time: Option<Duration>,
+    maximum_resident_languages: Option<usize>,
+    last_eviction_check: Mutex<Instant>,
+}
+
+impl LanguageUsageTracker {
+    pub(crate) fn new(
+        languages: &HashSet<Language>,
+        maximum_idle_time: Option<Duration>,
+        maximum_resident_languages: Option<usize>,
+    ) -> Self {
+        let now = Instant::now();
+        let records = languages
+            .iter()
+            .map(|language| {
+                let record = UsageRecord {
+                    detection_count: 0,
+                    last_used: now,
+                    is_resident: false,
+                    evaluation_count: 0,
+                };
+                (*language, record)
+            })
+            .collect();
+
+        Self {
+            records: Mutex::new(records),
+            maximum_idle_time,
+            maximum_resident_languages,
+            last_eviction_check: Mutex::new(now),
+        }
+    }
+
+    /// Records that the language models of the given languages are about to be evaluated
+    /// and passes the least recently used resident languages which exceed the maximum number
+    /// of resident languages, if such a maximum is configured, to the given function in order
+    /// to unload their models.
+    ///
+    /// Languages which are being evaluated, by this or any other thread, are never unloaded,
+    /// even if they exceed the maximum on their own. The given languages count as being
+    /// evaluated until the returned guard is dropped. The usage records stay locked while
+    /// the models are unloaded, so that no other evaluation can start to use them meanwhile.
+    pub(crate) fn start_evaluation(
+        &self,
+        languages: &HashSet<Language>,
+        unload_language_models: impl FnOnce(&HashSet<Language>),
+    ) -> EvaluationGuard<'_> {
+        let now = Instant::now();
+        let mut records = self.records.lock().unwrap();
+        let mut evaluated_languages = HashSet::new();
+
+        for language in languages {
+            if let Some(record) = records.get_mut(language) {
+                record.last_used = now;
+                record.is_resident = true;
+                record.evaluation_count += 1;
+                evaluated_languages.insert(*language);
+            }
+        }
+
+        let least_recently_used_languages = self.find_least_recently_used_languages(&records);
+
+        if !least_recently_used_languages.is_empty() {
+            unload_language_models(&least_recently_used_languages);
+            for language in least_recently_used_languages.iter() {
+                if let Some(record) = records.get_mut(language) {
+                    record.is_resident = false;
+                }
+            }
+        }
+
+        EvaluationGuard {
+            tracker: self,
+            languages: evaluated_languages,
+        }
+    }
+
+    /// Records that the language models of the given languages have been unloaded.
+    pub(crate) fn record_eviction(&self, languages: &HashSet<Language>) {
+        let mut records = self.records.lock().unwrap();
+        for language in languages {
+            if let Some(record) = records.get_mut(language) {
+                record.is_resident = false;
+            }
+        }
+    }
+
+    /// Returns the least recently used resident languages which exceed the maximum number
+    /// of resident languages, if such a maximum is configured. Languages which are being
+    /// evaluated right now are never returned.
+    fn find_least_recently_used_languages(
+        &self,
+        records: &HashMap<Language, UsageRecord>,
+    ) -> HashSet<Language> {
+        let maximum_resident_languages = match self.maximum_resident_languages {
+            Some(maximum_resident_languages) => maximum_resident_languages,
+            None => return HashSet::new(),
+        };
+        let mut resident_languages = records
+            .iter()
+            .filter(|(_, record)| record.is_resident && record.evaluation_count == 0)
+            .map(|(language, record)| (record.last_used, *language))
+            .collect::<Vec<_>>();
+        let resident_language_count = resident_languages.len()
+            + records
+                .values()
+                .filter(|record| record.is_resident && record.evaluation_count > 0)
+                .count();
+
+        if resident_language_count <= maximum_resident_languages {
+            return HashSet::new();
+        }
+
+        resident_languages.sort();
+        resident_languages
+            .into_iter()
+            .take(resident_language_count - maximum_resident_languages)
+            .map(|(_, language)| language)
+            .collect()
+    }
+
+    /// Records that the given language has been the most likely language of a text.
+    pub(crate) fn record_detection(&self, language: Language) {
+        if let Some(record) = self.records.lock().unwrap().get_mut(&language) {
+            record.detection_count += 1;
+        }
+    }
+
+    /// Returns the usage of all languages, the most frequently detected languages first.
+    pub(crate) fn usage(&self) -> Vec<LanguageUsage> {
+        let now = Instant::now();
+        let mut usage = self
+            .records
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(language, record)| LanguageUsage {
+                language: *language,
+                detection_count: record.detection_count,
+                idle_time: now.saturating_duration_since(record.last_used),
+            })
+            .collect::<Vec<_>>();
+
+        usage.sort_by(|first, second| {
+            second
+                .detection_count
+                .cmp(&first.detection_count)
+                .then(first.language.cmp(&second.language))
+        });
+
+        usage
+    }
+
+    /// Passes the resident languages whose language models have not been used for at least
+    /// the given time to the given function in order to unload their models and returns them.
+    /// Languages whose models have never been loaded or have been evicted already are skipped.
+    ///
+    /// Languages which are being evaluated right now are never unloaded. The usage records
+    /// stay locked while the models are unloaded, so that no other evaluation can start
+    /// to use them meanwhile.
+    pub(crate) fn evict_idle_languages(
+        &self,
+        maximum_idle_time: Duration,
+        unload_language_models: impl FnOnce(&HashSet<Language>),
+    ) -> HashSet<Language> {
+        let now = Instant::now();
+        let mut records = self.records.lock().unwrap();
+        let idle_languages = records
+            .iter()
+            .filter(|(_, record)| {
+                record.is_resident
+                    && record.evaluation_count == 0
+                    && now.saturating_duration_since(record.last_used) >= maximum_idle_time
+            })
+            .map(|(language, _)| *language)
+            .collect::<HashSet<_>>();
+
+        if !idle_languages.is_empty() {
+            unload_language_models(&idle_languages);
+            for language in idle_languages.iter() {
+                if let Some(record) = records.get_mut(language) {
+                    record.is_resident = false;
+                }
+            }
+        }
+
+        idle_languages
+    }
+
+    /// Evicts the idle languages as described for [LanguageUsageTracker::evict_idle_languages]
+    /// if automatic eviction is enabled and the last check for idle languages is long enough
+    /// ago, otherwise the given function is not called.
+    pub(crate) fn evict_languages_due_for_eviction(
+        &self,
+        unload_language_models: impl FnOnce(&HashSet<Language>),
+    ) {
+        let maximum_idle_time = match self.maximum_idle_time {
+            Some(maximum_idle_time) => maximum_idle_time,
+            None => return,
+        };
+        let check_interval = maximum_idle_time.min(EVICTION_CHECK_INTERVAL);
+
+        {
+            let mut last_eviction_check = self.last_eviction_check.lock().unwrap();
+            if last_eviction_check.elapsed() < check_interval {
+                return;
+            }
+            *last_eviction_check = Instant::now();
+        }
+
+        self.evict_idle_languages(maximum_idle_time, unload_language_models);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::language::Language::{Dutch, English, French, German};
+    use std::thread::sleep;
+
+    #[test]
+    fn assert_language_usage_is_tracked() {
+        let tracker = LanguageUsageTracker::new(&hashset!(English, French, German), None, None);
+
+        tracker.record_detection(German);
+        tracker.record_detection(German);
+        tracker.record_detection(English);
+        tracker.record_detection(Language::Dutch);
+
+        let usage = tracker.usage();
+
+        assert_eq!(
+            usage
+                .iter()
+                .map(|it| (it.language(), it.detection_count()))
+                .collect::<Vec<_>>(),
+            vec![(German, 2), (English, 1), (French, 0)]
+        );
+    }
+
+    #[test]
+    fn assert_idle_languages_are_evicted() {
+        let tracker =
+            LanguageUsageTracker::new(&hashset!(English, French, German, Dutch), None, None);
+        let mut evicted_languages = HashSet::new();
+
+        drop(tracker.start_evaluation(&hashset!(French), |_| {}));
+        sleep(Duration::from_millis(20));
+        drop(tracker.start_evaluation(&hashset!(English), |_| {}));
+        let _german_guard = tracker.start_evaluation(&hashset!(German), |_| {});
+
+        assert_eq!(
+            tracker.evict_idle_languages(Duration::from_millis(20), |languages| {
+                evicted_languages.extend(languages.iter().copied())
+            }),
+            hashset!(French)
+        );
+        assert_eq!(evicted_languages, hashset!(French));
+        assert!(tracker
+            .evict_idle_languages(Duration::ZERO, |languages| {
+                assert_eq!(languages, &hashset!(English))
+            })
+            .contains(&English));
+
+        tracker.evict_languages_due_for_eviction(|_| panic!("automatic eviction is disabled"));
+    }
+
+    #[test]
+    fn assert_languages_are_due_for_eviction_after_check_interval() {
+        let tracker = LanguageUsageTracker::new(
+            &hashset!(English, French),
+            Some(Duration::from_millis(20)),
+            None,
+        );
+        let mut evicted_languages = HashSet::new();
+
+        drop(tracker.start_evaluation(&hashset!(French), |_| {}));
+        tracker.evict_languages_due_for_eviction(|_| panic!("check interval has not passed"));
+
+        sleep(Duration::from_millis(20));
+        drop(tracker.start_evaluation(&hashset!(English), |_| {}));
+
+        tracker.evict_languages_due_for_eviction(|languages| {
+            evicted_languages.extend(languages.iter().copied())
+        });
+        assert_eq!(evicted_languages, hashset!(French));
+
+        tracker.evict_languages_due_for_eviction(|_| panic!("check interval has not passed"));
+    }
+
+    #[test]
+    fn assert_least_recently_used_languages_are_evicted() {
+        let tracker =
+            LanguageUsageTracker::new(&hashset!(English, French, German, Dutch), None, Some(2));
+        let mut evicted_languages = HashSet::new();
+
+        let english_guard = tracker.start_evaluation(&hashset!(English), |languages| {
+            evicted_languages.extend(languages.iter().copied())
+        });
+        for language in [French, German] {
+            sleep(Duration::from_millis(1));
+            drop(tracker.start_evaluation(&hashset!(language), |languages| {
+                evicted_languages.extend(languages.iter().copied())
+            }));
+        }
+
+        assert_eq!(evicted_languages, hashset!(French));
+
+        drop(english_guard);
+        sleep(Duration::from_millis(1));
+        let _dutch_guard = tracker.start_evaluation(&hashset!(Dutch), |languages| {
+            evicted_languages.extend(languages.iter().copied())
+        });
+
+        assert_eq!(evicted_languages, hashset!(French, English));
+    }
+}
@@ -23,10 +23,17 @@ use serde::{Deserialize, Serialize};
 
 use wasm_bindgen::prelude::*;
 
-use crate::builder::{MINIMUM_RELATIVE_DISTANCE_MESSAGE, MISSING_LANGUAGE_MESSAGE};
+use crate::builder::{
+    ALPHABET_DOMINANCE_RATIO_MESSAGE, MINIMUM_RELATIVE_DISTANCE_MESSAGE, MISSING_LANGUAGE_MESSAGE,
+};
+use crate::consensus::{CHUNK_SIZE_MESSAGE, MAXIMUM_DISAGREEMENT_MESSAGE};
+use crate::limits::{MAXIMUM_INPUT_BYTES_MESSAGE, MAXIMUM_NGRAM_COUNT_MESSAGE};
+use crate::profile::{WINDOW_SIZE_MESSAGE, WINDOW_STEP_MESSAGE};
+use crate::rules::MISSING_LANGUAGE_MESSAGE as SCRIPT_RULE_MISSING_LANGUAGE_MESSAGE;
 use crate::{
-    IsoCode639_1, IsoCode639_3, Language, LanguageDetector as Detector,
-    LanguageDetectorBuilder as Builder,
+    CleanupPolicy, IsoCode639_1, IsoCode639_3, Language, LanguageDetector as Detector,
+    LanguageDetectorBuilder as Builder, MarkupStripper, NormalizationForm,
+    ScriptRuleDetector as RuleDetector,
 };
 
 /// This struct configures and creates an instance of [LanguageDetector].
@@ -59,6 +66,45 @@ pub struct DetectionResult {
     pub language: String,
 }
 
+/// This struct describes the outcome of detecting the language of a long text chunk by chunk.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct ConsensusResult {
+    /// Returns the language detected in most of the chunks.
+    pub language: Option<String>,
+    /// Returns the language detected for each chunk in the order of the chunks.
+    pub chunkLanguages: Vec<Option<String>>,
+    /// Returns the share of chunks agreeing with the consensus language.
+    pub agreement: f64,
+    /// Returns whether the text is likely written in more than one language.
+    pub isMixed: bool,
+}
+
+/// This struct describes a window of consecutive words within a document,
+/// together with the confidence in the language of the entire document.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct ConfidenceWindow {
+    /// Returns the byte index of the start of the window within the document.
+    pub startIndex: usize,
+    /// Returns the byte index of the end of the window within the document.
+    pub endIndex: usize,
+    /// Returns the number of words in the window.
+    pub wordCount: usize,
+    /// Returns the confidence value of the language of the entire document for this window.
+    pub confidence: f64,
+    /// Returns the most likely language of the text of this window.
+    pub mostLikelyLanguage: Option<String>,
+}
+
+/// This struct describes how the confidence in the language of a document develops
+/// over the course of the document.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct ConfidenceProfile {
+    /// Returns the most likely language of the entire document.
+    pub language: Option<String>,
+    /// Returns the windows in the order of their occurrence in the document.
+    pub windows: Vec<ConfidenceWindow>,
+}
+
 #[wasm_bindgen]
 impl LanguageDetectorBuilder {
     /// Creates and returns an instance of `LanguageDetectorBuilder` with all built-in languages.
@@ -226,6 +272,216 @@ impl LanguageDetectorBuilder {
         Ok(self.clone())
     }
 
+    /// Sets the share of words that the most frequent alphabet of the input text
+    /// must at least be written in so that only the languages using this alphabet
+    /// are considered. Below this ratio, the languages of the second most frequent
+    /// alphabet remain candidates as well. A ratio of 0.0 restores the default behavior.
+    ///
+    /// ⚠ Throws an error if `ratio` is smaller than 0.0 or greater than 1.0.
+    pub fn withAlphabetDominanceRatio(
+        &mut self,
+        ratio: f64,
+    ) -> Result<LanguageDetectorBuilder, JsValue> {
+        if !(0.0..=1.0).contains(&ratio) {
+            return Err(JsValue::from(ALPHABET_DOMINANCE_RATIO_MESSAGE));
+        }
+        self.builder.with_alphabet_dominance_ratio(ratio);
+        Ok(self.clone())
+    }
+
+    /// Sets the minimum number of distinct trigrams, quadrigrams and fivegrams of the input
+    /// text that must occur in a language's models so that this language can be detected
+    /// at all. Languages with fewer
+    /// matched ngrams receive a confidence value of 0.0.
+    pub fn withMinimumMatchedNgrams(&mut self, count: u32) -> Self {
+        self.builder.with_minimum_matched_ngrams(count);
+        self.clone()
+    }
+
+    /// Sets the number of characters below which input texts are evaluated with unigrams,
+    /// bigrams and trigrams only, together with the rule-based filter engine.
+    pub fn withShortTextThreshold(&mut self, characterCount: usize) -> Self {
+        self.builder.with_short_text_threshold(characterCount);
+        self.clone()
+    }
+
+    /// Sets the maximum size of an input text in bytes. Texts exceeding this limit
+    /// are treated as texts whose language cannot be detected.
+    ///
+    /// ⚠ Throws an error if `bytes` is zero.
+    pub fn withMaximumInputBytes(
+        &mut self,
+        bytes: usize,
+    ) -> Result<LanguageDetectorBuilder, JsValue> {
+        if bytes == 0 {
+            return Err(JsValue::from(MAXIMUM_INPUT_BYTES_MESSAGE));
+        }
+        self.builder.with_maximum_input_bytes(bytes);
+        Ok(self.clone())
+    }
+
+    /// Sets the maximum number of ngrams of all lengths which are looked up in the
+    /// language models for a single input text. Texts exceeding this limit are treated
+    /// as texts whose language cannot be detected.
+    ///
+    /// ⚠ Throws an error if `count` is zero.
+    pub fn withMaximumNgramCount(
+        &mut self,
+        count: usize,
+    ) -> Result<LanguageDetectorBuilder, JsValue> {
+        if count == 0 {
+            return Err(JsValue::from(MAXIMUM_NGRAM_COUNT_MESSAGE));
+        }
+        self.builder.with_maximum_ngram_count(count);
+        Ok(self.clone())
+    }
+
+    /// Configures `LanguageDetectorBuilder` to keep script-specific characters within words:
+    /// geresh and gershayim in Hebrew texts, the zero-width non-joiner in texts written in
+    /// Arabic script and apostrophes in texts written in Latin script.
+    pub fn withRecommendedCleanupPolicy(&mut self) -> Self {
+        self.builder
+            .with_cleanup_policy(CleanupPolicy::recommended());
+        self.clone()
+    }
+
+    /// Configures `LanguageDetectorBuilder` to normalize homoglyphs of the Latin, Cyrillic
+    /// and Greek alphabets within words before the rules of the rule-based filter engine
+    /// are evaluated.
+    pub fn withHomoglyphNormalization(&mut self) -> Self {
+        self.builder.with_homoglyph_normalization();
+        self.clone()
+    }
+
+    /// Configures `LanguageDetectorBuilder` to remove the given tokens, such as brand names,
+    /// product SKUs or code identifiers, from the input text before its language is detected.
+    /// Tokens are matched case-insensitively and only as a whole, not as part of longer words.
+    pub fn withIgnoredTokens(&mut self, tokens: Box<[JsValue]>) -> Self {
+        let tokens = tokens.iter().filter_map(|it| it.as_string()).collect_vec();
+        self.builder
+            .with_ignored_tokens(&tokens.iter().map(|it| it.as_str()).collect_vec());
+        self.clone()
+    }
+
+    /// Configures `LanguageDetectorBuilder` to bring the input text into the given Unicode
+    /// normalization form before it is split into words. The form is either `NFC` or `NFKC`.
+    ///
+    /// ⚠ Throws an error if `form` is not supported.
+    pub fn withUnicodeNormalization(
+        &mut self,
+        form: &str,
+    ) -> Result<LanguageDetectorBuilder, JsValue> {
+        let form = match form.to_uppercase().as_str() {
+            "NFC" => NormalizationForm::Nfc,
+            "NFKC" => NormalizationForm::Nfkc,
+            _ => {
+                return Err(JsValue::from(format!(
+                    "Normalization form '{}' is not supported",
+                    form
+                )))
+            }
+        };
+        self.builder.with_unicode_normalization(form);
+        Ok(self.clone())
+    }
+
+    /// Configures `LanguageDetectorBuilder` to strip HTML and Markdown markup, such as tags,
+    /// entities, URLs and code, from the input text before its language is detected.
+    pub fn withMarkupStripping(&mut self) -> Self {
+        self.builder.with_preprocessor(MarkupStripper);
+        self.clone()
+    }
+
+    /// Configures `LanguageDetectorBuilder` to remove emoji, pictographs and all other
+    /// symbols from the input text before it is split into words.
+    pub fn withSymbolFiltering(&mut self) -> Self {
+        self.builder.with_symbol_filtering();
+        self.clone()
+    }
+
+    /// Configures `LanguageDetectorBuilder` to compute the confidence values of all languages
+    /// from the ngram models even if the language of a text is identified unambiguously
+    /// by the rule engine. The identified language still receives the value 1.0, so that
+    /// the values of all languages sum to more than 1.0.
+    pub fn withGradedRuleConfidences(&mut self) -> Self {
+        self.builder.with_graded_rule_confidences();
+        self.clone()
+    }
+
+    /// Configures `LanguageDetectorBuilder` to remove bracketed annotations, timestamps,
+    /// speaker labels and filler words from speech transcripts before detection.
+    pub fn withTranscriptPreprocessing(&mut self) -> Self {
+        self.builder.with_transcript_preprocessing();
+        self.clone()
+    }
+
+    /// Configures `LanguageDetectorBuilder` to decapitalize all-caps text with care
+    /// for the Turkish and Azerbaijani `İ`.
+    pub fn withAllCapsHandling(&mut self) -> Self {
+        self.builder.with_all_caps_handling();
+        self.clone()
+    }
+
+    /// Configures `LanguageDetectorBuilder` to restore the letter `ß` from `SS`
+    /// in all-caps text if German is a candidate language.
+    pub fn withEszettRestoration(&mut self) -> Self {
+        self.builder.with_eszett_restoration();
+        self.clone()
+    }
+
+    /// Configures `LanguageDetectorBuilder` to fold the Cyrillic letters which are
+    /// typically confused when recognizing scanned all-caps documents.
+    pub fn withCyrillicOcrNormalization(&mut self) -> Self {
+        self.builder.with_cyrillic_ocr_normalization();
+        self.clone()
+    }
+
+    /// Appends the given language to the end of the fallback chain, so that it is returned
+    /// by `detectLanguageOf` whenever no language can be detected otherwise.
+    ///
+    /// ⚠ Throws an error if `language` is not supported.
+    pub fn withFallbackLanguage(
+        &mut self,
+        language: &str,
+    ) -> Result<LanguageDetectorBuilder, JsValue> {
+        match Language::from_str(language) {
+            Ok(lang) => {
+                self.builder.with_fallback_language(lang);
+                Ok(self.clone())
+            }
+            Err(_) => Err(JsValue::from(format!(
+                "Language '{}' is not supported",
+                language
+            ))),
+        }
+    }
+
+    /// Configures `LanguageDetectorBuilder` to use the given model of the given language
+    /// and ngram length instead of the bundled one.
+    ///
+    /// The bytes must be the contents of a Brotli-compressed binary model file such as
+    /// `trigrams.bin.br`, e.g. fetched asynchronously from a server before the detector
+    /// is built. The trigram model of a language must be passed first.
+    ///
+    /// ⚠ Throws an error if `language` is not supported or if the bytes are not a valid model.
+    pub fn withCustomModelBytes(
+        &mut self,
+        language: &str,
+        ngramLength: usize,
+        bytes: Vec<u8>,
+    ) -> Result<LanguageDetectorBuilder, JsValue> {
+        let lang = Language::from_str(language)
+            .map_err(|_| JsValue::from(format!("Language '{}' is not supported", language)))?;
+
+        match self
+            .builder
+            .add_custom_model_bytes(lang, ngramLength, bytes)
+        {
+            Ok(()) => Ok(self.clone()),
+            Err(error) => Err(JsValue::from(error.to_string())),
+        }
+    }
+
     /// Configures `LanguageDetectorBuilder` to preload all language models when creating
     /// the instance of [LanguageDetector].
     ///
@@ -255,6 +511,29 @@ impl LanguageDetectorBuilder {
         self.clone()
     }
 
+    /// Configures `LanguageDetectorBuilder` with settings that work well for social media
+    /// content such as tweets, comments or chat messages. This enables symbol filtering,
+    /// homoglyph normalization and all-caps handling as well.
+    pub fn forSocialMedia(&mut self) -> Self {
+        self.builder.for_social_media();
+        self.clone()
+    }
+
+    /// Configures `LanguageDetectorBuilder` with settings that work well for search queries.
+    /// This resets the short text threshold and enables all-caps handling.
+    pub fn forSearchQueries(&mut self) -> Self {
+        self.builder.for_search_queries();
+        self.clone()
+    }
+
+    /// Configures `LanguageDetectorBuilder` with settings that work well for long documents
+    /// such as articles, e-mails or books. This brings the documents into Unicode
+    /// normalization form NFKC.
+    pub fn forLongDocuments(&mut self) -> Self {
+        self.builder.for_long_documents();
+        self.clone()
+    }
+
     /// Creates and returns the configured instance of [LanguageDetector].
     pub fn build(&mut self) -> LanguageDetector {
         LanguageDetector {
@@ -265,6 +544,25 @@ impl LanguageDetectorBuilder {
 
 #[wasm_bindgen]
 impl LanguageDetector {
+    /// Loads the language models of the given `languages` in advance, so that the first
+    /// detection involving them does not suffer from the latency of lazy loading.
+    /// Languages which this detector has not been built from are ignored.
+    pub fn warmUp(&self, languages: Box<[JsValue]>) {
+        let selected_languages = languages
+            .iter()
+            .filter_map(|it| it.as_string())
+            .filter_map(|it| Language::from_str(&it).ok())
+            .collect_vec();
+
+        self.detector.warm_up(&selected_languages);
+    }
+
+    /// Clears all language models loaded by this detector and frees the memory
+    /// consumed by them. The models are reloaded on demand.
+    pub fn unloadLanguageModels(&self) {
+        self.detector.unload_language_models();
+    }
+
     /// Detects the language of given input text.
     /// If the language cannot be reliably detected, `undefined` is returned.
     pub fn detectLanguageOf(&self, text: &str) -> Option<String> {
@@ -274,6 +572,34 @@ impl LanguageDetector {
         }
     }
 
+    /// Detects the language of given input text like `detectLanguageOf` does,
+    /// but only chooses among the given `languages` for this single call.
+    /// Languages which this detector has not been built from are ignored.
+    pub fn detectLanguageOfWithLanguages(
+        &self,
+        text: &str,
+        languages: Box<[JsValue]>,
+    ) -> Option<String> {
+        let selected_languages = languages
+            .iter()
+            .filter_map(|it| it.as_string())
+            .filter_map(|it| Language::from_str(&it).ok())
+            .collect_vec();
+
+        self.detector
+            .detect_language_of_with_languages(text, &selected_languages)
+            .map(|language| language.to_string())
+    }
+
+    /// Detects the language of given input text like `detectLanguageOf` does,
+    /// but throws an error if the text exceeds one of the configured limits.
+    pub fn tryDetectLanguageOf(&self, text: &str) -> Result<Option<String>, JsValue> {
+        match self.detector.try_detect_language_of(text) {
+            Ok(language) => Ok(language.map(|it| it.to_string())),
+            Err(error) => Err(JsValue::from(error.to_string())),
+        }
+    }
+
     /// Attempts to detect multiple languages in mixed-language text.
     ///
     /// This feature is experimental and under continuous development.
@@ -297,6 +623,118 @@ impl LanguageDetector {
         serde_wasm_bindgen::to_value(&detection_results).unwrap()
     }
 
+    /// Returns `true` if any word of the given text mixes visually identical characters
+    /// of the Latin, Cyrillic and Greek alphabets.
+    pub fn containsHomoglyphMixing(&self, text: &str) -> bool {
+        self.detector.contains_homoglyph_mixing(text)
+    }
+
+    /// Detects the alphabet most of the words of the given text are written in.
+    /// If no single alphabet prevails, `undefined` is returned.
+    pub fn detectScriptOf(&self, text: &str) -> Option<String> {
+        self.detector
+            .detect_script_of(text)
+            .map(|alphabet| format!("{alphabet:?}"))
+    }
+
+    /// Detects the likely linguistic origin of the given personal or company name.
+    /// If the origin cannot be reliably determined, `undefined` is returned.
+    pub fn detectOriginOfName(&self, name: &str) -> Option<String> {
+        self.detector
+            .detect_origin_of_name(name)
+            .map(|language| language.to_string())
+    }
+
+    /// Detects the language of a long text by splitting it into chunks of roughly
+    /// `chunkSize` characters and determining the language detected in most of the chunks.
+    /// If the share of chunks disagreeing with this language exceeds `maximumDisagreement`,
+    /// the text is flagged as mixed.
+    ///
+    /// ⚠ Throws an error if `chunkSize` is zero or if `maximumDisagreement`
+    /// is smaller than 0.0 or greater than 1.0.
+    pub fn detectLanguageByConsensusOf(
+        &self,
+        text: &str,
+        chunkSize: usize,
+        maximumDisagreement: f64,
+    ) -> Result<JsValue, JsValue> {
+        if chunkSize == 0 {
+            return Err(JsValue::from(CHUNK_SIZE_MESSAGE));
+        }
+        if !(0.0..=1.0).contains(&maximumDisagreement) {
+            return Err(JsValue::from(MAXIMUM_DISAGREEMENT_MESSAGE));
+        }
+
+        let result =
+            self.detector
+                .detect_language_by_consensus_of(text, chunkSize, maximumDisagreement);
+
+        let consensus_result = ConsensusResult {
+            language: result.language().map(|it| it.to_string()),
+            chunkLanguages: result
+                .chunk_languages()
+                .iter()
+                .map(|language| language.map(|it| it.to_string()))
+                .collect_vec(),
+            agreement: result.agreement(),
+            isMixed: result.is_mixed(),
+        };
+
+        Ok(serde_wasm_bindgen::to_value(&consensus_result).unwrap())
+    }
+
+    /// Returns the names of the features computed by `computeFeatureVector`,
+    /// in the same order as the features.
+    pub fn featureNames(&self) -> JsValue {
+        serde_wasm_bindgen::to_value(&self.detector.feature_names()).unwrap()
+    }
+
+    /// Converts the given text into the fixed-length feature vector that this detector
+    /// bases its decision on, so that a downstream classifier can be trained on top of it.
+    pub fn computeFeatureVector(&self, text: &str) -> Vec<f64> {
+        self.detector.compute_feature_vector(text)
+    }
+
+    /// Computes how the confidence in the language of a long document develops over the
+    /// course of the document. The document is split into windows of `windowSize` consecutive
+    /// words, each window starting `step` words after the previous one.
+    ///
+    /// ⚠ Throws an error if `windowSize` or `step` is zero.
+    pub fn computeConfidenceProfile(
+        &self,
+        text: &str,
+        windowSize: usize,
+        step: usize,
+    ) -> Result<JsValue, JsValue> {
+        if windowSize == 0 {
+            return Err(JsValue::from(WINDOW_SIZE_MESSAGE));
+        }
+        if step == 0 {
+            return Err(JsValue::from(WINDOW_STEP_MESSAGE));
+        }
+
+        let profile = self
+            .detector
+            .compute_confidence_profile(text, windowSize, step);
+
+        let confidence_profile = ConfidenceProfile {
+            language: profile.language().map(|it| it.to_string()),
+            windows: profile
+                .windows()
+                .iter()
+                .map(|window| ConfidenceWindow {
+                    startIndex: window.start_index(),
+                    endIndex: window.end_index(),
+                    wordCount: window.word_count(),
+                    confidence: window.confidence(),
+                    mostLikelyLanguage: window.most_likely_language().map(|it| it.to_string()),
+                })
+                .collect_vec(),
+        };
+
+        Ok(serde_wasm_bindgen::to_value(&confidence_profile).unwrap())
+    }
+
     /// Computes confidence values for each language supported by this detector for the given
     /// input text. These values denote how likely it is that the given text has been written
     /// in any of the languages supported by this detector.
@@ -307,7 +745,8 @@ impl LanguageDetector {
     /// Each value is a probability between 0.0 and 1.0. The probabilities of all languages will
     /// sum to 1.0. If the language is unambiguously identified by the rule engine, the value
     /// 1.0 will always be returned for this language. The other languages will receive a value
-    /// of 0.0.
+    /// of 0.0, unless graded confidence values have been enabled. In this case, the values
+    /// of all languages sum to more than 1.0.
     pub fn computeLanguageConfidenceValues(&self, text: &str) -> JsValue {
         let confidence_values = self
             .detector
@@ -338,4 +777,59 @@ impl LanguageDetector {
             ))),
         }
     }
+
+    /// Returns an array of objects describing every language supported by this detector,
+    /// sorted by language. Each object comprises the language's ISO codes, its alphabets,
+    /// the sizes of its embedded ngram models and the provenance of its training corpus,
+    /// which is `null` if unknown.
+    ///
+    /// ⚠ Throws an error if the report cannot be converted into JavaScript objects.
+    pub fn coverageReport(&self) -> Result<JsValue, JsError> {
+        serde_wasm_bindgen::to_value(&self.detector.coverage_report())
+            .map_err(|error| JsError::new(&error.to_string()))
+    }
+}
+
+/// This struct detects the language of given input text by its script and by characters
+/// unique to a language only, without loading any language models.
+#[wasm_bindgen]
+pub struct ScriptRuleDetector {
+    detector: RuleDetector,
+}
+
+#[wasm_bindgen]
+impl ScriptRuleDetector {
+    /// Creates and returns an instance of `ScriptRuleDetector` with all built-in languages.
+    pub fn fromAllLanguages() -> Self {
+        ScriptRuleDetector {
+            detector: RuleDetector::from_all_languages(),
+        }
+    }
+
+    /// Creates and returns an instance of `ScriptRuleDetector` with the specified `languages`.
+    ///
+    /// ⚠ Throws an error if no `languages` are specified.
+    pub fn fromLanguages(languages: Box<[JsValue]>) -> Result<ScriptRuleDetector, JsValue> {
+        let selected_languages = languages
+            .iter()
+            .filter_map(|it| it.as_string())
+            .filter_map(|it| Language::from_str(&it).ok())
+            .collect_vec();
+
+        if selected_languages.is_empty() {
+            return Err(JsValue::from(SCRIPT_RULE_MISSING_LANGUAGE_MESSAGE));
+        }
+
+        Ok(ScriptRuleDetector {
+            detector: RuleDetector::from_languages(&selected_languages),
+        })
+    }
+
+    /// Detects the language of given input text by its script and unique characters.
+    /// If the language cannot be detected this way, `undefined` is returned.
+    pub fn detectLanguageOf(&self, text: &str) -> Option<String> {
+        self.detector
+            .detect_language_of(text)
+            .map(|language| language.to_string())
+    }
 }
@@ -24,11 +24,16 @@ use brotli::CompressorWriter;
 use itertools::Itertools;
 use regex::Regex;
 
+use crate::anonymize::{anonymize, AnonymizationFilter};
 use crate::constant::{MULTIPLE_WHITESPACE, NUMBERS, PUNCTUATION};
+use crate::json::load_json;
 use crate::model::TrainingDataLanguageModel;
 use crate::ngram::Ngram;
 use crate::Language;
 
+pub(crate) const RELATED_LANGUAGE_WEIGHT_MESSAGE: &str =
+    "Weight of related language must be between 0.0 and 1.0";
+
 /// This struct creates language model files and writes them to a directory.
 pub struct LanguageModelFilesWriter;
 
@@ -60,97 +65,189 @@ impl LanguageModelFilesWriter {
         output_directory_path: &Path,
         language: &Language,
         char_class: &str,
+    ) -> io::Result<()> {
+        Self::create_and_write_anonymized_language_model_files(
+            input_file_path,
+            output_directory_path,
+            language,
+            char_class,
+            &[],
+        )
+    }
+
+    /// Creates language model files like
+    /// [LanguageModelFilesWriter::create_and_write_language_model_files] does, but scrubs
+    /// the given kinds of personal data and noise such as email addresses or URLs from
+    /// each line of the input file before the ngrams are counted.
+    ///
+    /// Published language model files should not memorize personal data of the training
+    /// corpus. Besides, such tokens only add noise to the frequency tables.
+    /// See [AnonymizationFilter] for the available filters.
+    ///
+    /// ⚠ Panics under the same conditions as
+    /// [LanguageModelFilesWriter::create_and_write_language_model_files].
+    pub fn create_and_write_anonymized_language_model_files(
+        input_file_path: &Path,
+        output_directory_path: &Path,
+        language: &Language,
+        char_class: &str,
+        filters: &[AnonymizationFilter],
     ) -> io::Result<()> {
         check_input_file_path(input_file_path);
         check_output_directory_path(output_directory_path);
 
-        let unigram_model =
-            Self::create_language_model(input_file_path, language, 1, char_class, &hashmap!())?;
+        let lines = Self::read_training_data(input_file_path, filters)?;
+        let models = Self::create_language_models(&lines, language, char_class);
 
-        let bigram_model = Self::create_language_model(
-            input_file_path,
-            language,
-            2,
-            char_class,
-            unigram_model.absolute_frequencies.as_ref().unwrap(),
-        )?;
+        Self::write_compressed_language_models(&models, output_directory_path)
+    }
 
-        let trigram_model = Self::create_language_model(
-            input_file_path,
-            language,
-            3,
-            char_class,
-            bigram_model.absolute_frequencies.as_ref().unwrap(),
-        )?;
+    /// Bootstraps language model files for a low-resource language for which no
+    /// sufficiently large training corpus exists yet and writes them to a directory.
+    ///
+    /// The ngram probabilities learned from a small seed lexicon of the language are
+    /// linearly interpolated with those of the bundled models of a closely related language.
+    /// The resulting models are explicitly marked as bootstrapped, so they can be told apart
+    /// from models trained on a proper corpus, e.g. with
+    /// [ModelPack::bootstrapped_languages](crate::ModelPack::bootstrapped_languages) after
+    /// registering them. Detection results for such languages should be regarded as
+    /// low-confidence.
+    ///
+    /// `seed_lexicon_file_path`: The path to a txt file containing words or phrases of the
+    /// language, one per line. The assumed encoding of the txt file is UTF-8.
+    ///
+    /// `output_directory_path`: The path to an existing directory where the language model files
+    /// are to be written.
+    ///
+    /// `language`: The language for which to create language models.
+    ///
+    /// `related_language`: The language whose bundled models are interpolated with the
+    /// seed lexicon. It should share the script and much of the orthography of `language`.
+    ///
+    /// `related_language_weight`: The weight of the related language's models between
+    /// 0.0 and 1.0. The smaller the seed lexicon, the higher the weight should be.
+    ///
+    /// `char_class`: A regex character class such as `\\p{L}` to restrict the set of characters
+    /// that the language models are built from.
+    ///
+    /// Returns an error if the models of the related language are not available,
+    /// e.g. because the crate feature of the related language is disabled.
+    ///
+    /// ⚠ Panics if:
+    /// - the seed lexicon file path is not absolute or does not point to an existing txt file
+    /// - the seed lexicon file's encoding is not UTF-8
+    /// - the output directory path is not absolute or does not point to an existing directory
+    /// - the weight of the related language is not between 0.0 and 1.0
+    /// - the character class cannot be compiled to a valid regular expression
+    pub fn create_and_write_bootstrapped_language_model_files(
+        seed_lexicon_file_path: &Path,
+        output_directory_path: &Path,
+        language: &Language,
+        related_language: &Language,
+        related_language_weight: f64,
+        char_class: &str,
+    ) -> io::Result<()> {
+        check_input_file_path(seed_lexicon_file_path);
+        check_output_directory_path(output_directory_path);
 
-        let quadrigram_model = Self::create_language_model(
-            input_file_path,
-            language,
-            4,
-            char_class,
-            trigram_model.absolute_frequencies.as_ref().unwrap(),
-        )?;
+        if !(0.0..=1.0).contains(&related_language_weight) {
+            panic!("{RELATED_LANGUAGE_WEIGHT_MESSAGE}");
+        }
 
-        let fivegram_model = Self::create_language_model(
-            input_file_path,
-            language,
-            5,
-            char_class,
-            quadrigram_model.absolute_frequencies.as_ref().unwrap(),
-        )?;
+        let lines = Self::read_training_data(seed_lexicon_file_path, &[])?;
+        let seed_models = Self::create_language_models(&lines, language, char_class);
+        let mut models = vec![];
 
-        Self::write_compressed_language_model(
-            &unigram_model,
-            output_directory_path,
-            "unigrams.json",
-        )?;
-        Self::write_compressed_language_model(
-            &bigram_model,
-            output_directory_path,
-            "bigrams.json",
-        )?;
-        Self::write_compressed_language_model(
-            &trigram_model,
-            output_directory_path,
-            "trigrams.json",
-        )?;
-        Self::write_compressed_language_model(
-            &quadrigram_model,
-            output_directory_path,
-            "quadrigrams.json",
-        )?;
-        Self::write_compressed_language_model(
-            &fivegram_model,
-            output_directory_path,
-            "fivegrams.json",
-        )?;
+        for (i, seed_model) in seed_models.iter().enumerate() {
+            let related_language_json = load_json(*related_language, i + 1)?;
+            let related_language_model =
+                TrainingDataLanguageModel::from_json(&related_language_json);
 
-        Ok(())
+            models.push(TrainingDataLanguageModel::from_interpolation(
+                seed_model,
+                &related_language_model,
+                related_language_weight,
+            ));
+        }
+
+        Self::write_compressed_language_models(&models, output_directory_path)
     }
 
-    fn create_language_model(
-        input_file_path: &Path,
+    /// Creates the models of all ngram lengths from unigrams to fivegrams,
+    /// each one being based on the absolute frequencies of the next lower one.
+    fn create_language_models(
+        lines: &[String],
         language: &Language,
-        ngram_length: usize,
         char_class: &str,
-        lower_ngram_absolute_frequencies: &HashMap<Ngram, u32>,
-    ) -> io::Result<TrainingDataLanguageModel> {
+    ) -> Vec<TrainingDataLanguageModel> {
+        let no_lower_ngrams = HashMap::new();
+        let mut models: Vec<TrainingDataLanguageModel> = vec![];
+
+        for ngram_length in 1..=5 {
+            let lower_ngram_absolute_frequencies = match models.last() {
+                Some(model) => model.absolute_frequencies.as_ref().unwrap(),
+                None => &no_lower_ngrams,
+            };
+            let model = Self::create_language_model(
+                lines,
+                language,
+                ngram_length,
+                char_class,
+                lower_ngram_absolute_frequencies,
+            );
+            models.push(model);
+        }
+
+        models
+    }
+
+    fn write_compressed_language_models(
+        models: &[TrainingDataLanguageModel],
+        output_directory_path: &Path,
+    ) -> io::Result<()> {
+        for (i, model) in models.iter().enumerate() {
+            let ngram_name = Ngram::find_ngram_name_by_length(i + 1);
+            Self::write_compressed_language_model(
+                model,
+                output_directory_path,
+                &format!("{ngram_name}s.json"),
+            )?;
+        }
+
+        Ok(())
+    }
+
+    fn read_training_data(
+        input_file_path: &Path,
+        filters: &[AnonymizationFilter],
+    ) -> io::Result<Vec<String>> {
         let file = File::open(input_file_path)?;
         let reader = BufReader::new(file);
         let lines = reader
             .lines()
-            .map(|line| line.unwrap())
+            .map(|line| anonymize(&line.unwrap(), filters))
             .filter(|line| !line.trim().is_empty())
             .collect_vec();
+
+        Ok(lines)
+    }
+
+    fn create_language_model(
+        lines: &[String],
+        language: &Language,
+        ngram_length: usize,
+        char_class: &str,
+        lower_ngram_absolute_frequencies: &HashMap<Ngram, u32>,
+    ) -> TrainingDataLanguageModel {
         let lines_as_str = lines.iter().map(|line| line.as_str()).collect_vec();
 
-        Ok(TrainingDataLanguageModel::from_text(
+        TrainingDataLanguageModel::from_text(
             &lines_as_str,
             language,
             ngram_length,
             char_class,
             lower_ngram_absolute_frequencies,
-        ))
+        )
     }
 
     fn write_compressed_language_model(
@@ -504,7 +601,7 @@ mod tests {
             assert_eq!(files.len(), 5);
 
             let unigrams_file_path = files.get(4).unwrap();
-            let bigrams_file_path = files.get(0).unwrap();
+            let bigrams_file_path = files.first().unwrap();
             let trigrams_file_path = files.get(3).unwrap();
             let quadrigrams_file_path = files.get(2).unwrap();
             let fivegrams_file_path = files.get(1).unwrap();
@@ -522,19 +619,87 @@ mod tests {
             assert_file_content(fivegrams_file_path, EXPECTED_FIVEGRAM_MODEL);
         }
 
+        #[test]
+        fn test_anonymized_language_model_files_writer() {
+            let input_file = create_temp_input_file(
+                "Contact jane.doe@example.com or visit https://shop.example.com today",
+            );
+            let output_directory = tempdir().expect("Temporary directory could not be created");
+            let result = LanguageModelFilesWriter::create_and_write_anonymized_language_model_files(
+                input_file.path(),
+                output_directory.path(),
+                &Language::English,
+                "\\p{L}",
+                &[AnonymizationFilter::Email, AnonymizationFilter::Url],
+            );
+
+            assert!(result.is_ok());
+
+            let trigrams = read_file_content(&output_directory.path().join("trigrams.json.br"));
+
+            assert!(trigrams.contains("con"));
+            assert!(trigrams.contains("tod"));
+            assert!(!trigrams.contains("jan"));
+            assert!(!trigrams.contains("exa"));
+            assert!(!trigrams.contains("sho"));
+        }
+
+        #[test]
+        fn test_bootstrapped_language_model_files_writer() {
+            let input_file = create_temp_input_file("goeie\nmôre\ndankie\nasseblief");
+            let output_directory = tempdir().expect("Temporary directory could not be created");
+            let result =
+                LanguageModelFilesWriter::create_and_write_bootstrapped_language_model_files(
+                    input_file.path(),
+                    output_directory.path(),
+                    &Language::Afrikaans,
+                    &Language::Dutch,
+                    0.5,
+                    "\\p{L}",
+                );
+
+            assert!(result.is_ok());
+            assert_eq!(read_directory_content(output_directory.path()).len(), 5);
+
+            let trigrams = read_file_content(&output_directory.path().join("trigrams.json.br"));
+
+            assert!(trigrams.starts_with(r#"{"language":"AFRIKAANS""#));
+            assert!(trigrams.ends_with(r#""bootstrapped":true}"#));
+            assert!(trigrams.contains("ank"));
+            assert!(trigrams.contains("ijk"));
+        }
+
+        #[test]
+        #[should_panic(expected = "Weight of related language must be between 0.0 and 1.0")]
+        fn assert_invalid_related_language_weight_is_rejected() {
+            let input_file = create_temp_input_file("goeie");
+            let output_directory = tempdir().expect("Temporary directory could not be created");
+            let _ = LanguageModelFilesWriter::create_and_write_bootstrapped_language_model_files(
+                input_file.path(),
+                output_directory.path(),
+                &Language::Afrikaans,
+                &Language::Dutch,
+                1.5,
+                "\\p{L}",
+            );
+        }
+
         fn assert_file_names(file_path: &Path, expected_file_name: &str) {
             assert_eq!(file_path.file_name().unwrap(), expected_file_name);
         }
 
         fn assert_file_content(file_path: &Path, expected_file_content: &str) {
+            assert_eq!(read_file_content(file_path), minify(expected_file_content));
+        }
+
+        fn read_file_content(file_path: &Path) -> String {
             let compressed_file = File::open(file_path).unwrap();
             let mut uncompressed_file = Decompressor::new(compressed_file, 4096);
             let mut uncompressed_file_content = String::new();
             uncompressed_file
                 .read_to_string(&mut uncompressed_file_content)
                 .unwrap();
-
-            assert_eq!(uncompressed_file_content, minify(expected_file_content));
+            uncompressed_file_content
         }
     }
 
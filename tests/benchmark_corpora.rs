@@ -0,0 +1,49 @@
+/*
+ * Copyright © 2020-present Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Checks the corpora of the `corpora` benchmark, which are not part of the library.
+
+#![cfg(not(target_family = "wasm"))]
+
+#[path = "../benches/common/mod.rs"]
+mod common;
+
+use strum::IntoEnumIterator;
+
+use common::BenchmarkCorpus;
+use lingua::LanguageDetectorBuilder;
+
+#[test]
+fn assert_benchmark_corpora_are_labeled_correctly() {
+    let detector = LanguageDetectorBuilder::from_all_languages().build();
+
+    for corpus in BenchmarkCorpus::iter() {
+        for (language, text) in corpus.texts() {
+            assert_eq!(detector.detect_language_of(text), Some(language), "{text}");
+        }
+    }
+}
+
+#[test]
+fn assert_benchmark_corpora_have_expected_text_lengths() {
+    for (_, text) in BenchmarkCorpus::Tweets.texts() {
+        assert!(text.chars().count() <= 80, "{text}");
+    }
+    for (_, text) in BenchmarkCorpus::Paragraphs.texts() {
+        assert!(text.chars().count() >= 300, "{text}");
+    }
+    assert_eq!(BenchmarkCorpus::Cjk.languages().len(), 3);
+}
@@ -18,7 +18,7 @@
 
 use lingua::{
     ConfidenceValue, IsoCode639_1, IsoCode639_3, Language, WasmDetectionResult,
-    WasmLanguageDetectorBuilder,
+    WasmLanguageDetectorBuilder, WasmScriptRuleDetector,
 };
 use wasm_bindgen::JsValue;
 use wasm_bindgen_test::*;
@@ -265,6 +265,37 @@ fn test_detect_language() {
 
     language = detector.detectLanguageOf("ma maison est grande");
     assert_eq!(language, Some(Language::French.to_string()));
+
+    language = detector.detectLanguageOfWithLanguages(
+        "ma maison est grande",
+        Box::new([
+            JsValue::from(Language::French.to_string()),
+            JsValue::from(Language::Italian.to_string()),
+        ]),
+    );
+    assert_eq!(language, Some(Language::French.to_string()));
+
+    language = detector.detectLanguageOfWithLanguages(
+        "ma maison est grande",
+        Box::new([JsValue::from(Language::Italian.to_string())]),
+    );
+    assert_eq!(language, None);
+}
+
+#[wasm_bindgen_test]
+fn test_detect_language_by_script_rules() {
+    let detector = WasmScriptRuleDetector::fromLanguages(Box::new([
+        JsValue::from(Language::Greek.to_string()),
+        JsValue::from(Language::English.to_string()),
+    ]))
+    .unwrap();
+
+    assert_eq!(
+        detector.detectLanguageOf("Καλημέρα κόσμε"),
+        Some(Language::Greek.to_string())
+    );
+    assert_eq!(detector.detectLanguageOf("my house is big"), None);
+    assert!(WasmScriptRuleDetector::fromLanguages(Box::new([])).is_err());
 }
 
 #[wasm_bindgen_test]
@@ -0,0 +1,169 @@
+/*
+ * Copyright © 2020-present Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Quality gate for the bundled language models.
+//!
+//! Every supported language is paired with its nearest neighbors, i.e. the languages
+//! it is most easily confused with. For each pair, a detector is built from the language
+//! and its neighbors, and the language's test sentences must be classified correctly
+//! at least with the given minimum accuracy. When a new language is added, it must be
+//! registered in [QUALITY_GATES], otherwise `cargo test` fails.
+
+#![cfg(not(target_family = "wasm"))]
+
+use std::fs;
+use std::path::PathBuf;
+
+use lingua::Language::*;
+use lingua::{Language, LanguageDetectorBuilder};
+
+const QUALITY_GATES: &[(Language, &[Language], f64)] = &[
+    (Afrikaans, &[Dutch, English], 0.90),
+    (Albanian, &[Italian, Romanian], 0.95),
+    (Arabic, &[Persian, Urdu], 0.95),
+    (Armenian, &[Georgian, Russian], 0.95),
+    (Azerbaijani, &[Turkish, Kazakh], 0.95),
+    (Basque, &[Spanish, Catalan], 0.95),
+    (Belarusian, &[Russian, Ukrainian], 0.95),
+    (Bengali, &[Hindi, Marathi], 0.95),
+    (Bokmal, &[Nynorsk, Danish, Swedish], 0.70),
+    (Bosnian, &[Croatian, Slovene], 0.35),
+    (Bulgarian, &[Macedonian, Russian], 0.95),
+    (Catalan, &[Spanish, French], 0.85),
+    (Chinese, &[Japanese], 0.95),
+    (Croatian, &[Bosnian, Slovene], 0.85),
+    (Czech, &[Slovak, Polish], 0.90),
+    (Danish, &[Bokmal, Swedish], 0.95),
+    (Dutch, &[Afrikaans, German], 0.90),
+    (English, &[German, Dutch], 0.95),
+    (Esperanto, &[Spanish, Italian], 0.95),
+    (Estonian, &[Finnish, Latvian], 0.95),
+    (Finnish, &[Estonian], 0.95),
+    (French, &[Catalan, Italian], 0.95),
+    (Ganda, &[Swahili, Shona], 0.95),
+    (Georgian, &[Armenian], 0.95),
+    (German, &[Dutch, English], 0.95),
+    (Greek, &[Bulgarian], 0.95),
+    (Gujarati, &[Hindi], 0.95),
+    (Hebrew, &[Arabic], 0.95),
+    (Hindi, &[Marathi], 0.90),
+    (Hungarian, &[Finnish], 0.95),
+    (Icelandic, &[Nynorsk, Danish], 0.95),
+    (Indonesian, &[Malay, Tagalog], 0.75),
+    (Irish, &[Welsh], 0.95),
+    (Italian, &[Spanish, Latin], 0.95),
+    (Japanese, &[Chinese], 0.95),
+    (Kazakh, &[Mongolian, Russian], 0.95),
+    (Korean, &[Japanese], 0.95),
+    (Latin, &[Italian], 0.95),
+    (Latvian, &[Lithuanian], 0.95),
+    (Lithuanian, &[Latvian], 0.95),
+    (Macedonian, &[Bulgarian, Serbian], 0.95),
+    (Malay, &[Indonesian], 0.25),
+    (Maori, &[Tagalog, Indonesian], 0.95),
+    (Marathi, &[Hindi], 0.90),
+    (Mongolian, &[Kazakh, Russian], 0.95),
+    (Nynorsk, &[Bokmal, Danish], 0.85),
+    (Persian, &[Arabic, Urdu], 0.95),
+    (Polish, &[Czech, Slovak], 0.95),
+    (Portuguese, &[Spanish, Catalan], 0.95),
+    (Punjabi, &[Hindi], 0.95),
+    (Romanian, &[Italian, Latin], 0.95),
+    (Russian, &[Ukrainian, Belarusian], 0.95),
+    (Serbian, &[Macedonian, Bulgarian], 0.95),
+    (Shona, &[Zulu, Swahili], 0.95),
+    (Slovak, &[Czech], 0.95),
+    (Slovene, &[Croatian], 0.95),
+    (Somali, &[Swahili], 0.95),
+    (Sotho, &[Tswana], 0.95),
+    (Spanish, &[Portuguese, Catalan], 0.95),
+    (Swahili, &[Ganda], 0.95),
+    (Swedish, &[Danish, Bokmal], 0.95),
+    (Tagalog, &[Indonesian], 0.95),
+    (Tamil, &[Telugu], 0.95),
+    (Telugu, &[Tamil], 0.95),
+    (Thai, &[Chinese], 0.95),
+    (Tsonga, &[Zulu, Xhosa], 0.95),
+    (Tswana, &[Sotho], 0.95),
+    (Turkish, &[Azerbaijani], 0.95),
+    (Ukrainian, &[Russian, Belarusian], 0.95),
+    (Urdu, &[Persian, Arabic], 0.90),
+    (Vietnamese, &[Tagalog], 0.95),
+    (Welsh, &[Irish], 0.95),
+    (Xhosa, &[Zulu], 0.95),
+    (Yoruba, &[Ganda], 0.95),
+    (Zulu, &[Xhosa], 0.90),
+];
+
+#[test]
+fn assert_every_language_has_a_quality_gate() {
+    let gated_languages = QUALITY_GATES
+        .iter()
+        .map(|(language, _, _)| *language)
+        .collect();
+    let missing_languages = Language::all()
+        .difference(&gated_languages)
+        .cloned()
+        .collect::<Vec<_>>();
+
+    assert!(
+        missing_languages.is_empty(),
+        "languages without quality gate: {:?}",
+        missing_languages
+    );
+}
+
+#[test]
+fn assert_languages_are_distinguished_from_their_nearest_neighbors() {
+    let mut failures = vec![];
+
+    for (language, neighbors, minimum_accuracy) in QUALITY_GATES {
+        let mut languages = neighbors.to_vec();
+        languages.push(*language);
+
+        let detector = LanguageDetectorBuilder::from_languages(&languages).build();
+        let sentences = read_test_sentences(*language);
+        let correct_count = sentences
+            .iter()
+            .filter(|sentence| detector.detect_language_of(*sentence) == Some(*language))
+            .count();
+        let accuracy = correct_count as f64 / sentences.len() as f64;
+
+        if accuracy < *minimum_accuracy {
+            failures.push(format!(
+                "{language:?} vs {neighbors:?}: accuracy {accuracy:.3} < {minimum_accuracy}"
+            ));
+        }
+
+        detector.unload_language_models();
+    }
+
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
+
+fn read_test_sentences(language: Language) -> Vec<String> {
+    let file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("language-models")
+        .join(language.iso_code_639_1().to_string())
+        .join("testdata")
+        .join("sentences.txt");
+
+    fs::read_to_string(file_path)
+        .unwrap()
+        .lines()
+        .map(|line| line.to_string())
+        .collect()
+}